@@ -0,0 +1,37 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::path::PathBuf;
+use thermal_parser::command_sets::esc_pos;
+use thermal_parser::parser::Parser;
+
+//Parses every binary sample, which are mostly text and raster graphics
+fn parse_samples(c: &mut Criterion) {
+    let samples = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("sample_files")
+        .join("in");
+
+    let mut files: Vec<PathBuf> = std::fs::read_dir(samples)
+        .unwrap()
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "bin"))
+        .collect();
+    files.sort();
+
+    //The parser resets after each job, so building the command set
+    //is left out of the measurements
+    let mut parser = Parser::new(esc_pos::new());
+    let mut group = c.benchmark_group("parse");
+
+    for file in files {
+        let bytes = std::fs::read(&file).unwrap();
+        let name = file.file_stem().unwrap().to_string_lossy().to_string();
+
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_function(name, |b| b.iter(|| parser.parse_bytes(&bytes)));
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, parse_samples);
+criterion_main!(benches);
@@ -0,0 +1,291 @@
+//! Builds ESC/POS bytes from typed commands
+//!
+//! Every method writes one complete command, so the bytes parse
+//! back into the same commands with parse_esc_pos.
+//!
+//! ```
+//! use thermal_parser::builder::EscPosBuilder;
+//! use thermal_parser::commands::barcode::BarcodeType;
+//! use thermal_parser::context::TextJustify;
+//!
+//! let bytes = EscPosBuilder::new()
+//!     .initialize()
+//!     .justify(TextJustify::Center)
+//!     .bold(true)
+//!     .line("TOTAL")
+//!     .barcode(BarcodeType::Code128Auto, "12345678")
+//!     .qr("https://example.com")
+//!     .cut()
+//!     .build();
+//! ```
+use crate::commands::barcode::BarcodeType;
+use crate::constants::*;
+use crate::context::*;
+use crate::decoder::{get_codepage, Codepage};
+
+pub struct EscPosBuilder {
+    bytes: Vec<u8>,
+    //Text is encoded with the codepage the printer would decode it with
+    code_table: u8,
+    character_set: u8,
+    codepage: Codepage,
+}
+
+impl Default for EscPosBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EscPosBuilder {
+    pub fn new() -> Self {
+        Self {
+            bytes: vec![],
+            code_table: 0,
+            character_set: 0,
+            codepage: get_codepage(0, 0),
+        }
+    }
+
+    /// The bytes written so far
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn build(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Writes bytes as is, nothing is checked
+    pub fn raw(mut self, bytes: &[u8]) -> Self {
+        self.bytes.extend_from_slice(bytes);
+        self
+    }
+
+    fn command(self, command: &[u8], data: &[u8]) -> Self {
+        self.raw(command).raw(data)
+    }
+
+    //GS ( k and GS ( L style command with a two byte size
+    fn subcommand(self, command: &[u8], data: &[u8]) -> Self {
+        let size = data.len() as u16;
+        self.raw(command).raw(&size.to_le_bytes()).raw(data)
+    }
+
+    /// ESC @, this also resets the codepage
+    pub fn initialize(mut self) -> Self {
+        self.code_table = 0;
+        self.character_set = 0;
+        self.codepage = get_codepage(0, 0);
+        self.command(&[ESC, b'@'], &[])
+    }
+
+    /// Text in the current codepage. New lines are written as line feeds,
+    /// other control characters are left out and characters that the
+    /// codepage doesn't have are written as ?
+    pub fn text(mut self, text: &str) -> Self {
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                self = self.line_feed();
+            }
+
+            let line: String = line.chars().filter(|c| !c.is_control()).collect();
+            let encoded = self.codepage.encode(&line);
+            self.bytes.extend(encoded);
+        }
+        self
+    }
+
+    /// Text followed by a line feed
+    pub fn line(self, text: &str) -> Self {
+        self.text(text).line_feed()
+    }
+
+    pub fn line_feed(self) -> Self {
+        self.command(&[LF], &[])
+    }
+
+    /// ESC d, prints and feeds n lines
+    pub fn feed_lines(self, lines: u8) -> Self {
+        self.command(&[ESC, b'd'], &[lines])
+    }
+
+    /// ESC J, prints and feeds n motion units
+    pub fn feed(self, units: u8) -> Self {
+        self.command(&[ESC, b'J'], &[units])
+    }
+
+    /// ESC t
+    pub fn code_table(mut self, code_table: u8) -> Self {
+        self.code_table = code_table;
+        self.codepage = get_codepage(self.code_table, self.character_set);
+        self.command(&[ESC, b't'], &[code_table])
+    }
+
+    /// ESC R
+    pub fn international_charset(mut self, character_set: u8) -> Self {
+        self.character_set = character_set;
+        self.codepage = get_codepage(self.code_table, self.character_set);
+        self.command(&[ESC, b'R'], &[character_set])
+    }
+
+    /// ESC E
+    pub fn bold(self, enabled: bool) -> Self {
+        self.command(&[ESC, b'E'], &[enabled as u8])
+    }
+
+    /// ESC -
+    pub fn underline(self, underline: TextUnderline) -> Self {
+        let n = match underline {
+            TextUnderline::Off => 0,
+            TextUnderline::On => 1,
+            TextUnderline::Double => 2,
+        };
+        self.command(&[ESC, b'-'], &[n])
+    }
+
+    /// ESC G
+    pub fn double_strike(self, enabled: bool) -> Self {
+        self.command(&[ESC, b'G'], &[enabled as u8])
+    }
+
+    /// ESC {
+    pub fn upside_down(self, enabled: bool) -> Self {
+        self.command(&[ESC, b'{'], &[enabled as u8])
+    }
+
+    /// GS B
+    pub fn invert(self, enabled: bool) -> Self {
+        self.command(&[GS, b'B'], &[enabled as u8])
+    }
+
+    /// ESC M
+    pub fn font(self, font: Font) -> Self {
+        self.command(&[ESC, b'M'], &[font.to_raw()])
+    }
+
+    /// ESC a
+    pub fn justify(self, justify: TextJustify) -> Self {
+        let n = match justify {
+            TextJustify::Left => 0,
+            TextJustify::Center => 1,
+            TextJustify::Right => 2,
+        };
+        self.command(&[ESC, b'a'], &[n])
+    }
+
+    /// GS !, width and height are multipliers from 1 to 8
+    pub fn size(self, width: u8, height: u8) -> Self {
+        let width = width.clamp(1, 8) - 1;
+        let height = height.clamp(1, 8) - 1;
+        self.command(&[GS, b'!'], &[width << 4 | height])
+    }
+
+    /// ESC 3
+    pub fn line_spacing(self, units: u8) -> Self {
+        self.command(&[ESC, b'3'], &[units])
+    }
+
+    /// ESC 2
+    pub fn default_line_spacing(self) -> Self {
+        self.command(&[ESC, b'2'], &[])
+    }
+
+    /// GS h
+    pub fn barcode_height(self, dots: u8) -> Self {
+        self.command(&[GS, b'h'], &[dots])
+    }
+
+    /// GS w
+    pub fn barcode_width(self, dots: u8) -> Self {
+        self.command(&[GS, b'w'], &[dots])
+    }
+
+    /// GS H
+    pub fn barcode_hri(self, hri: HumanReadableInterface) -> Self {
+        let n = match hri {
+            HumanReadableInterface::None => 0,
+            HumanReadableInterface::Above => 1,
+            HumanReadableInterface::Below => 2,
+            HumanReadableInterface::Both => 3,
+        };
+        self.command(&[GS, b'H'], &[n])
+    }
+
+    /// GS k with a defined data size, data past 255 bytes is left out.
+    /// Code 128 data needs to start with a {A {B or {C code set.
+    pub fn barcode(self, kind: BarcodeType, data: &str) -> Self {
+        let m = match kind.to_raw() {
+            Some(m) => m,
+            None => return self,
+        };
+
+        let data = &data.as_bytes()[..data.len().min(255)];
+        self.command(&[GS, b'k', m, data.len() as u8], data)
+    }
+
+    /// GS ( k QR model
+    pub fn qr_model(self, model: QrModel) -> Self {
+        let n = match model {
+            QrModel::Model1 => 49,
+            QrModel::Model2 => 50,
+            QrModel::Micro => 51,
+        };
+        self.subcommand(&[GS, b'(', b'k'], &[49, 65, n, 0])
+    }
+
+    /// GS ( k QR module size in dots
+    pub fn qr_size(self, dots: u8) -> Self {
+        self.subcommand(&[GS, b'(', b'k'], &[49, 67, dots])
+    }
+
+    /// GS ( k QR error correction level
+    pub fn qr_error_correction(self, level: QrErrorCorrection) -> Self {
+        let n = match level {
+            QrErrorCorrection::L => 48,
+            QrErrorCorrection::M => 49,
+            QrErrorCorrection::Q => 50,
+            QrErrorCorrection::H => 51,
+        };
+        self.subcommand(&[GS, b'(', b'k'], &[49, 69, n])
+    }
+
+    /// GS ( k stores the QR data and prints it
+    pub fn qr(self, data: &str) -> Self {
+        //The size also holds the 3 bytes before the data
+        let data = &data.as_bytes()[..data.len().min(u16::MAX as usize - 3)];
+        let mut store = vec![49, 80, 48];
+        store.extend_from_slice(data);
+
+        self.subcommand(&[GS, b'(', b'k'], &store)
+            .subcommand(&[GS, b'(', b'k'], &[49, 81, 48])
+    }
+
+    /// GS v 0 image with one bit per dot, rows are padded to a whole
+    /// byte. Data is padded or cut to fit the width and height.
+    pub fn raster_image(self, width: u16, height: u16, data: &[u8]) -> Self {
+        let width_bytes = width.div_ceil(8);
+        let mut data = data.to_vec();
+        data.resize(width_bytes as usize * height as usize, 0);
+
+        let [xl, xh] = width_bytes.to_le_bytes();
+        let [yl, yh] = height.to_le_bytes();
+        self.command(&[GS, b'v', b'0'], &[0, xl, xh, yl, yh])
+            .raw(&data)
+    }
+
+    /// GS V full cut
+    pub fn cut(self) -> Self {
+        self.command(&[GS, b'V'], &[0])
+    }
+
+    /// GS V partial cut
+    pub fn partial_cut(self) -> Self {
+        self.command(&[GS, b'V'], &[1])
+    }
+
+    /// ESC p, pulses the cash drawer pin for on and off times
+    pub fn pulse(self, pin: u8, on: u8, off: u8) -> Self {
+        self.command(&[ESC, b'p'], &[pin, on, off])
+    }
+}
@@ -1,4 +1,5 @@
 use crate::context::{Context, TextJustify};
+use crate::diagnostic::Diagnostic;
 use crate::graphics::GraphicsCommand;
 use crate::text::TextSpan;
 use std::rc::Rc;
@@ -26,6 +27,8 @@ pub enum DeviceCommand {
     Transmit(Vec<u8>),
     MoveX(u16),
     ClearBufferGraphics,
+    //Times to run, wait time in 100ms and the mode, 1 waits for the feed button
+    ExecuteMacro(u8, u8, u8),
 }
 
 impl DeviceCommand {
@@ -49,6 +52,7 @@ impl DeviceCommand {
             Self::Transmit(_b) => "Transmit Data Back".to_string(),
             Self::MoveX(_n) => "Move Horizontally".to_string(),
             Self::ClearBufferGraphics => "Clear Buffer Graphics".to_string(),
+            Self::ExecuteMacro(r, _t, _m) => format!("Execute Macro {} Times", r),
             Self::Justify(n) => format!("Justify {:?}", n),
             Self::SetTextWidth(_) => "Scale Text Width".to_string(),
             Self::SetTextHeight(_) => "Scale Text Height".to_string(),
@@ -90,6 +94,11 @@ pub struct Command {
     pub kind: CommandType,
     pub data_kind: DataType,
     pub handler: Box<dyn CommandHandler>,
+    //Offset of the first byte of the command in the input
+    pub start: usize,
+    //Offset just past the last byte of the command, so the
+    //command's bytes are input[start..end]
+    pub end: usize,
 }
 
 impl Command {
@@ -109,6 +118,33 @@ impl Command {
             kind,
             data_kind,
             handler,
+            start: 0,
+            end: 0,
+        }
+    }
+
+    //Length of the data for commands with a fixed amount of data
+    pub fn fixed_data_len(&self) -> Option<usize> {
+        match self.data_kind {
+            DataType::Single => Some(1),
+            DataType::Double => Some(2),
+            DataType::Triple => Some(3),
+            DataType::Quad => Some(4),
+            DataType::Octet => Some(8),
+            _ => None,
+        }
+    }
+
+    //Whether the command has all of its data. Text and unknown data
+    //only ends when the next command starts, so those are never complete
+    pub fn is_complete(&self) -> bool {
+        match self.data_kind {
+            DataType::Empty => true,
+            DataType::Custom | DataType::Subcommand => self.handler.is_complete(self),
+            DataType::Text | DataType::Unknown => false,
+            _ => self
+                .fixed_data_len()
+                .is_some_and(|len| self.data.len() >= len),
         }
     }
 
@@ -209,6 +245,16 @@ pub trait CommandHandler: CloneCommandHandler {
         return false;
     }
 
+    //Whether a command with custom data has received all of it
+    fn is_complete(&self, _command: &Command) -> bool {
+        false
+    }
+
+    //Problems found with the command data while parsing
+    fn get_diagnostics(&self, _command: &Command) -> Vec<Diagnostic> {
+        vec![]
+    }
+
     //Returns the subcommand for a command, see subcommand module
     fn get_subcommand(&mut self) -> Option<Command> {
         None
@@ -0,0 +1,32 @@
+use crate::command_sets::CommandSet;
+use crate::commands::{end_print, epl, label};
+
+//EPL2 for Eltron and Zebra label printers, these should always be in alphabetical
+//order. Each label from N to P is rendered as a page, see commands::epl.
+pub fn new() -> CommandSet {
+    let commands = vec![
+        epl::ascii_text::new(),
+        epl::barcode::new(),
+        epl::box_draw::new(),
+        epl::character_set::new(),
+        epl::clear_buffer::new(),
+        epl::density::new(),
+        epl::form_length::new(),
+        epl::label_width::new(),
+        epl::line_draw_black::new(),
+        epl::options::new(),
+        epl::print::new(),
+        epl::print_direction::new(),
+        epl::qr_code::new(),
+        epl::speed::new(),
+        epl::text_data::new(),
+    ];
+
+    CommandSet {
+        default: label::ignored::new(),
+        unknown: epl::unknown::new(),
+        begin_parsing: label::begin_print::new(),
+        end_parsing: end_print::new(),
+        commands: Box::from(commands),
+    }
+}
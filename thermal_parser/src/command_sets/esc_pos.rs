@@ -6,9 +6,15 @@ pub fn new() -> CommandSet {
         barcode::new(),
         bit_image::new(),
         cancel::new(),
+        cancel_kanji_mode::new(),
+        cancel_user_character::new(),
         carriage_return::new(),
         code_2d::new(),
         default_line_spacing::new(),
+        define_bit_image::new(),
+        define_nv_bit_image::new(),
+        define_user_characters::new(),
+        execute_macro::new(),
         feed_and_cut::new(),
         formfeed::new(),
         graphics::new(),
@@ -16,10 +22,13 @@ pub fn new() -> CommandSet {
         initialize::new(),
         large_graphics::new(),
         linefeed::new(),
+        macro_definition::new(),
         paper_end_sensor::new(),
         print_and_feed_lines::new(),
         print_and_feed::new(),
         print_and_reverse_feed_lines::new(),
+        print_bit_image::new(),
+        print_nv_bit_image::new(),
         print_stop_sensor::new(),
         pulse::new(),
         raster_bit_image::new(),
@@ -40,10 +49,17 @@ pub fn new() -> CommandSet {
         set_international_charset::new(),
         set_italic::new(), //NOT part of ESCPOS
         set_justification::new(),
+        set_kanji_code_system::new(),
+        set_kanji_print_mode::new(),
+        set_kanji_quadruple::new(),
+        set_kanji_spacing::new(),
+        set_kanji_underline::new(),
+        set_left_margin::new(),
         set_line_spacing::new(),
         set_panel_buttons::new(),
         set_peripheral_device::new(),
         set_print_mode::new(),
+        set_print_area_width::new(),
         offset_vertical_pos::new(),
         set_smoothing::new(),
         set_tab_len::new(),
@@ -55,9 +71,12 @@ pub fn new() -> CommandSet {
         page_mode_print_area::new(),
         page_mode_print_direction::new(),
         page_mode_print_data::new(),
+        select_kanji_mode::new(),
         select_standard_mode::new(),
+        select_user_characters::new(),
         set_character_effects::new(),
         unknown_gs_g::new(),
+        variable_bit_image::new(),
     ];
 
     CommandSet {
@@ -13,4 +13,9 @@ pub struct CommandSet {
     pub end_parsing: Command,
 }
 
+pub mod epl;
 pub mod esc_pos;
+pub mod star_graphic;
+pub mod star_line;
+pub mod trie;
+pub mod zpl;
@@ -0,0 +1,36 @@
+use crate::commands::star;
+use crate::{command_sets::CommandSet, commands::*};
+
+//Star Graphic Mode, these should always be in alphabetical order.
+//This is the raster mode that the Star printer drivers use.
+pub fn new() -> CommandSet {
+    let commands = vec![
+        initialize::new(),
+        star::cut::new(),
+        star::open_drawer::new(),
+        star::raster_clear::new(),
+        star::raster_enter::new(),
+        star::raster_execute_eot_mode::new(),
+        star::raster_execute_ff_mode::new(),
+        star::raster_initialize::new(),
+        star::raster_line::new(),
+        star::raster_line_compressed::new(),
+        star::raster_move_vertical::new(),
+        star::raster_quit::new(),
+        star::raster_set_eot_mode::new(),
+        star::raster_set_ff_mode::new(),
+        star::raster_set_left_margin::new(),
+        star::raster_set_page_length::new(),
+        star::raster_set_print_quality::new(),
+        star::raster_set_right_margin::new(),
+        star::raster_set_top_margin::new(),
+    ];
+
+    CommandSet {
+        default: text::new(),
+        unknown: unknown::new(),
+        begin_parsing: begin_print::new(),
+        end_parsing: end_print::new(),
+        commands: Box::from(commands),
+    }
+}
@@ -0,0 +1,53 @@
+use crate::commands::star;
+use crate::{command_sets::CommandSet, commands::*};
+
+//Star Line Mode, these should always be in alphabetical order.
+//Commands that are the same as in ESC/POS are shared.
+pub fn new() -> CommandSet {
+    let commands = vec![
+        carriage_return::new(),
+        formfeed::new(),
+        horizontal_tab::new(),
+        initialize::new(),
+        linefeed::new(),
+        set_international_charset::new(),
+        set_underline::new(),
+        star::barcode::new(),
+        star::cancel_double_height::new(),
+        star::cancel_double_width::new(),
+        star::cancel_emphasis::new(),
+        star::cancel_highlight::new(),
+        star::cancel_upside_down::new(),
+        star::cut::new(),
+        star::feed::new(),
+        star::feed_lines::new(),
+        star::open_drawer::new(),
+        star::qr_print::new(),
+        star::qr_set_correction_level::new(),
+        star::qr_set_model::new(),
+        star::qr_set_size::new(),
+        star::qr_store::new(),
+        star::set_absolute_position::new(),
+        star::set_character_expansion::new(),
+        star::set_code_table::new(),
+        star::set_double_height::new(),
+        star::set_double_width::new(),
+        star::set_emphasis::new(),
+        star::set_font::new(),
+        star::set_height_expansion::new(),
+        star::set_highlight::new(),
+        star::set_justification::new(),
+        star::set_line_spacing::new(),
+        star::set_upperline::new(),
+        star::set_upside_down::new(),
+        star::set_width_expansion::new(),
+    ];
+
+    CommandSet {
+        default: text::new(),
+        unknown: unknown::new(),
+        begin_parsing: begin_print::new(),
+        end_parsing: end_print::new(),
+        commands: Box::from(commands),
+    }
+}
@@ -0,0 +1,83 @@
+use crate::command::Command;
+
+const ROOT: usize = 0;
+//Node 0 is the root, which is never a child, so 0 means no child
+const NO_NODE: usize = 0;
+
+pub enum TrieMatch {
+    //No command starts with the bytes
+    None,
+    //More bytes are needed, continue from this node
+    Partial(usize),
+    //Index of the only command that starts with the bytes
+    Command(usize),
+}
+
+/// Prefix tree of the command bytes in a command set, with a 256 entry
+/// jump table per node. A command matches once it is the only command
+/// left that starts with the bytes and all of its bytes are in.
+pub struct CommandTrie {
+    next: Vec<usize>,
+    //Number of commands that start with the bytes up to the node
+    counts: Vec<usize>,
+    //Command that ends at the node, if it is the only one
+    commands: Vec<Option<usize>>,
+}
+
+impl CommandTrie {
+    pub fn new(commands: &[Command]) -> Self {
+        let mut trie = Self {
+            next: vec![NO_NODE; 256],
+            counts: vec![0],
+            commands: vec![None],
+        };
+
+        for (index, command) in commands.iter().enumerate() {
+            let mut node = ROOT;
+
+            for byte in command.commands.iter() {
+                let slot = node * 256 + *byte as usize;
+
+                if trie.next[slot] == NO_NODE {
+                    trie.next[slot] = trie.counts.len();
+                    trie.next.extend([NO_NODE; 256]);
+                    trie.counts.push(0);
+                    trie.commands.push(None);
+                }
+
+                node = trie.next[slot];
+                trie.counts[node] += 1;
+            }
+
+            if node != ROOT {
+                trie.commands[node] = Some(index);
+            }
+        }
+
+        //Commands that share all of their bytes with another never match
+        for node in 0..trie.counts.len() {
+            if trie.counts[node] != 1 {
+                trie.commands[node] = None;
+            }
+        }
+
+        trie
+    }
+
+    pub fn root(&self) -> usize {
+        ROOT
+    }
+
+    pub fn step(&self, node: usize, byte: u8) -> TrieMatch {
+        let next = self.next[node * 256 + byte as usize];
+
+        if next == NO_NODE {
+            return TrieMatch::None;
+        }
+
+        match self.commands[next] {
+            Some(index) => TrieMatch::Command(index),
+            None => TrieMatch::Partial(next),
+        }
+    }
+}
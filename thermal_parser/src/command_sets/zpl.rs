@@ -0,0 +1,36 @@
+use crate::command_sets::CommandSet;
+use crate::commands::{end_print, label, zpl};
+
+//ZPL II for Zebra label printers, these should always be in alphabetical order.
+//Each ^XA ... ^XZ format is rendered as a label, see commands::zpl.
+pub fn new() -> CommandSet {
+    let commands = vec![
+        zpl::bar_code_defaults::new(),
+        zpl::change_default_font::new(),
+        zpl::code128::new(),
+        zpl::code39::new(),
+        zpl::comment::new(),
+        zpl::ean13::new(),
+        zpl::end_format::new(),
+        zpl::field_block::new(),
+        zpl::field_data::new(),
+        zpl::field_origin::new(),
+        zpl::field_separator::new(),
+        zpl::field_typeset::new(),
+        zpl::font::new(),
+        zpl::graphic_box::new(),
+        zpl::label_length::new(),
+        zpl::print_width::new(),
+        zpl::qr_code::new(),
+        zpl::start_format::new(),
+        zpl::upc_a::new(),
+    ];
+
+    CommandSet {
+        default: label::ignored::new(),
+        unknown: zpl::unknown::new(),
+        begin_parsing: label::begin_print::new(),
+        end_parsing: end_print::new(),
+        commands: Box::from(commands),
+    }
+}
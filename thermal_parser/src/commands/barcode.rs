@@ -13,11 +13,14 @@ use barcoders::sym::ean8::EAN8;
 use barcoders::sym::tf::TF;
 
 use crate::text::TextSpan;
+use crate::utils::barcodes::code128::{auto, gs1_128};
+use crate::utils::barcodes::databar::{gtin_hri, DataBar};
+use crate::utils::barcodes::gs1::ElementString;
 use crate::utils::barcodes::upce::UPCE;
 use crate::{command::*, constants::*, context::*, graphics::*};
 
 #[derive(Clone)]
-enum BarcodeType {
+pub enum BarcodeType {
     UpcA,
     UpcE,
     Ean13,
@@ -36,6 +39,30 @@ enum BarcodeType {
     Unknown,
 }
 
+impl BarcodeType {
+    //The m byte for the type when the data has a defined size
+    pub fn to_raw(&self) -> Option<u8> {
+        match self {
+            BarcodeType::UpcA => Some(65),
+            BarcodeType::UpcE => Some(66),
+            BarcodeType::Ean13 => Some(67),
+            BarcodeType::Ean8 => Some(68),
+            BarcodeType::Code39 => Some(69),
+            BarcodeType::Itf => Some(70),
+            BarcodeType::Nw7Codabar => Some(71),
+            BarcodeType::Code93 => Some(72),
+            BarcodeType::Code128 => Some(73),
+            BarcodeType::Gs1128 => Some(74),
+            BarcodeType::Gs1DatabarOmni => Some(75),
+            BarcodeType::Gs1DatabarTruncated => Some(76),
+            BarcodeType::Gs1DatabarLimited => Some(77),
+            BarcodeType::Gs1DatabarExpanded => Some(78),
+            BarcodeType::Code128Auto => Some(79),
+            BarcodeType::Unknown => None,
+        }
+    }
+}
+
 #[derive(Clone, PartialEq)]
 enum EncodingFunction {
     NulTerminated,
@@ -78,7 +105,7 @@ impl BarcodeHandler {
             BarcodeType::Gs1128 => "GS1 128",
             BarcodeType::Gs1DatabarOmni => "GS1 Omni",
             BarcodeType::Gs1DatabarTruncated => "GS1 Truncated",
-            BarcodeType::Gs1DatabarLimited => "GS1 Kimited",
+            BarcodeType::Gs1DatabarLimited => "GS1 Limited",
             BarcodeType::Gs1DatabarExpanded => "GS1 Expanded",
             BarcodeType::Code128Auto => "Code 128 Auto",
             BarcodeType::Unknown => "Unknown",
@@ -125,14 +152,27 @@ impl CommandHandler for BarcodeHandler {
         }
 
         match self.kind {
-            BarcodeType::Code128 => {
-                //all code128 data has two bytes that set the type, we are converting this to the barcoders format
-                let adjusted_data = data
-                    .replace("{A", "À")
-                    .replace("{B", "Ɓ")
-                    .replace("{C", "Ć");
+            BarcodeType::Code128 | BarcodeType::Gs1128 => {
+                let (adjusted_data, hri_data) = if matches!(self.kind, BarcodeType::Gs1128) {
+                    //GS1 data picks its own character sets, so any given ones are dropped
+                    let gs1_data = data.replace("{A", "").replace("{B", "").replace("{C", "");
+
+                    match ElementString::new(&gs1_data) {
+                        Ok(element_string) => (gs1_128(&element_string.data), element_string.hri),
+                        Err(error) => return self.decorate_error(error, command),
+                    }
+                } else {
+                    //all code128 data has two bytes that set the type, we are converting this to the barcoders format
+                    let adjusted_data = data
+                        .replace("{A", "À")
+                        .replace("{B", "Ɓ")
+                        .replace("{C", "Ć");
 
-                let hri_data: String = data.replace("{A", "").replace("{B", "").replace("{C", "");
+                    let hri_data: String =
+                        data.replace("{A", "").replace("{B", "").replace("{C", "");
+
+                    (adjusted_data, hri_data)
+                };
 
                 return match Code128::new(adjusted_data.to_string()) {
                     Ok(barcode) => Some(GraphicsCommand::Barcode(Barcode {
@@ -145,6 +185,23 @@ impl CommandHandler for BarcodeHandler {
                     Err(error) => self.decorate_error(error.to_string(), command),
                 };
             }
+            BarcodeType::Code128Auto => {
+                let (adjusted_data, hri_data) = match auto(data) {
+                    Ok(auto_data) => auto_data,
+                    Err(error) => return self.decorate_error(error, command),
+                };
+
+                return match Code128::new(adjusted_data) {
+                    Ok(barcode) => Some(GraphicsCommand::Barcode(Barcode {
+                        points: barcode.encode(),
+                        text: TextSpan::new_for_barcode(hri_data, context),
+                        point_width,
+                        point_height,
+                        hri,
+                    })),
+                    Err(error) => self.decorate_error(error.to_string(), command),
+                };
+            }
             BarcodeType::Nw7Codabar => {
                 return match Codabar::new(data.to_string()) {
                     Ok(barcode) => Some(GraphicsCommand::Barcode(Barcode {
@@ -249,6 +306,45 @@ impl CommandHandler for BarcodeHandler {
                     Err(error) => self.decorate_error(error.to_string(), command),
                 };
             }
+            BarcodeType::Gs1DatabarOmni | BarcodeType::Gs1DatabarTruncated => {
+                let symbol = if matches!(self.kind, BarcodeType::Gs1DatabarTruncated) {
+                    DataBar::truncated(raw_data)
+                } else {
+                    DataBar::omnidirectional(raw_data)
+                };
+
+                return match symbol {
+                    Ok(mut symbol) => Some(GraphicsCommand::Barcode(Barcode {
+                        points: symbol.rows.remove(0).modules,
+                        text: TextSpan::new_for_barcode(gtin_hri(raw_data), context),
+                        point_width,
+                        point_height,
+                        hri,
+                    })),
+                    Err(error) => self.decorate_error(error, command),
+                };
+            }
+            BarcodeType::Gs1DatabarExpanded => {
+                let element_string = match ElementString::new(data) {
+                    Ok(element_string) => element_string,
+                    Err(error) => return self.decorate_error(error, command),
+                };
+
+                return match DataBar::expanded(&element_string.data, 0) {
+                    Ok(mut symbol) => Some(GraphicsCommand::Barcode(Barcode {
+                        points: symbol.rows.remove(0).modules,
+                        text: TextSpan::new_for_barcode(element_string.hri, context),
+                        point_width,
+                        point_height,
+                        hri,
+                    })),
+                    Err(error) => self.decorate_error(error, command),
+                };
+            }
+            BarcodeType::Gs1DatabarLimited => {
+                //TODO Limited needs the table of check character patterns
+                return self.decorate_error("is not supported yet".to_string(), command);
+            }
             _ => return self.decorate_error("Unknown barcode type".to_string(), command),
         }
     }
@@ -306,12 +402,12 @@ impl CommandHandler for BarcodeHandler {
                 6 | 71 => BarcodeType::Nw7Codabar,
                 72 => BarcodeType::Code93,
                 73 => BarcodeType::Code128,
-                80 => BarcodeType::Gs1128,
-                81 => BarcodeType::Gs1DatabarOmni,
-                82 => BarcodeType::Gs1DatabarTruncated,
-                83 => BarcodeType::Gs1DatabarLimited,
-                84 => BarcodeType::Gs1DatabarExpanded,
-                85 => BarcodeType::Code128Auto,
+                74 => BarcodeType::Gs1128,
+                75 => BarcodeType::Gs1DatabarOmni,
+                76 => BarcodeType::Gs1DatabarTruncated,
+                77 => BarcodeType::Gs1DatabarLimited,
+                78 => BarcodeType::Gs1DatabarExpanded,
+                79 => BarcodeType::Code128Auto,
                 _ => BarcodeType::Unknown,
             };
 
@@ -355,6 +451,26 @@ impl CommandHandler for BarcodeHandler {
     }
 }
 
+/// Graphics for barcode data of a known type, for command
+/// sets that select the type and data a different way
+pub fn get_barcode_graphics(
+    kind: BarcodeType,
+    command: &Command,
+    context: &Context,
+) -> Option<GraphicsCommand> {
+    let handler = BarcodeHandler {
+        kind,
+        kind_id: 0,
+        encoding: EncodingFunction::Unknown,
+        capacity: 0,
+        has_capacity: false,
+        accept_data: false,
+        raw_params: vec![],
+    };
+
+    handler.get_graphics(command, context)
+}
+
 pub fn new() -> Command {
     Command::new(
         "Barcode",
@@ -1,3 +1,4 @@
+use crate::diagnostic::{Diagnostic, DiagnosticCode, Severity};
 use crate::{command::*, constants::*, context::*, graphics::*};
 
 #[derive(Clone)]
@@ -37,6 +38,18 @@ impl CommandHandler for Handler {
             ))
         }
     }
+    fn get_diagnostics(&self, command: &Command) -> Vec<Diagnostic> {
+        match self.params.first() {
+            Some(0 | 1 | 32 | 33) | None => vec![],
+            Some(m) => vec![Diagnostic::new(
+                command,
+                Severity::Warning,
+                DiagnosticCode::InvalidParameter,
+                format!("Bit image mode {} isn't 0, 1, 32 or 33", m),
+            )],
+        }
+    }
+
     fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
         let data_len = data.len();
 
@@ -88,6 +101,10 @@ impl CommandHandler for Handler {
         true
     }
 
+    fn is_complete(&self, _command: &Command) -> bool {
+        self.accept_data && self.size >= self.capacity
+    }
+
     //Used when converting commands back into other formats i.e. Thermal format
     fn get_command_bytes(&self, command: &Command) -> (Vec<u8>, Vec<u8>) {
         let mut data = self.params.clone();
@@ -0,0 +1,24 @@
+///
+/// This command turns off Kanji mode, text is decoded
+/// with the single byte codepage again.
+///
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, _command: &Command, context: &mut Context) {
+        context.text.kanji.enabled = false;
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Cancel Kanji Mode",
+        vec![FS, '.' as u8],
+        CommandType::TextStyle,
+        DataType::Empty,
+        Box::new(Handler {}),
+    )
+}
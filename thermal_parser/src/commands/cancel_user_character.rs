@@ -0,0 +1,34 @@
+///
+/// This command deletes a downloaded character from the current font,
+/// the code prints with the built in character again.
+///
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let n = *command.data.first().unwrap_or(&0u8);
+        let font = context.text.font.clone();
+        context.text.user_characters.cancel(&font, n);
+    }
+
+    fn debug(&self, command: &Command, _context: &Context) -> String {
+        format!(
+            "{} --> {}",
+            &command.name,
+            command.data.first().unwrap_or(&0u8)
+        )
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Cancel User-Defined Character",
+        vec![ESC, '?' as u8],
+        CommandType::Context,
+        DataType::Single,
+        Box::new(Handler {}),
+    )
+}
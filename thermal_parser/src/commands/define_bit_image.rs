@@ -0,0 +1,140 @@
+///
+/// This command downloads a bit image that is printed with GS /
+///
+/// GS * x y d1...d(x*y*8)
+///
+/// The image is x * 8 dots wide and y * 8 dots high,
+/// the data is sent by column. Defining an image
+/// replaces the previous one.
+///
+use crate::diagnostic::{Diagnostic, DiagnosticCode, Severity};
+use crate::{command::*, constants::*, context::*, graphics::*};
+
+#[derive(Clone)]
+struct Handler {
+    capacity: u32,
+    accept_data: bool,
+    params: Vec<u8>,
+}
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        //Empty images and images that are missing data are not defined
+        if self.capacity == 0 || command.data.len() < self.capacity as usize {
+            return;
+        }
+
+        let width = *self.params.first().unwrap_or(&0u8) as u32 * 8;
+        let height = *self.params.get(1).unwrap_or(&0u8) as u32 * 8;
+        let image_ref = ImageRef {
+            kc1: 0,
+            kc2: 0,
+            storage: ImageRefStorage::DownloadBitImage,
+        };
+
+        let graphics = GraphicsCommand::image_from_column_bytes_single_color(
+            width,
+            height,
+            (1, 1),
+            context.graphics.render_colors.color_for_number(1),
+            ImageFlow::Block,
+            &command.data,
+        );
+
+        context.graphics.stored_graphics.insert(image_ref, graphics);
+    }
+
+    fn get_diagnostics(&self, command: &Command) -> Vec<Diagnostic> {
+        if !self.accept_data {
+            vec![Diagnostic::new(
+                command,
+                Severity::Error,
+                DiagnosticCode::TruncatedCommand,
+                "The input ended before the image size".to_string(),
+            )]
+        } else if command.data.len() < self.capacity as usize {
+            vec![Diagnostic::new(
+                command,
+                Severity::Error,
+                DiagnosticCode::TruncatedCommand,
+                format!(
+                    "Expected {} bytes of image data but the input ended after {}",
+                    self.capacity,
+                    command.data.len()
+                ),
+            )]
+        } else if self.capacity == 0 {
+            vec![Diagnostic::new(
+                command,
+                Severity::Warning,
+                DiagnosticCode::InvalidSize,
+                format!(
+                    "A {}x{} bit image can't be defined",
+                    *self.params.first().unwrap_or(&0u8) as u32 * 8,
+                    *self.params.get(1).unwrap_or(&0u8) as u32 * 8
+                ),
+            )]
+        } else {
+            vec![]
+        }
+    }
+
+    fn debug(&self, command: &Command, _context: &Context) -> String {
+        format!(
+            "{} --> {}x{} dots",
+            &command.name,
+            *self.params.first().unwrap_or(&0u8) as u32 * 8,
+            *self.params.get(1).unwrap_or(&0u8) as u32 * 8
+        )
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        if !self.accept_data {
+            if data.is_empty() {
+                data.push(byte);
+                return true;
+            }
+
+            let x = data[0];
+            let y = byte;
+
+            self.capacity = x as u32 * y as u32 * 8;
+            self.params = vec![x, y];
+            self.accept_data = true;
+            data.clear();
+            return true;
+        }
+
+        if data.len() >= self.capacity as usize {
+            return false;
+        }
+        data.push(byte);
+        true
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        self.accept_data && command.data.len() >= self.capacity as usize
+    }
+
+    //Used when converting commands back into other formats i.e. Thermal format
+    fn get_command_bytes(&self, command: &Command) -> (Vec<u8>, Vec<u8>) {
+        let mut data = self.params.clone();
+        let commands = command.commands.to_vec();
+        data.extend(command.data.clone());
+        (commands, data)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Define Downloaded Bit Image",
+        vec![GS, '*' as u8],
+        CommandType::Context,
+        DataType::Custom,
+        Box::new(Handler {
+            capacity: 0,
+            accept_data: false,
+            params: vec![],
+        }),
+    )
+}
@@ -0,0 +1,171 @@
+///
+/// This command defines the NV bit images that are printed with FS p
+///
+/// FS q n [xL xH yL yH d1...dk]1...[xL xH yL yH d1...dk]n
+///
+/// Each image is (xL + xH * 256) * 8 dots wide and (yL + yH * 256) * 8
+/// dots high, the data is sent by column. The images are numbered from 1
+/// and defining them deletes all of the previous NV bit images.
+///
+/// NV bit images are kept when the printer is initialized.
+///
+use crate::diagnostic::{Diagnostic, DiagnosticCode, Severity};
+use crate::{command::*, constants::*, context::*, graphics::*};
+
+#[derive(Clone)]
+struct Handler {
+    images: u32,
+    header: u8,
+    remaining: u64,
+}
+
+//The width, height and data of each image, an image
+//that is missing data ends the list
+fn get_images(data: &[u8]) -> Vec<(u32, u32, &[u8])> {
+    let mut images = vec![];
+    let count = *data.first().unwrap_or(&0u8);
+    let mut index = 1;
+
+    for _ in 0..count {
+        let header = match data.get(index..index + 4) {
+            Some(header) => header,
+            None => break,
+        };
+        let width = (header[0] as u32 + header[1] as u32 * 256) * 8;
+        let height = (header[2] as u32 + header[3] as u32 * 256) * 8;
+        let len = usize::try_from(width as u64 * height as u64 / 8).unwrap_or(usize::MAX);
+        let start = index + 4;
+
+        let image = match data.get(start..).filter(|rest| rest.len() >= len) {
+            Some(rest) => &rest[..len],
+            None => break,
+        };
+
+        images.push((width, height, image));
+        index = start + len;
+    }
+
+    images
+}
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let stored_graphics = &mut context.graphics.stored_graphics;
+        stored_graphics.retain(|k, _| k.storage != ImageRefStorage::NvBitImage);
+
+        for (i, (width, height, data)) in get_images(&command.data).iter().enumerate() {
+            //Empty images keep their number but can't be printed
+            if *width == 0 || *height == 0 {
+                continue;
+            }
+
+            let image_ref = ImageRef {
+                kc1: i as u8 + 1,
+                kc2: 0,
+                storage: ImageRefStorage::NvBitImage,
+            };
+
+            let graphics = GraphicsCommand::image_from_column_bytes_single_color(
+                *width,
+                *height,
+                (1, 1),
+                context.graphics.render_colors.color_for_number(1),
+                ImageFlow::Block,
+                data,
+            );
+
+            stored_graphics.insert(image_ref, graphics);
+        }
+    }
+
+    fn get_diagnostics(&self, command: &Command) -> Vec<Diagnostic> {
+        let count = *command.data.first().unwrap_or(&0u8) as usize;
+        let images = get_images(&command.data);
+        let defined = images.len();
+
+        if let Some(n) = images.iter().position(|(w, h, _)| *w == 0 || *h == 0) {
+            let (width, height, _) = images[n];
+            return vec![Diagnostic::new(
+                command,
+                Severity::Warning,
+                DiagnosticCode::InvalidSize,
+                format!(
+                    "NV bit image {} is {}x{} and can't be defined",
+                    n + 1,
+                    width,
+                    height
+                ),
+            )];
+        }
+
+        if defined < count {
+            vec![Diagnostic::new(
+                command,
+                Severity::Error,
+                DiagnosticCode::TruncatedCommand,
+                format!(
+                    "Expected {} images but the data ended after {}",
+                    count, defined
+                ),
+            )]
+        } else {
+            vec![]
+        }
+    }
+
+    fn debug(&self, command: &Command, _context: &Context) -> String {
+        format!(
+            "{} --> {} images",
+            &command.name,
+            command.data.first().unwrap_or(&0u8)
+        )
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        data.push(byte);
+
+        //n
+        if data.len() == 1 {
+            self.images = byte as u32;
+            return true;
+        }
+
+        if self.remaining > 0 {
+            self.remaining -= 1;
+            return true;
+        }
+
+        //xL xH yL yH
+        self.header += 1;
+
+        if self.header == 4 {
+            let header = &data[data.len() - 4..];
+            let x = header[0] as u64 + header[1] as u64 * 256;
+            let y = header[2] as u64 + header[3] as u64 * 256;
+
+            self.remaining = x * y * 8;
+            self.images = self.images.saturating_sub(1);
+            self.header = 0;
+        }
+
+        true
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        !command.data.is_empty() && self.images == 0 && self.header == 0 && self.remaining == 0
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Define NV Bit Image",
+        vec![FS, 'q' as u8],
+        CommandType::Context,
+        DataType::Custom,
+        Box::new(Handler {
+            images: 0,
+            header: 0,
+            remaining: 0,
+        }),
+    )
+}
@@ -0,0 +1,124 @@
+///
+/// This command downloads characters for the current font.
+///
+/// ESC & y c1 c2 [x d1...d(y*x)]...
+///
+/// Every code from c1 to c2 gets a character that is x dots wide
+/// and y bytes high, the data for each character is sent by column.
+///
+/// The characters are only printed after ESC % selects them.
+///
+use crate::diagnostic::{Diagnostic, DiagnosticCode, Severity};
+use crate::text::UserCharacter;
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler {
+    characters: u32,
+    remaining: u32,
+}
+
+//The characters in the data, with the code they are defined for
+fn get_characters(data: &[u8]) -> Vec<(u8, UserCharacter)> {
+    let mut characters = vec![];
+
+    if data.len() < 3 {
+        return characters;
+    }
+
+    let column_bytes = data[0] as u32;
+    let mut index = 3;
+
+    for code in data[1]..=data[2] {
+        let width = *data.get(index).unwrap_or(&0u8) as u32;
+        let len = (width * column_bytes) as usize;
+        let bytes = data.get(index + 1..index + 1 + len).unwrap_or(&[]);
+
+        characters.push((
+            code,
+            UserCharacter::from_column_bytes(width, column_bytes, bytes),
+        ));
+        index += 1 + len;
+    }
+
+    characters
+}
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let font = context.text.font.clone();
+
+        for (code, character) in get_characters(&command.data) {
+            context.text.user_characters.define(&font, code, character);
+        }
+    }
+
+    fn debug(&self, command: &Command, _context: &Context) -> String {
+        match (command.data.get(1), command.data.get(2)) {
+            (Some(c1), Some(c2)) => format!("{} --> {} to {}", &command.name, c1, c2),
+            _ => command.name.to_string(),
+        }
+    }
+
+    fn get_diagnostics(&self, command: &Command) -> Vec<Diagnostic> {
+        let (y, c1, c2) = match command.data.get(0..3) {
+            Some(params) => (params[0], params[1], params[2]),
+            None => return vec![],
+        };
+
+        if y == 0 || y > 3 || c1 < 32 || c2 > 126 || c1 > c2 {
+            return vec![Diagnostic::new(
+                command,
+                Severity::Warning,
+                DiagnosticCode::InvalidSize,
+                format!(
+                    "User-defined characters {} to {} with a height of {} bytes can't be defined",
+                    c1, c2, y
+                ),
+            )];
+        }
+
+        vec![]
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        data.push(byte);
+
+        //y c1 c2
+        if data.len() == 3 {
+            self.characters = (byte as u32 + 1).saturating_sub(data[1] as u32);
+            return true;
+        }
+
+        if data.len() < 3 {
+            return true;
+        }
+
+        //Each character starts with its width, followed by the columns
+        if self.remaining == 0 {
+            self.remaining = byte as u32 * data[0] as u32;
+            self.characters -= 1;
+        } else {
+            self.remaining -= 1;
+        }
+
+        true
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        command.data.len() >= 3 && self.characters == 0 && self.remaining == 0
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Define User-Defined Characters",
+        vec![ESC, '&' as u8],
+        CommandType::Context,
+        DataType::Custom,
+        Box::new(Handler {
+            characters: 0,
+            remaining: 0,
+        }),
+    )
+}
@@ -0,0 +1,76 @@
+use crate::command::*;
+use crate::commands::epl::{number, parameters};
+use crate::commands::label::*;
+use crate::constants::*;
+use crate::context::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//A p1,p2,p3,p4,p5,p6,p7,"data" where p1 and p2 are the x and y, p4 the font
+//and p5 and p6 the multipliers. The quoted data is the text_data command.
+//Only normal rotation and printing are supported.
+fn size(command: &Command) -> (u32, u32) {
+    let params = parameters(&command.data);
+
+    //Font sizes in dots at 203 dpi
+    let (width, height) = match number(&params, 3) {
+        Some(1) => (8, 12),
+        Some(3) => (12, 20),
+        Some(4) => (14, 24),
+        Some(5) => (32, 48),
+        _ => (10, 16),
+    };
+
+    (
+        width * number(&params, 4).unwrap_or(1).max(1),
+        height * number(&params, 5).unwrap_or(1).max(1),
+    )
+}
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let params = parameters(&command.data);
+        let x = number(&params, 0).unwrap_or(0);
+        let y = number(&params, 1).unwrap_or(0);
+        let (width, height) = size(command);
+        let (font, _, _) = font_for_size(width, height);
+
+        set_field_origin(context, x, y);
+        context.set_font(font);
+    }
+
+    fn get_device_command(
+        &self,
+        command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        let (width, height) = size(command);
+        let (_, width_mult, height_mult) = font_for_size(width, height);
+
+        Some(vec![
+            DeviceCommand::ChangePageArea,
+            DeviceCommand::SetTextWidth(width_mult),
+            DeviceCommand::SetTextHeight(height_mult),
+        ])
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        if byte == b'"' || data.last() == Some(&LF) {
+            return false;
+        }
+
+        data.push(byte);
+        true
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "ASCII Text",
+        vec!['A' as u8],
+        CommandType::ContextControl,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,79 @@
+use crate::command::*;
+use crate::commands::barcode::BarcodeType;
+use crate::commands::epl::{number, parameters, push_line};
+use crate::commands::label::{barcode_graphics, set_position};
+use crate::constants::*;
+use crate::context::*;
+use crate::graphics::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//B p1,p2,p3,p4,p5,p6,p7,p8,"data" where p1 and p2 are the x and y, p4 the type,
+//p5 the narrow bar width, p7 the height and p8 B to print the human readable
+//text. The wide bar width and rotation aren't supported.
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let params = parameters(&command.data);
+        set_position(
+            context,
+            number(&params, 0).unwrap_or(0),
+            number(&params, 1).unwrap_or(0),
+        );
+    }
+
+    fn get_graphics(&self, command: &Command, context: &Context) -> Option<GraphicsCommand> {
+        let params = parameters(&command.data);
+
+        let kind = match params.get(3).map(|p| p.as_str()) {
+            Some("1" | "1A" | "1B" | "1C") => BarcodeType::Code128Auto,
+            Some("3" | "3C") => BarcodeType::Code39,
+            Some("9") => BarcodeType::Code93,
+            Some("2" | "2C") => BarcodeType::Itf,
+            Some("K") => BarcodeType::Nw7Codabar,
+            Some("E30") => BarcodeType::Ean13,
+            Some("E80") => BarcodeType::Ean8,
+            Some("UA0") => BarcodeType::UpcA,
+            Some("UE0") => BarcodeType::UpcE,
+            _ => {
+                return Some(GraphicsCommand::Error(format!(
+                    "Unsupported barcode type {}",
+                    params.get(3).map_or("", |p| p.as_str())
+                )))
+            }
+        };
+
+        let human_readable = match params.get(7).map(|p| p.as_str()) {
+            Some("B") => HumanReadableInterface::Below,
+            _ => HumanReadableInterface::None,
+        };
+
+        barcode_graphics(
+            kind,
+            command,
+            context,
+            params.get(8).map_or(vec![], |p| p.as_bytes().to_vec()),
+            number(&params, 4),
+            number(&params, 6),
+            human_readable,
+        )
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_line(data, byte)
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        command.data.last() == Some(&LF)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Bar Code",
+        vec!['B' as u8],
+        CommandType::Graphics,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,56 @@
+use crate::command::*;
+use crate::commands::epl::{number, parameters, push_line};
+use crate::commands::label::{box_graphics, set_position};
+use crate::constants::*;
+use crate::context::*;
+use crate::graphics::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//X p1,p2,p3,p4,p5 draws a box from p1,p2 to p4,p5 with lines p3 thick
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let params = parameters(&command.data);
+        set_position(
+            context,
+            number(&params, 0).unwrap_or(0),
+            number(&params, 1).unwrap_or(0),
+        );
+    }
+
+    fn get_graphics(&self, command: &Command, context: &Context) -> Option<GraphicsCommand> {
+        let params = parameters(&command.data);
+        let x = number(&params, 0).unwrap_or(0);
+        let y = number(&params, 1).unwrap_or(0);
+        let thickness = number(&params, 2).unwrap_or(1).max(1);
+        let width = number(&params, 3).unwrap_or(0).saturating_sub(x);
+        let height = number(&params, 4).unwrap_or(0).saturating_sub(y);
+
+        Some(box_graphics(
+            context,
+            width.max(thickness),
+            height.max(thickness),
+            thickness,
+            context.graphics.render_colors.color_for_number(1),
+        ))
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_line(data, byte)
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        command.data.last() == Some(&LF)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Box Draw",
+        vec!['X' as u8],
+        CommandType::Graphics,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,42 @@
+use crate::command::*;
+use crate::commands::epl::push_line;
+use crate::commands::label::*;
+use crate::constants::*;
+use crate::context::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//Starts a new label the same size as the previous one
+impl CommandHandler for Handler {
+    fn apply_context(&self, _command: &Command, context: &mut Context) {
+        let (width, length) = label_size(context);
+        begin_label(context, width, length);
+    }
+
+    fn get_device_command(
+        &self,
+        _command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        Some(begin_label_commands())
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_line(data, byte)
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        command.data.last() == Some(&LF)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Clear Image Buffer",
+        vec!['N' as u8],
+        CommandType::ContextControl,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,44 @@
+use crate::command::*;
+use crate::commands::epl::{number, parameters, push_line};
+use crate::commands::label::*;
+use crate::constants::*;
+use crate::context::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//Q p1,p2 where p1 is the label length and p2 the gap between labels,
+//the label starts over with the new length
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let (width, length) = label_size(context);
+        let length = number(&parameters(&command.data), 0).unwrap_or(length);
+        begin_label(context, width, length);
+    }
+
+    fn get_device_command(
+        &self,
+        _command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        Some(begin_label_commands())
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_line(data, byte)
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        command.data.last() == Some(&LF)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Set Form Length",
+        vec!['Q' as u8],
+        CommandType::ContextControl,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,43 @@
+use crate::command::*;
+use crate::commands::epl::{number, parameters, push_line};
+use crate::commands::label::*;
+use crate::constants::*;
+use crate::context::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//The label starts over with the new width, so this goes before the fields
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let (width, length) = label_size(context);
+        let width = number(&parameters(&command.data), 0).unwrap_or(width);
+        begin_label(context, width, length);
+    }
+
+    fn get_device_command(
+        &self,
+        _command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        Some(begin_label_commands())
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_line(data, byte)
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        command.data.last() == Some(&LF)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Set Label Width",
+        vec!['q' as u8],
+        CommandType::ContextControl,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
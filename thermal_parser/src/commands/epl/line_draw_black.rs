@@ -0,0 +1,53 @@
+use crate::command::*;
+use crate::commands::epl::{number, parameters, push_line};
+use crate::commands::label::{box_graphics, set_position};
+use crate::constants::*;
+use crate::context::*;
+use crate::graphics::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//LO p1,p2,p3,p4 draws a black line at p1 and p2 that is p3 wide and p4 tall
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let params = parameters(&command.data);
+        set_position(
+            context,
+            number(&params, 0).unwrap_or(0),
+            number(&params, 1).unwrap_or(0),
+        );
+    }
+
+    fn get_graphics(&self, command: &Command, context: &Context) -> Option<GraphicsCommand> {
+        let params = parameters(&command.data);
+        let width = number(&params, 2).unwrap_or(1).max(1);
+        let height = number(&params, 3).unwrap_or(1).max(1);
+
+        Some(box_graphics(
+            context,
+            width,
+            height,
+            width.max(height),
+            context.graphics.render_colors.color_for_number(1),
+        ))
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_line(data, byte)
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        command.data.last() == Some(&LF)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Line Draw Black",
+        vec!['L' as u8, 'O' as u8],
+        CommandType::Graphics,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
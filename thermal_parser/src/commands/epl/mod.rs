@@ -0,0 +1,83 @@
+//! EPL2 commands, for Eltron and Zebra label printers
+//!
+//! Each command is a line that starts with one or two letters, the
+//! parameters are separated by commas and strings are quoted. Labels
+//! start with N, fields like A, B and LO place themselves at the x and
+//! y of their first parameters, and P prints the label.
+//!
+//! The quoted data of an A command is parsed as its own text command,
+//! so the text is rendered the same way as receipt text. See
+//! command_sets::epl for the full set.
+
+use crate::command::*;
+use crate::constants::*;
+
+pub mod ascii_text;
+pub mod barcode;
+pub mod box_draw;
+pub mod character_set;
+pub mod clear_buffer;
+pub mod density;
+pub mod form_length;
+pub mod label_width;
+pub mod line_draw_black;
+pub mod options;
+pub mod print;
+pub mod print_direction;
+pub mod qr_code;
+pub mod speed;
+pub mod text_data;
+pub mod unknown;
+
+//Commands go up to and including the line break
+pub(crate) fn push_line(data: &mut Vec<u8>, byte: u8) -> bool {
+    if data.last() == Some(&LF) {
+        return false;
+    }
+
+    data.push(byte);
+    true
+}
+
+//Printer settings that don't change how a label looks
+#[derive(Clone)]
+pub(crate) struct Setting;
+
+impl CommandHandler for Setting {
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_line(data, byte)
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        command.data.last() == Some(&LF)
+    }
+}
+
+//Splits the parameters on commas outside of quotes, quoted
+//strings are returned without the quotes or escapes
+pub(crate) fn parameters(data: &[u8]) -> Vec<String> {
+    let mut params = vec![];
+    let mut param = vec![];
+    let mut quoted = false;
+    let mut bytes = data.iter().copied();
+
+    while let Some(byte) = bytes.next() {
+        match byte {
+            b'"' => quoted = !quoted,
+            b'\\' if quoted => param.extend(bytes.next()),
+            b',' if !quoted => params.push(std::mem::take(&mut param)),
+            b'\r' | b'\n' if !quoted => {}
+            _ => param.push(byte),
+        }
+    }
+
+    params.push(param);
+    params
+        .iter()
+        .map(|p| String::from_utf8_lossy(p).trim().to_string())
+        .collect()
+}
+
+pub(crate) fn number(parameters: &[String], index: usize) -> Option<u32> {
+    parameters.get(index).and_then(|p| p.parse::<u32>().ok())
+}
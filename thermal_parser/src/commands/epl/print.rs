@@ -0,0 +1,37 @@
+use crate::command::*;
+use crate::commands::epl::push_line;
+use crate::commands::label::print_label_commands;
+use crate::constants::*;
+use crate::context::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//P p1,p2 prints p1 sets of p2 copies, the label is rendered once
+impl CommandHandler for Handler {
+    fn get_device_command(
+        &self,
+        _command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        Some(print_label_commands())
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_line(data, byte)
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        command.data.last() == Some(&LF)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Print",
+        vec!['P' as u8],
+        CommandType::Control,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,12 @@
+use crate::command::*;
+use crate::commands::epl::Setting;
+
+pub fn new() -> Command {
+    Command::new(
+        "Print Direction",
+        vec!['Z' as u8],
+        CommandType::Control,
+        DataType::Custom,
+        Box::new(Setting {}),
+    )
+}
@@ -0,0 +1,82 @@
+use crate::command::*;
+use crate::commands::epl::{number, parameters, push_line};
+use crate::commands::label::set_position;
+use crate::constants::*;
+use crate::context::*;
+use crate::graphics::*;
+use crate::subcommands::gs_code2d::qr_store::store;
+
+#[derive(Clone)]
+struct Handler;
+
+//b p1,p2,Q,options,"data" where p1 and p2 are the x and y, the options
+//are m for the model, s for the scale and e for the error correction
+//like m2,s4,eM. Only QR codes are supported.
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let params = parameters(&command.data);
+        set_position(
+            context,
+            number(&params, 0).unwrap_or(0),
+            number(&params, 1).unwrap_or(0),
+        );
+    }
+
+    fn get_graphics(&self, command: &Command, context: &Context) -> Option<GraphicsCommand> {
+        let params = parameters(&command.data);
+
+        if params.get(2).map(|p| p.as_str()) != Some("Q") || params.len() < 4 {
+            return Some(GraphicsCommand::Error(
+                "Only QR codes are supported".to_string(),
+            ));
+        }
+
+        let mut context = context.clone();
+        context.code2d.qr_model = QrModel::Model2;
+        context.code2d.qr_size = 3;
+        context.code2d.qr_error_correction = QrErrorCorrection::M;
+
+        for option in params[3..params.len() - 1].iter() {
+            let value = option.get(1..).unwrap_or("");
+
+            match option.chars().next() {
+                Some('m') if value == "1" => context.code2d.qr_model = QrModel::Model1,
+                Some('s') => context.code2d.qr_size = value.parse::<u8>().unwrap_or(3).clamp(1, 99),
+                Some('e') => {
+                    context.code2d.qr_error_correction = match value {
+                        "H" => QrErrorCorrection::H,
+                        "Q" => QrErrorCorrection::Q,
+                        "L" => QrErrorCorrection::L,
+                        _ => QrErrorCorrection::M,
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        store(params[params.len() - 1].as_bytes(), &mut context);
+
+        match context.code2d.symbol_storage {
+            Some(symbol) => Some(symbol),
+            None => Some(GraphicsCommand::Error("Invalid QR code data".to_string())),
+        }
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_line(data, byte)
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        command.data.last() == Some(&LF)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "2D Bar Code",
+        vec!['b' as u8],
+        CommandType::Graphics,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
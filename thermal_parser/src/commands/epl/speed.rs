@@ -0,0 +1,12 @@
+use crate::command::*;
+use crate::commands::epl::Setting;
+
+pub fn new() -> Command {
+    Command::new(
+        "Speed Select",
+        vec!['S' as u8],
+        CommandType::Control,
+        DataType::Custom,
+        Box::new(Setting {}),
+    )
+}
@@ -0,0 +1,57 @@
+use crate::command::*;
+use crate::constants::*;
+use crate::context::*;
+use crate::text::TextSpan;
+
+#[derive(Clone)]
+struct Handler;
+
+//The quoted data of an A command, the opening quote is the command
+//and the data goes up to the end of the line
+fn text(command: &Command, context: &Context) -> String {
+    let mut data = vec![];
+    let mut bytes = command.data.iter().copied();
+
+    while let Some(byte) = bytes.next() {
+        match byte {
+            b'"' => break,
+            b'\\' => data.extend(bytes.next()),
+            _ => data.push(byte),
+        }
+    }
+
+    context.text.decoder.decode_utf8(&data)
+}
+
+impl CommandHandler for Handler {
+    fn get_text(&self, command: &Command, context: &Context) -> Option<TextSpan> {
+        Some(TextSpan::new(text(command, context), context))
+    }
+
+    fn debug(&self, command: &Command, context: &Context) -> String {
+        format!("{:?}", text(command, context))
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        if data.last() == Some(&LF) {
+            return false;
+        }
+
+        data.push(byte);
+        true
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        command.data.last() == Some(&LF)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Text Data",
+        vec!['"' as u8],
+        CommandType::Text,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,17 @@
+use crate::command::*;
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {}
+
+//Lines that start with a letter but aren't a known command
+pub fn new() -> Command {
+    Command::new(
+        "Unknown Command",
+        (b'A'..=b'Z').chain(b'a'..=b'z').collect(),
+        CommandType::Unknown,
+        DataType::Unknown,
+        Box::new(Handler {}),
+    )
+}
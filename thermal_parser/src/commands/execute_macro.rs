@@ -0,0 +1,62 @@
+///
+/// This command runs the macro that was recorded with GS :
+///
+/// GS ^ r t m
+///
+/// The macro runs r times with a wait of t * 100ms in between,
+/// when m is 1 the printer waits for the feed button instead.
+/// Running a macro while one is being defined clears it.
+///
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+fn get_params(data: &[u8]) -> (u8, u8, u8) {
+    let r = *data.first().unwrap_or(&0u8);
+    let t = *data.get(1).unwrap_or(&0u8);
+    let m = *data.get(2).unwrap_or(&0u8);
+
+    (r, t, m & 1)
+}
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, _command: &Command, context: &mut Context) {
+        let macros = &mut context.macros;
+
+        if macros.recording {
+            macros.recording = false;
+            macros.data.clear();
+        }
+    }
+
+    fn get_device_command(
+        &self,
+        command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        let (r, t, m) = get_params(&command.data);
+        Some(vec![DeviceCommand::ExecuteMacro(r, t, m)])
+    }
+
+    fn debug(&self, command: &Command, _context: &Context) -> String {
+        let (r, t, m) = get_params(&command.data);
+        format!(
+            "{} --> {} times, wait {}ms, mode {}",
+            &command.name,
+            r,
+            t as u32 * 100,
+            m
+        )
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Execute Macro",
+        vec![GS, '^' as u8],
+        CommandType::ContextControl,
+        DataType::Triple,
+        Box::new(Handler {}),
+    )
+}
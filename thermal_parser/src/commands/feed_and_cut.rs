@@ -40,6 +40,14 @@ impl CommandHandler for Handler {
         };
         false
     }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        match command.data.first() {
+            Some(0u8 | 48u8 | 1u8 | 49u8) => true,
+            Some(_) => command.data.len() == 2,
+            None => false,
+        }
+    }
 }
 
 pub fn new() -> Command {
@@ -0,0 +1,35 @@
+use crate::command::*;
+use crate::commands::label::default_label_size;
+use crate::context::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//Labels are wider than receipts, so the paper is set to the label width
+impl CommandHandler for Handler {
+    fn apply_context(&self, _command: &Command, context: &mut Context) {
+        let (width, _) = default_label_size(context);
+        let margins = context.graphics.paper_area.w - context.graphics.render_area.w;
+
+        context.graphics.render_area.w = width;
+        context.graphics.paper_area.w = width + margins;
+    }
+
+    fn get_device_command(
+        &self,
+        _command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        Some(vec![DeviceCommand::BeginPrint])
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Begin Label Print",
+        vec![],
+        CommandType::ContextControl,
+        DataType::Empty,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,17 @@
+use crate::command::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//Bytes outside of commands, like the line breaks between them, aren't printed
+impl CommandHandler for Handler {}
+
+pub fn new() -> Command {
+    Command::new(
+        "Ignored Data",
+        vec![],
+        CommandType::Control,
+        DataType::Text,
+        Box::new(Handler {}),
+    )
+}
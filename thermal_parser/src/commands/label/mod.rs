@@ -0,0 +1,169 @@
+//! Helpers shared by the label printer languages, see commands::zpl
+//! and commands::epl
+//!
+//! Each label is rendered as a page in page mode, the same way as an
+//! ESC/POS page. A field sets the print area to start at its origin,
+//! so text, barcodes and images are drawn where the field is.
+
+use crate::command::{Command, DeviceCommand};
+use crate::commands::barcode::{get_barcode_graphics, BarcodeType};
+use crate::context::*;
+use crate::graphics::*;
+
+pub mod begin_print;
+pub mod ignored;
+
+//Labels are usually 4 by 6 inches
+pub(crate) fn default_label_size(context: &Context) -> (u32, u32) {
+    let dpi = context.graphics.dots_per_inch as u32;
+    (dpi * 4, dpi * 6)
+}
+
+//The size of the current label, which is the page area
+pub(crate) fn label_size(context: &Context) -> (u32, u32) {
+    let page = &context.page_mode.page_area;
+
+    if page.w == 0 || page.h == 0 {
+        default_label_size(context)
+    } else {
+        (page.w, page.h)
+    }
+}
+
+//Starts a new page for the label, anything on the previous page is dropped.
+//Needs to be followed by the device commands from begin_label_commands.
+pub(crate) fn begin_label(context: &mut Context, width: u32, length: u32) {
+    let empty = RenderArea {
+        x: 0,
+        y: 0,
+        w: 0,
+        h: 0,
+    };
+
+    context.page_mode.page_area = empty.clone();
+    context.page_mode.render_area = empty;
+    context.page_mode.direction = PrintDirection::TopLeft2Right;
+    context.page_mode.previous_direction = PrintDirection::TopLeft2Right;
+    context.set_page_area(RenderArea {
+        x: 0,
+        y: 0,
+        w: width,
+        h: length,
+    });
+}
+
+pub(crate) fn begin_label_commands() -> Vec<DeviceCommand> {
+    vec![DeviceCommand::BeginPageMode, DeviceCommand::ChangePageArea]
+}
+
+pub(crate) fn print_label_commands() -> Vec<DeviceCommand> {
+    vec![DeviceCommand::EndPageMode, DeviceCommand::PrintPageMode]
+}
+
+//Moves the print area to the field origin, needs to be
+//followed by DeviceCommand::ChangePageArea
+pub(crate) fn set_field_origin(context: &mut Context, x: u32, y: u32) {
+    let (width, length) = label_size(context);
+    let (x, y) = (x.min(width), y.min(length));
+
+    context.set_page_area(RenderArea {
+        x,
+        y,
+        w: width.saturating_sub(x),
+        h: length.saturating_sub(y),
+    });
+}
+
+//Moves the render position to a point on the label without changing the
+//size of the page, for graphics that are drawn at their own position.
+//The page origin moves too since graphics like barcodes start from it.
+pub(crate) fn set_position(context: &mut Context, x: u32, y: u32) {
+    let (width, length) = label_size(context);
+    let (x, y) = (x.min(width), y.min(length));
+    let page_mode = &mut context.page_mode;
+
+    page_mode.page_area.x = x;
+    page_mode.page_area.y = y;
+    page_mode.render_area = RenderArea {
+        x,
+        y,
+        w: width.saturating_sub(x),
+        h: length.saturating_sub(y),
+    };
+}
+
+//Font sizes are in dots, the closest font and multipliers are used
+pub(crate) fn font_for_size(width: u32, height: u32) -> (Font, u8, u8) {
+    let (w, h) = Font::A.to_size();
+
+    if height < h as u32 {
+        return (Font::B, 1, 1);
+    }
+
+    let multiplier =
+        |dots: u32, size: u8| ((dots as f32 / size as f32).round() as u32).clamp(1, 8) as u8;
+
+    (Font::A, multiplier(width, w), multiplier(height, h))
+}
+
+//A box with lines of the given thickness, filled when
+//the thickness is as large as the width or height.
+//Only the part of the box that is on the label is drawn.
+pub(crate) fn box_graphics(
+    context: &Context,
+    w: u32,
+    h: u32,
+    thickness: u32,
+    color: &RGBA,
+) -> GraphicsCommand {
+    let thickness = thickness.max(1);
+    let (width, length) = label_size(context);
+    let visible_w = w.min(width.saturating_sub(context.page_mode.page_area.x));
+    let visible_h = h.min(length.saturating_sub(context.page_mode.page_area.y));
+    let mut pixels = Vec::with_capacity(visible_w as usize * visible_h as usize);
+
+    for y in 0..visible_h {
+        for x in 0..visible_w {
+            let edge = x < thickness || y < thickness || x + thickness >= w || y + thickness >= h;
+            pixels.push(if edge { *color } else { RGBA::blank() });
+        }
+    }
+
+    GraphicsCommand::Image(Image {
+        pixels,
+        x: 0,
+        y: 0,
+        w: visible_w,
+        h: visible_h,
+        flow: ImageFlow::None,
+        upside_down: false,
+    })
+}
+
+//The barcode settings of a field are only used for that field,
+//the data is what's encoded without any of the parameters
+pub(crate) fn barcode_graphics(
+    kind: BarcodeType,
+    command: &Command,
+    context: &Context,
+    data: Vec<u8>,
+    width: Option<u32>,
+    height: Option<u32>,
+    human_readable: HumanReadableInterface,
+) -> Option<GraphicsCommand> {
+    let mut context = context.clone();
+    let mut command = command.clone();
+
+    if let Some(width) = width {
+        context.barcode.width = width.clamp(1, 10) as u8;
+    }
+
+    if let Some(height) = height {
+        context.barcode.height = height.clamp(1, 255) as u8;
+    }
+
+    context.barcode.human_readable = human_readable;
+    command.data = data;
+
+    get_barcode_graphics(kind, &command, &context)
+}
@@ -0,0 +1,34 @@
+///
+/// This command starts or ends a macro definition.
+///
+/// The commands between two GS : are recorded while they are
+/// printed as usual, GS ^ runs them again. Starting a new
+/// definition clears the previous macro.
+///
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, _command: &Command, context: &mut Context) {
+        let macros = &mut context.macros;
+
+        if macros.recording {
+            macros.recording = false;
+        } else {
+            macros.recording = true;
+            macros.data.clear();
+        }
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Start or End Macro Definition",
+        vec![GS, ':' as u8],
+        CommandType::Context,
+        DataType::Empty,
+        Box::new(Handler {}),
+    )
+}
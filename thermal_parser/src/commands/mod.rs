@@ -2,17 +2,26 @@ pub mod barcode;
 pub mod begin_print;
 pub mod bit_image;
 pub mod cancel;
+pub mod cancel_kanji_mode;
+pub mod cancel_user_character;
 pub mod carriage_return;
 pub mod code_2d;
 pub mod default_line_spacing;
+pub mod define_bit_image;
+pub mod define_nv_bit_image;
+pub mod define_user_characters;
 pub mod end_print;
+pub mod execute_macro;
+pub mod epl;
 pub mod feed_and_cut;
 pub mod formfeed;
 pub mod graphics;
 pub mod horizontal_tab;
 pub mod initialize;
+pub mod label;
 pub mod large_graphics;
 pub mod linefeed;
+pub mod macro_definition;
 pub mod offset_vertical_pos;
 pub mod page_mode_print_area;
 pub mod page_mode_print_data;
@@ -21,11 +30,15 @@ pub mod paper_end_sensor;
 pub mod print_and_feed;
 pub mod print_and_feed_lines;
 pub mod print_and_reverse_feed_lines;
+pub mod print_bit_image;
+pub mod print_nv_bit_image;
 pub mod print_stop_sensor;
 pub mod pulse;
 pub mod raster_bit_image;
 pub mod request_response_transmission;
+pub mod select_kanji_mode;
 pub mod select_standard_mode;
+pub mod select_user_characters;
 pub mod set_alt_color;
 pub mod set_barcode_font;
 pub mod set_barcode_height;
@@ -42,18 +55,28 @@ pub mod set_horizontal_pos;
 pub mod set_international_charset;
 pub mod set_italic;
 pub mod set_justification;
+pub mod set_kanji_code_system;
+pub mod set_kanji_print_mode;
+pub mod set_kanji_quadruple;
+pub mod set_kanji_spacing;
+pub mod set_kanji_underline;
+pub mod set_left_margin;
 pub mod set_line_spacing;
 pub mod set_motion_units;
 pub mod set_page_mode;
 pub mod set_panel_buttons;
 pub mod set_peripheral_device;
+pub mod set_print_area_width;
 pub mod set_print_mode;
 pub mod set_smoothing;
 pub mod set_tab_len;
 pub mod set_underline;
 pub mod set_upside_down;
 pub mod set_vertical_pos;
+pub mod star;
 pub mod text;
 pub mod transmit_printer_id;
 pub mod unknown;
 pub mod unknown_gs_g;
+pub mod variable_bit_image;
+pub mod zpl;
@@ -0,0 +1,37 @@
+///
+/// This command prints the bit image that was downloaded with GS *
+///
+/// The mode scales the image to double width, double height or both.
+///
+use crate::{command::*, constants::*, context::*, graphics::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn get_graphics(&self, command: &Command, context: &Context) -> Option<GraphicsCommand> {
+        let mode = *command.data.first().unwrap_or(&0u8);
+        let image_ref = ImageRef {
+            kc1: 0,
+            kc2: 0,
+            storage: ImageRefStorage::DownloadBitImage,
+        };
+
+        match context.graphics.stored_graphics.get(&image_ref) {
+            Some(GraphicsCommand::Image(image)) => Some(GraphicsCommand::Image(
+                image.stretch(bit_image_stretch(mode)),
+            )),
+            other => other.cloned(),
+        }
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Print Downloaded Bit Image",
+        vec![GS, '/' as u8],
+        CommandType::Graphics,
+        DataType::Single,
+        Box::new(Handler {}),
+    )
+}
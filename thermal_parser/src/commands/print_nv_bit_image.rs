@@ -0,0 +1,49 @@
+///
+/// This command prints an NV bit image that was defined with FS q
+///
+/// FS p n m
+///
+/// n is the number of the image and the mode scales it
+/// to double width, double height or both.
+///
+use crate::{command::*, constants::*, context::*, graphics::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn get_graphics(&self, command: &Command, context: &Context) -> Option<GraphicsCommand> {
+        let n = *command.data.first().unwrap_or(&0u8);
+        let mode = *command.data.get(1).unwrap_or(&0u8);
+        let image_ref = ImageRef {
+            kc1: n,
+            kc2: 0,
+            storage: ImageRefStorage::NvBitImage,
+        };
+
+        match context.graphics.stored_graphics.get(&image_ref) {
+            Some(GraphicsCommand::Image(image)) => Some(GraphicsCommand::Image(
+                image.stretch(bit_image_stretch(mode)),
+            )),
+            other => other.cloned(),
+        }
+    }
+
+    fn debug(&self, command: &Command, _context: &Context) -> String {
+        format!(
+            "{} --> {}",
+            &command.name,
+            command.data.first().unwrap_or(&0u8)
+        )
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Print NV Bit Image",
+        vec![FS, 'p' as u8],
+        CommandType::Graphics,
+        DataType::Double,
+        Box::new(Handler {}),
+    )
+}
@@ -64,6 +64,10 @@ impl CommandHandler for Handler {
         true
     }
 
+    fn is_complete(&self, command: &Command) -> bool {
+        self.accept_data && command.data.len() >= self.capacity as usize
+    }
+
     //Used when converting commands back into other formats i.e. Thermal format
     fn get_command_bytes(&self, command: &Command) -> (Vec<u8>, Vec<u8>) {
         let mut data = self.params.clone();
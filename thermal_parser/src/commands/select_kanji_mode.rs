@@ -0,0 +1,33 @@
+///
+/// This command turns on Kanji mode.
+///
+/// Text is decoded with the multi byte encoding for the international
+/// character set and the Kanji code system until FS . is received.
+///
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, _command: &Command, context: &mut Context) {
+        context.text.kanji.enabled = true;
+    }
+
+    fn debug(&self, command: &Command, context: &Context) -> String {
+        match &context.text.kanji.encoding {
+            Some(encoding) => format!("{} ({})", command.name, encoding.name()),
+            None => format!("{} (no multi byte encoding)", command.name),
+        }
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Select Kanji Mode",
+        vec![FS, '&' as u8],
+        CommandType::TextStyle,
+        DataType::Empty,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,27 @@
+///
+/// This command selects or cancels the user-defined character set.
+///
+/// Only the lowest bit is used, codes that were downloaded with ESC &
+/// for the current font are printed with the downloaded characters.
+///
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let n = *command.data.first().unwrap_or(&0u8);
+        context.text.user_defined = n & 1 == 1;
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Select User-Defined Characters",
+        vec![ESC, '%' as u8],
+        CommandType::Context,
+        DataType::Single,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,44 @@
+///
+/// This command selects the Kanji code system.
+///
+/// 0 or 48 JIS
+/// 1 or 49 Shift JIS
+/// 2 or 50 Shift JIS-2004
+///
+/// Japanese printers use the code system to pick the multi byte
+/// encoding, the other character sets don't use it.
+///
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let n = *command.data.first().unwrap_or(&1u8);
+
+        context.text.kanji.code_system = match n {
+            0 | 48 => 0,
+            2 | 50 => 2,
+            _ => 1,
+        };
+        context.update_decoder();
+    }
+
+    fn debug(&self, command: &Command, context: &Context) -> String {
+        match &context.text.kanji.encoding {
+            Some(encoding) => format!("{} to {}", command.name, encoding.name()),
+            None => format!("{} to {}", command.name, context.text.kanji.code_system),
+        }
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Select Kanji Code System",
+        vec![FS, 'C' as u8],
+        CommandType::TextStyle,
+        DataType::Single,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,60 @@
+///
+/// This command sets the print mode for Kanji characters.
+///
+/// Bit 2 is double width, bit 3 is double height and bit 7 is underline.
+///
+use crate::{command::*, constants::*, context::*, util::bitflags_lsb};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let (_, _, wide, tall, _, _, _, underline) =
+            bitflags_lsb(command.data.first().unwrap_or(&0u8));
+
+        let kanji = &mut context.text.kanji;
+        kanji.double_width = wide;
+        kanji.double_height = tall;
+        kanji.underline = if underline {
+            TextUnderline::On
+        } else {
+            TextUnderline::Off
+        };
+    }
+
+    fn debug(&self, command: &Command, _context: &Context) -> String {
+        let (_, _, wide, tall, _, _, _, underline) =
+            bitflags_lsb(command.data.first().unwrap_or(&0u8));
+
+        let changes = vec![
+            if wide {
+                "Double Width"
+            } else {
+                "Standard Width"
+            },
+            if tall {
+                "Double Height"
+            } else {
+                "Standard Height"
+            },
+            if underline {
+                "Underline"
+            } else {
+                "No Underline"
+            },
+        ];
+
+        format!("{}: {:?}", command.name, changes)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Set Kanji Print Mode",
+        vec![FS, '!' as u8],
+        CommandType::TextStyle,
+        DataType::Single,
+        Box::new(Handler {}),
+    )
+}
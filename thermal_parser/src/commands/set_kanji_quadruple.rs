@@ -0,0 +1,26 @@
+///
+/// This command turns quadruple size Kanji characters on or off,
+/// quadruple size is double width and double height.
+///
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let quadruple = *command.data.first().unwrap_or(&0u8) & 1 == 1;
+        context.text.kanji.double_width = quadruple;
+        context.text.kanji.double_height = quadruple;
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Set Kanji Quadruple Size",
+        vec![FS, 'W' as u8],
+        CommandType::TextStyle,
+        DataType::Single,
+        Box::new(Handler {}),
+    )
+}
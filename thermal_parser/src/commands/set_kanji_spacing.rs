@@ -0,0 +1,35 @@
+///
+/// This command sets the spacing on the left and
+/// right side of Kanji characters in dots.
+///
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let kanji = &mut context.text.kanji;
+        kanji.left_spacing = *command.data.first().unwrap_or(&0u8);
+        kanji.right_spacing = *command.data.get(1).unwrap_or(&0u8);
+    }
+
+    fn debug(&self, command: &Command, _context: &Context) -> String {
+        format!(
+            "{} to left {} right {}",
+            command.name,
+            command.data.first().unwrap_or(&0u8),
+            command.data.get(1).unwrap_or(&0u8)
+        )
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Set Kanji Character Spacing",
+        vec![FS, 'S' as u8],
+        CommandType::TextStyle,
+        DataType::Double,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,29 @@
+///
+/// This command sets the underline for Kanji characters.
+///
+/// 0 or 48 off, 1 or 49 one dot thick, 2 or 50 two dots thick
+///
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        context.text.kanji.underline = match *command.data.first().unwrap_or(&0u8) {
+            1 | 49 => TextUnderline::On,
+            2 | 50 => TextUnderline::Double,
+            _ => TextUnderline::Off,
+        };
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Set Kanji Underline",
+        vec![FS, '-' as u8],
+        CommandType::TextStyle,
+        DataType::Single,
+        Box::new(Handler {}),
+    )
+}
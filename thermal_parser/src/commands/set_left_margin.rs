@@ -0,0 +1,38 @@
+///
+/// This command sets the left margin in standard mode.
+///
+/// Lines start at the margin, the print area width shrinks
+/// when the margin would push it past the printable area.
+/// It is ignored when it isn't at the beginning of a line.
+///
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+fn get_margin(data: &[u8]) -> u32 {
+    let nl = data.first().unwrap_or(&0u8);
+    let nh = data.get(1).unwrap_or(&0u8);
+
+    (*nl as u16 + *nh as u16 * 256) as u32
+}
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        context.set_left_margin(get_margin(&command.data));
+    }
+
+    fn debug(&self, command: &Command, _context: &Context) -> String {
+        format!("{} --> {}", &command.name, get_margin(&command.data))
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Set Left Margin",
+        vec![GS, 'L' as u8],
+        CommandType::Context,
+        DataType::Double,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,38 @@
+///
+/// This command sets the width of the print area in standard mode.
+///
+/// The width starts at the left margin, it is at least one
+/// character wide and can't go past the printable area.
+/// It is ignored when it isn't at the beginning of a line.
+///
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+fn get_width(data: &[u8]) -> u32 {
+    let nl = data.first().unwrap_or(&0u8);
+    let nh = data.get(1).unwrap_or(&0u8);
+
+    (*nl as u16 + *nh as u16 * 256) as u32
+}
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        context.set_print_area_width(get_width(&command.data));
+    }
+
+    fn debug(&self, command: &Command, _context: &Context) -> String {
+        format!("{} --> {}", &command.name, get_width(&command.data))
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Set Print Area Width",
+        vec![GS, 'W' as u8],
+        CommandType::Context,
+        DataType::Double,
+        Box::new(Handler {}),
+    )
+}
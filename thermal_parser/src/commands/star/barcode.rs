@@ -0,0 +1,106 @@
+use crate::commands::barcode::{get_barcode_graphics, BarcodeType};
+use crate::commands::star::parameter;
+use crate::{command::*, constants::*, context::*, graphics::*};
+
+//ESC b n1 n2 n3 n4 d1...dk RS
+#[derive(Clone)]
+struct Handler {
+    //Type, HRI, module width and height
+    params: Vec<u8>,
+    complete: bool,
+}
+
+impl Handler {
+    fn kind(&self) -> BarcodeType {
+        match parameter(*self.params.first().unwrap_or(&0xFF)) {
+            0 => BarcodeType::UpcE,
+            1 => BarcodeType::UpcA,
+            2 => BarcodeType::Ean8,
+            3 => BarcodeType::Ean13,
+            4 => BarcodeType::Code39,
+            5 => BarcodeType::Itf,
+            6 => BarcodeType::Code128Auto,
+            7 => BarcodeType::Code93,
+            8 => BarcodeType::Nw7Codabar,
+            _ => BarcodeType::Unknown,
+        }
+    }
+}
+
+impl CommandHandler for Handler {
+    fn get_graphics(&self, command: &Command, context: &Context) -> Option<GraphicsCommand> {
+        let hri = parameter(*self.params.get(1).unwrap_or(&1));
+        let mode = parameter(*self.params.get(2).unwrap_or(&1));
+        let height = *self.params.get(3).unwrap_or(&0);
+
+        //The barcode settings are part of the command instead of the context
+        let mut context = context.clone();
+
+        context.barcode.human_readable = match hri {
+            2 | 4 => HumanReadableInterface::Below,
+            _ => HumanReadableInterface::None,
+        };
+
+        //Modes 1 to 9 step through the narrow bar widths and ratios,
+        //only the narrow bar width of 2, 3 or 4 dots is used here
+        context.barcode.width = match mode {
+            2 | 5 | 8 => 3,
+            3 | 6 | 9 => 4,
+            _ => 2,
+        };
+
+        if height > 0 {
+            context.barcode.height = height;
+        }
+
+        get_barcode_graphics(self.kind(), command, &context)
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        if self.complete {
+            return false;
+        }
+
+        if self.params.len() < 4 {
+            self.params.push(byte);
+            return true;
+        }
+
+        if byte == RS {
+            self.complete = true;
+        } else {
+            data.push(byte);
+        }
+
+        true
+    }
+
+    fn is_complete(&self, _command: &Command) -> bool {
+        self.complete
+    }
+
+    fn get_command_bytes(&self, command: &Command) -> (Vec<u8>, Vec<u8>) {
+        let mut commands = command.commands.to_vec();
+        commands.extend(self.params.clone());
+        let mut data = command.data.to_vec();
+
+        if self.complete {
+            data.push(RS);
+        }
+
+        (commands, data)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Barcode",
+        vec![ESC, 'b' as u8],
+        CommandType::Graphics,
+        DataType::Custom,
+        Box::new(Handler {
+            params: vec![],
+            complete: false,
+        }),
+    )
+}
@@ -0,0 +1,25 @@
+use crate::command::DeviceCommand::SetTextWidth;
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn get_device_command(
+        &self,
+        _command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        Some(vec![SetTextWidth(1)])
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Cancel Double Width",
+        vec![DC4],
+        CommandType::TextStyle,
+        DataType::Empty,
+        Box::new(Handler {}),
+    )
+}
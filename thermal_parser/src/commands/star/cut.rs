@@ -0,0 +1,32 @@
+use crate::commands::star::parameter;
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    //n = 2 and 3 feed to the cutter first, which the renderer does for every cut
+    fn get_device_command(
+        &self,
+        command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        let n = *command.data.first().unwrap_or(&0u8);
+
+        match parameter(n) {
+            0 | 2 => Some(vec![DeviceCommand::FullCut]),
+            1 | 3 => Some(vec![DeviceCommand::PartialCut]),
+            _ => None,
+        }
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Cut",
+        vec![ESC, 'd' as u8],
+        CommandType::Control,
+        DataType::Single,
+        Box::new(Handler {}),
+    )
+}
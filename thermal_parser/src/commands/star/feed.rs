@@ -0,0 +1,28 @@
+use crate::commands::star::mm_to_dots;
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    //Feeds n/4 mm
+    fn get_device_command(
+        &self,
+        command: &Command,
+        context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        let n = *command.data.first().unwrap_or(&0u8);
+        let dots = mm_to_dots(n as f32 / 4.0, context);
+        Some(vec![DeviceCommand::Feed(dots as i16)])
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Feed",
+        vec![ESC, 'J' as u8],
+        CommandType::Control,
+        DataType::Single,
+        Box::new(Handler {}),
+    )
+}
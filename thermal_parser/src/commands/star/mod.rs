@@ -0,0 +1,194 @@
+//! Star Line Mode commands, for Star printers like the TSP100 and TSP650
+//!
+//! Commands that work the same as in ESC/POS are not repeated here,
+//! see command_sets::star_line for the full set.
+//!
+//! The raster_ commands are Star Graphic Mode commands, which is what
+//! the CUPS and Windows raster drivers send, see command_sets::star_graphic.
+
+pub mod barcode;
+pub mod cancel_double_height;
+pub mod cancel_double_width;
+pub mod cancel_emphasis;
+pub mod cancel_highlight;
+pub mod cancel_upside_down;
+pub mod cut;
+pub mod feed;
+pub mod feed_lines;
+pub mod open_drawer;
+pub mod qr_print;
+pub mod qr_set_correction_level;
+pub mod qr_set_model;
+pub mod qr_set_size;
+pub mod qr_store;
+pub mod raster_clear;
+pub mod raster_enter;
+pub mod raster_execute_eot_mode;
+pub mod raster_execute_ff_mode;
+pub mod raster_initialize;
+pub mod raster_line;
+pub mod raster_line_compressed;
+pub mod raster_move_vertical;
+pub mod raster_quit;
+pub mod raster_set_eot_mode;
+pub mod raster_set_ff_mode;
+pub mod raster_set_left_margin;
+pub mod raster_set_page_length;
+pub mod raster_set_print_quality;
+pub mod raster_set_right_margin;
+pub mod raster_set_top_margin;
+pub mod set_absolute_position;
+pub mod set_character_expansion;
+pub mod set_code_table;
+pub mod set_double_height;
+pub mod set_double_width;
+pub mod set_emphasis;
+pub mod set_font;
+pub mod set_height_expansion;
+pub mod set_highlight;
+pub mod set_justification;
+pub mod set_line_spacing;
+pub mod set_upperline;
+pub mod set_upside_down;
+pub mod set_width_expansion;
+
+use crate::{command::*, constants::*, context::*, graphics::*};
+
+//Most Star parameters can be sent as a number or as an ASCII digit
+pub(crate) fn parameter(n: u8) -> u8 {
+    if n.is_ascii_digit() {
+        n - b'0'
+    } else {
+        n
+    }
+}
+
+//Character expansion of 0 to 5 is a multiplier of 1 to 6
+pub(crate) fn expansion(n: u8) -> u8 {
+    parameter(n).min(5) + 1
+}
+
+//Star units are in millimeters, the context is in dots
+pub(crate) fn mm_to_dots(mm: f32, context: &crate::context::Context) -> u32 {
+    (mm * context.graphics.dots_per_inch as f32 / 25.4).round() as u32
+}
+
+//Raster settings are sent as ASCII digits followed by NUL
+#[derive(Clone)]
+pub(crate) struct RasterSetting {
+    complete: bool,
+}
+
+impl RasterSetting {
+    pub(crate) fn new() -> Self {
+        Self { complete: false }
+    }
+}
+
+impl CommandHandler for RasterSetting {
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        if self.complete {
+            return false;
+        }
+
+        if byte == NUL {
+            self.complete = true;
+        } else {
+            data.push(byte);
+        }
+
+        true
+    }
+
+    fn is_complete(&self, _command: &Command) -> bool {
+        self.complete
+    }
+
+    fn get_command_bytes(&self, command: &Command) -> (Vec<u8>, Vec<u8>) {
+        let mut data = command.data.to_vec();
+
+        if self.complete {
+            data.push(NUL);
+        }
+
+        (command.commands.to_vec(), data)
+    }
+}
+
+//The value of a raster setting, anything that isn't a digit is ignored
+pub(crate) fn raster_value(data: &[u8]) -> u32 {
+    data.iter()
+        .filter(|b| b.is_ascii_digit())
+        .fold(0u32, |n, b| {
+            n.saturating_mul(10).saturating_add((b - b'0') as u32)
+        })
+}
+
+//Raster lines are kept in the buffer graphics as images that are one dot
+//high, until the page is finished and they can be printed as a single image
+pub(crate) fn push_raster_line(data: &[u8], context: &mut Context) {
+    let graphics = GraphicsCommand::image_from_raster_bytes_single_color(
+        data.len() as u32 * 8,
+        1,
+        (1, 1),
+        context.graphics.render_colors.color_for_number(1),
+        ImageFlow::Block,
+        data,
+        true,
+    );
+
+    context.graphics.buffer_graphics.push(graphics);
+}
+
+//Blank raster lines, kept as a single image without any pixels
+pub(crate) fn push_raster_space(height: u32, context: &mut Context) {
+    context.graphics.buffer_graphics.push(GraphicsCommand::Image(Image {
+        pixels: vec![],
+        x: 0,
+        y: 0,
+        w: 0,
+        h: height,
+        flow: ImageFlow::Block,
+        upside_down: false,
+    }));
+}
+
+//Stacks the buffered raster lines into a single image
+pub(crate) fn raster_graphics(context: &Context) -> Option<GraphicsCommand> {
+    let mut lines = vec![];
+
+    for g in context.graphics.buffer_graphics.iter() {
+        match g {
+            GraphicsCommand::Error(_) => return Some(g.clone()),
+            GraphicsCommand::Image(img) => lines.push(img),
+            _ => {}
+        }
+    }
+
+    let w = lines.iter().map(|img| img.w).max().unwrap_or(0);
+    let h = lines.iter().map(|img| img.h).sum();
+
+    if w == 0 || h == 0 {
+        return None;
+    }
+
+    let mut pixels = Vec::with_capacity(w as usize * h as usize);
+
+    for img in lines {
+        for row in 0..img.h as usize {
+            let start = row * img.w as usize;
+            pixels.extend_from_slice(&img.pixels[start..start + img.w as usize]);
+            pixels.extend((img.w..w).map(|_| RGBA::blank()));
+        }
+    }
+
+    Some(GraphicsCommand::Image(Image {
+        pixels,
+        x: 0,
+        y: 0,
+        w,
+        h,
+        flow: ImageFlow::Block,
+        upside_down: false,
+    }))
+}
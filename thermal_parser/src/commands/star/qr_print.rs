@@ -0,0 +1,12 @@
+use crate::subcommands::gs_code2d::qr_print::Handler;
+use crate::{command::*, constants::*};
+
+pub fn new() -> Command {
+    Command::new(
+        "Print the Code2D data",
+        vec![ESC, GS, 'y' as u8, 'P' as u8],
+        CommandType::Graphics,
+        DataType::Empty,
+        Box::new(Handler),
+    )
+}
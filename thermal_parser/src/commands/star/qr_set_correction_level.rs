@@ -0,0 +1,27 @@
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let n = *command.data.first().unwrap_or(&0u8);
+
+        context.code2d.qr_error_correction = match n {
+            1 => QrErrorCorrection::M,
+            2 => QrErrorCorrection::Q,
+            3 => QrErrorCorrection::H,
+            _ => QrErrorCorrection::L,
+        };
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "QR Set Error Correction Level",
+        vec![ESC, GS, 'y' as u8, 'S' as u8, '1' as u8],
+        CommandType::Context,
+        DataType::Single,
+        Box::new(Handler {}),
+    )
+}
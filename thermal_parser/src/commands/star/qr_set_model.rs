@@ -0,0 +1,25 @@
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let n = *command.data.first().unwrap_or(&2u8);
+
+        context.code2d.qr_model = match n {
+            1 => QrModel::Model1,
+            _ => QrModel::Model2,
+        };
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "QR Set Model",
+        vec![ESC, GS, 'y' as u8, 'S' as u8, '0' as u8],
+        CommandType::Context,
+        DataType::Single,
+        Box::new(Handler {}),
+    )
+}
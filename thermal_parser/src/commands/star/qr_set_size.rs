@@ -0,0 +1,22 @@
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    //Cell size in dots, from 1 to 8
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let n = *command.data.first().unwrap_or(&3u8);
+        context.code2d.qr_size = n.clamp(1, 8);
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "QR Set Cell Size",
+        vec![ESC, GS, 'y' as u8, 'S' as u8, '2' as u8],
+        CommandType::Context,
+        DataType::Single,
+        Box::new(Handler {}),
+    )
+}
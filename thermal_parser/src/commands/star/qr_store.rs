@@ -0,0 +1,57 @@
+use crate::subcommands::gs_code2d::qr_store::store;
+use crate::{command::*, constants::*, context::*};
+
+//ESC GS y D 1 m nL nH d1...dk, the data is analyzed automatically
+#[derive(Clone)]
+struct Handler {
+    //m, nL and nH
+    params: Vec<u8>,
+    capacity: usize,
+}
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        store(&command.data, context);
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        if self.params.len() < 3 {
+            self.params.push(byte);
+
+            if self.params.len() == 3 {
+                self.capacity = self.params[1] as usize + self.params[2] as usize * 256;
+            }
+            return true;
+        }
+
+        if data.len() >= self.capacity {
+            return false;
+        }
+
+        data.push(byte);
+        true
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        self.params.len() == 3 && command.data.len() >= self.capacity
+    }
+
+    fn get_command_bytes(&self, command: &Command) -> (Vec<u8>, Vec<u8>) {
+        let mut commands = command.commands.to_vec();
+        commands.extend(self.params.clone());
+        (commands, command.data.to_vec())
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "QR Store the Code2D data",
+        vec![ESC, GS, 'y' as u8, 'D' as u8, '1' as u8],
+        CommandType::Context,
+        DataType::Custom,
+        Box::new(Handler {
+            params: vec![],
+            capacity: 0,
+        }),
+    )
+}
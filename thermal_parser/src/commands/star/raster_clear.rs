@@ -0,0 +1,21 @@
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+//ESC * r C, drops the raster data that has not been printed yet
+impl CommandHandler for Handler {
+    fn apply_context(&self, _command: &Command, context: &mut Context) {
+        context.graphics.buffer_graphics.clear();
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Clear Raster Data",
+        vec![ESC, '*' as u8, 'r' as u8, 'C' as u8],
+        CommandType::Context,
+        DataType::Empty,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,21 @@
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+//ESC * r A, anything left over from a previous page is dropped
+impl CommandHandler for Handler {
+    fn apply_context(&self, _command: &Command, context: &mut Context) {
+        context.graphics.buffer_graphics.clear();
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Enter Raster Mode",
+        vec![ESC, '*' as u8, 'r' as u8, 'A' as u8],
+        CommandType::Context,
+        DataType::Empty,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,30 @@
+use crate::commands::star::raster_graphics;
+use crate::{command::*, constants::*, context::*, graphics::*};
+
+#[derive(Clone)]
+struct Handler;
+
+//ESC FF EOT, prints the raster data and then feeds and cuts as set by ESC * r E
+impl CommandHandler for Handler {
+    fn get_graphics(&self, _command: &Command, context: &Context) -> Option<GraphicsCommand> {
+        raster_graphics(context)
+    }
+
+    fn get_device_command(
+        &self,
+        _command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        Some(vec![DeviceCommand::ClearBufferGraphics])
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Execute EOT Mode",
+        vec![ESC, FF, EOT],
+        CommandType::Graphics,
+        DataType::Empty,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,30 @@
+use crate::commands::star::raster_graphics;
+use crate::{command::*, constants::*, context::*, graphics::*};
+
+#[derive(Clone)]
+struct Handler;
+
+//ESC FF NUL, prints the raster data and then feeds and cuts as set by ESC * r F
+impl CommandHandler for Handler {
+    fn get_graphics(&self, _command: &Command, context: &Context) -> Option<GraphicsCommand> {
+        raster_graphics(context)
+    }
+
+    fn get_device_command(
+        &self,
+        _command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        Some(vec![DeviceCommand::ClearBufferGraphics])
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Execute FF Mode",
+        vec![ESC, FF, NUL],
+        CommandType::Graphics,
+        DataType::Empty,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,21 @@
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+//ESC * r R, resets the raster settings and drops the raster data
+impl CommandHandler for Handler {
+    fn apply_context(&self, _command: &Command, context: &mut Context) {
+        context.graphics.buffer_graphics.clear();
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Initialize Raster Mode",
+        vec![ESC, '*' as u8, 'r' as u8, 'R' as u8],
+        CommandType::Context,
+        DataType::Empty,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,57 @@
+use crate::commands::star::push_raster_line;
+use crate::{command::*, context::*};
+
+//b n1 n2 d1...dk, one line of raster data where k = n1 + n2 * 256
+#[derive(Clone)]
+struct Handler {
+    //n1 and n2
+    params: Vec<u8>,
+    capacity: usize,
+}
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        push_raster_line(&command.data, context);
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        if self.params.len() < 2 {
+            self.params.push(byte);
+
+            if self.params.len() == 2 {
+                self.capacity = self.params[0] as usize + self.params[1] as usize * 256;
+            }
+            return true;
+        }
+
+        if data.len() >= self.capacity {
+            return false;
+        }
+
+        data.push(byte);
+        true
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        self.params.len() == 2 && command.data.len() >= self.capacity
+    }
+
+    fn get_command_bytes(&self, command: &Command) -> (Vec<u8>, Vec<u8>) {
+        let mut commands = command.commands.to_vec();
+        commands.extend(self.params.clone());
+        (commands, command.data.to_vec())
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Transfer Raster Data",
+        vec!['b' as u8],
+        CommandType::Context,
+        DataType::Custom,
+        Box::new(Handler {
+            params: vec![],
+            capacity: 0,
+        }),
+    )
+}
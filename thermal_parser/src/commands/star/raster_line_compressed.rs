@@ -0,0 +1,86 @@
+use crate::commands::star::push_raster_line;
+use crate::{command::*, constants::*, context::*};
+
+//ESC * b n1 n2 d1...dk, one line of raster data compressed with PackBits
+//where k = n1 + n2 * 256 is the compressed length
+#[derive(Clone)]
+struct Handler {
+    //n1 and n2
+    params: Vec<u8>,
+    capacity: usize,
+}
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        push_raster_line(&unpack_bits(&command.data), context);
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        if self.params.len() < 2 {
+            self.params.push(byte);
+
+            if self.params.len() == 2 {
+                self.capacity = self.params[0] as usize + self.params[1] as usize * 256;
+            }
+            return true;
+        }
+
+        if data.len() >= self.capacity {
+            return false;
+        }
+
+        data.push(byte);
+        true
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        self.params.len() == 2 && command.data.len() >= self.capacity
+    }
+
+    fn get_command_bytes(&self, command: &Command) -> (Vec<u8>, Vec<u8>) {
+        let mut commands = command.commands.to_vec();
+        commands.extend(self.params.clone());
+        (commands, command.data.to_vec())
+    }
+}
+
+//A header byte of 0 to 127 is followed by that many bytes plus one,
+//-1 to -127 is followed by one byte that repeats that many times plus one
+fn unpack_bits(data: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![];
+    let mut i = 0;
+
+    while i < data.len() {
+        let header = data[i] as i8;
+        i += 1;
+
+        if header >= 0 {
+            let end = (i + header as usize + 1).min(data.len());
+            bytes.extend_from_slice(&data[i..end]);
+            i = end;
+        } else if header != -128 {
+            if let Some(byte) = data.get(i) {
+                bytes.extend(std::iter::repeat_n(
+                    *byte,
+                    1 + header.unsigned_abs() as usize,
+                ));
+            }
+            i += 1;
+        }
+    }
+
+    bytes
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Transfer Compressed Raster Data",
+        vec![ESC, '*' as u8, 'b' as u8],
+        CommandType::Context,
+        DataType::Custom,
+        Box::new(Handler {
+            params: vec![],
+            capacity: 0,
+        }),
+    )
+}
@@ -0,0 +1,61 @@
+use crate::commands::star::{push_raster_space, raster_value, RasterSetting};
+use crate::diagnostic::{Diagnostic, DiagnosticCode, Severity};
+use crate::{command::*, constants::*, context::*};
+
+//Longest move that is printed, about a meter at 203 dpi
+const MAX_MOVE: u32 = 8000;
+
+//ESC * r Y n NUL, moves down n dots by adding blank raster lines
+#[derive(Clone)]
+struct Handler {
+    setting: RasterSetting,
+}
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let n = raster_value(&command.data).min(MAX_MOVE);
+
+        if n > 0 {
+            push_raster_space(n, context);
+        }
+    }
+
+    fn get_diagnostics(&self, command: &Command) -> Vec<Diagnostic> {
+        let n = raster_value(&command.data);
+
+        if n > MAX_MOVE {
+            vec![Diagnostic::new(
+                command,
+                Severity::Warning,
+                DiagnosticCode::InvalidParameter,
+                format!("Moving {} dots is over the limit of {}", n, MAX_MOVE),
+            )]
+        } else {
+            vec![]
+        }
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        self.setting.push(data, byte)
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        self.setting.is_complete(command)
+    }
+
+    fn get_command_bytes(&self, command: &Command) -> (Vec<u8>, Vec<u8>) {
+        self.setting.get_command_bytes(command)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Move Raster Vertical Position",
+        vec![ESC, '*' as u8, 'r' as u8, 'Y' as u8],
+        CommandType::Context,
+        DataType::Custom,
+        Box::new(Handler {
+            setting: RasterSetting::new(),
+        }),
+    )
+}
@@ -0,0 +1,30 @@
+use crate::commands::star::raster_graphics;
+use crate::{command::*, constants::*, context::*, graphics::*};
+
+#[derive(Clone)]
+struct Handler;
+
+//ESC * r B, prints the raster data that is left
+impl CommandHandler for Handler {
+    fn get_graphics(&self, _command: &Command, context: &Context) -> Option<GraphicsCommand> {
+        raster_graphics(context)
+    }
+
+    fn get_device_command(
+        &self,
+        _command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        Some(vec![DeviceCommand::ClearBufferGraphics])
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Quit Raster Mode",
+        vec![ESC, '*' as u8, 'r' as u8, 'B' as u8],
+        CommandType::Graphics,
+        DataType::Empty,
+        Box::new(Handler {}),
+    )
+}
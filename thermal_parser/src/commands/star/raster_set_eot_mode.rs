@@ -0,0 +1,13 @@
+use crate::commands::star::RasterSetting;
+use crate::{command::*, constants::*};
+
+//ESC * r E n NUL, the cut and feed when the job ends, not rendered yet
+pub fn new() -> Command {
+    Command::new(
+        "Set Raster EOT Mode",
+        vec![ESC, '*' as u8, 'r' as u8, 'E' as u8],
+        CommandType::Control,
+        DataType::Custom,
+        Box::new(RasterSetting::new()),
+    )
+}
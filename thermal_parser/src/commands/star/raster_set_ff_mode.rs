@@ -0,0 +1,13 @@
+use crate::commands::star::RasterSetting;
+use crate::{command::*, constants::*};
+
+//ESC * r F n NUL, the cut and feed on a form feed, not rendered yet
+pub fn new() -> Command {
+    Command::new(
+        "Set Raster FF Mode",
+        vec![ESC, '*' as u8, 'r' as u8, 'F' as u8],
+        CommandType::Control,
+        DataType::Custom,
+        Box::new(RasterSetting::new()),
+    )
+}
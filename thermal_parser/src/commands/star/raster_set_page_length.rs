@@ -0,0 +1,13 @@
+use crate::commands::star::RasterSetting;
+use crate::{command::*, constants::*};
+
+//ESC * r P n NUL, 0 is continuous paper which is all that is rendered
+pub fn new() -> Command {
+    Command::new(
+        "Set Raster Page Length",
+        vec![ESC, '*' as u8, 'r' as u8, 'P' as u8],
+        CommandType::Control,
+        DataType::Custom,
+        Box::new(RasterSetting::new()),
+    )
+}
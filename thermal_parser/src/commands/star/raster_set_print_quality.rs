@@ -0,0 +1,13 @@
+use crate::commands::star::RasterSetting;
+use crate::{command::*, constants::*};
+
+//ESC * r Q n NUL, only changes the print speed
+pub fn new() -> Command {
+    Command::new(
+        "Set Raster Print Quality",
+        vec![ESC, '*' as u8, 'r' as u8, 'Q' as u8],
+        CommandType::Control,
+        DataType::Custom,
+        Box::new(RasterSetting::new()),
+    )
+}
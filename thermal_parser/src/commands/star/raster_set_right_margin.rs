@@ -0,0 +1,13 @@
+use crate::commands::star::RasterSetting;
+use crate::{command::*, constants::*};
+
+//ESC * r m r n NUL, the drivers send the margin as part of the raster data
+pub fn new() -> Command {
+    Command::new(
+        "Set Raster Right Margin",
+        vec![ESC, '*' as u8, 'r' as u8, 'm' as u8, 'r' as u8],
+        CommandType::Control,
+        DataType::Custom,
+        Box::new(RasterSetting::new()),
+    )
+}
@@ -0,0 +1,13 @@
+use crate::commands::star::RasterSetting;
+use crate::{command::*, constants::*};
+
+//ESC * r T n NUL, only applies to cut paper
+pub fn new() -> Command {
+    Command::new(
+        "Set Raster Top Margin",
+        vec![ESC, '*' as u8, 'r' as u8, 'T' as u8],
+        CommandType::Control,
+        DataType::Custom,
+        Box::new(RasterSetting::new()),
+    )
+}
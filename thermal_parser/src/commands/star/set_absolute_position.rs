@@ -0,0 +1,31 @@
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+fn get_pos(data: &[u8]) -> u32 {
+    let nl = *data.first().unwrap_or(&0u8) as u32;
+    let nh = *data.get(1).unwrap_or(&0u8) as u32;
+
+    nl + nh * 256
+}
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        context.set_x_absolute(get_pos(&command.data));
+    }
+
+    fn debug(&self, command: &Command, _context: &Context) -> String {
+        format!("{} --> {}", &command.name, get_pos(&command.data))
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Set Absolute Horizontal Position",
+        vec![ESC, GS, 'A' as u8],
+        CommandType::Context,
+        DataType::Double,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,43 @@
+use crate::command::DeviceCommand::{SetTextHeight, SetTextWidth};
+use crate::commands::star::expansion;
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn get_device_command(
+        &self,
+        command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        let height = *command.data.first().unwrap_or(&0u8);
+        let width = *command.data.get(1).unwrap_or(&0u8);
+
+        Some(vec![
+            SetTextWidth(expansion(width)),
+            SetTextHeight(expansion(height)),
+        ])
+    }
+
+    fn debug(&self, command: &Command, _context: &Context) -> String {
+        let height = *command.data.first().unwrap_or(&0u8);
+        let width = *command.data.get(1).unwrap_or(&0u8);
+        format!(
+            "{} w{} h{}",
+            command.name,
+            expansion(width),
+            expansion(height)
+        )
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Set Character Expansion",
+        vec![ESC, 'i' as u8],
+        CommandType::TextStyle,
+        DataType::Double,
+        Box::new(Handler {}),
+    )
+}
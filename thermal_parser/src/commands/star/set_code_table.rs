@@ -0,0 +1,86 @@
+//! Sets the code table to use for displaying text
+//!
+//! Star numbers its code pages differently from ESC/POS, so the
+//! pages are mapped to the ESC/POS code table with the same characters.
+//! Pages without one fall back to PC437.
+//!
+//! Page 0 [Normal]
+//! Page 1 [PC437: USA, Standard Europe]
+//! Page 2 [Katakana]
+//! Page 3 [PC437: USA, Standard Europe]
+//! Page 4 [PC858: Multilingual]
+//! Page 5 [PC852: Latin 2]
+//! Page 6 [PC860: Portuguese]
+//! Page 7 [PC861: Icelandic]
+//! Page 8 [PC863: Canadian-French]
+//! Page 9 [PC865: Nordic]
+//! Page 10 [PC866: Russian]
+//! Page 11 [PC855: Cyrillic]
+//! Page 12 [PC857: Turkish]
+//! Page 13 [PC862: Hebrew]
+//! Page 14 [PC864: Arabic]
+//! Page 15 [PC737: Greek]
+//! Page 16 [PC851: Greek]
+//! Page 17 [PC869: Greek]
+//! Page 32 [WPC1252]
+//! Page 33 [WPC1250: Latin 2]
+//! Page 34 [WPC1251: Cyrillic]
+//! Page 96 - 102 [Thai Character Code 42, 11, 13, 14, 16, 17, 18]
+
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+fn to_esc_pos(n: u8) -> u8 {
+    match n {
+        2 => 1,
+        4 => 19,
+        5 => 18,
+        6 => 3,
+        7 => 35,
+        8 => 4,
+        9 => 5,
+        10 => 17,
+        11 => 34,
+        12 => 13,
+        13 => 36,
+        14 => 37,
+        15 => 14,
+        16 => 11,
+        17 => 38,
+        32 => 16,
+        33 => 45,
+        34 => 46,
+        96..=102 => n - 76,
+        _ => 0,
+    }
+}
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let n = *command.data.first().unwrap_or(&0u8);
+        context.text.code_table = to_esc_pos(n);
+        context.update_decoder();
+    }
+
+    fn debug(&self, command: &Command, context: &Context) -> String {
+        format!(
+            "{} to {}. {} ({})",
+            command.name,
+            command.data.first().unwrap_or(&0u8),
+            context.text.decoder.name,
+            context.text.decoder.language
+        )
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Set Code Table",
+        vec![ESC, GS, 't' as u8],
+        CommandType::TextStyle,
+        DataType::Single,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,25 @@
+use crate::command::DeviceCommand::SetTextHeight;
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn get_device_command(
+        &self,
+        _command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        Some(vec![SetTextHeight(2)])
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Enable Double Height",
+        vec![ESC, SO],
+        CommandType::TextStyle,
+        DataType::Empty,
+        Box::new(Handler {}),
+    )
+}
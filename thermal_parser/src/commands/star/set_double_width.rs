@@ -0,0 +1,25 @@
+use crate::command::DeviceCommand::SetTextWidth;
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn get_device_command(
+        &self,
+        _command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        Some(vec![SetTextWidth(2)])
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Enable Double Width",
+        vec![SO],
+        CommandType::TextStyle,
+        DataType::Empty,
+        Box::new(Handler {}),
+    )
+}
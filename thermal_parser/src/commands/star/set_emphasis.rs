@@ -0,0 +1,20 @@
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, _command: &Command, context: &mut Context) {
+        context.text.bold = true;
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Enable Emphasis",
+        vec![ESC, 'E' as u8],
+        CommandType::TextStyle,
+        DataType::Empty,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,22 @@
+use crate::commands::star::parameter;
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let n = *command.data.first().unwrap_or(&0u8);
+        context.set_font(Font::from_raw(parameter(n)));
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Set Font",
+        vec![ESC, RS, 'F' as u8],
+        CommandType::TextStyle,
+        DataType::Single,
+        Box::new(Handler {}),
+    )
+}
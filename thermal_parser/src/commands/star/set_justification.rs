@@ -0,0 +1,32 @@
+use crate::command::DeviceCommand::Justify;
+use crate::commands::star::parameter;
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn get_device_command(
+        &self,
+        command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        let n = *command.data.first().unwrap_or(&0u8);
+
+        Some(vec![Justify(match parameter(n) {
+            1 => TextJustify::Center,
+            2 => TextJustify::Right,
+            _ => TextJustify::Left,
+        })])
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Set Text Justification",
+        vec![ESC, GS, 'a' as u8],
+        CommandType::Control,
+        DataType::Single,
+        Box::new(Handler {}),
+    )
+}
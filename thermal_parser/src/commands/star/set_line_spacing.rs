@@ -0,0 +1,24 @@
+use crate::commands::star::{mm_to_dots, parameter};
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    //n = 0 is 3mm and n = 1 is 4mm
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let n = *command.data.first().unwrap_or(&0u8);
+        let mm = if parameter(n) == 1 { 4.0 } else { 3.0 };
+        context.text.line_spacing = mm_to_dots(mm, context).min(255) as u8;
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Set Line Spacing",
+        vec![ESC, 'z' as u8],
+        CommandType::Context,
+        DataType::Single,
+        Box::new(Handler {}),
+    )
+}
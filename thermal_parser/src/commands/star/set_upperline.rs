@@ -0,0 +1,17 @@
+use crate::{command::*, constants::*};
+
+#[derive(Clone)]
+struct Handler;
+
+//Lines over the text are not rendered yet
+impl CommandHandler for Handler {}
+
+pub fn new() -> Command {
+    Command::new(
+        "Enable Upperline",
+        vec![ESC, '_' as u8],
+        CommandType::TextStyle,
+        DataType::Single,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,20 @@
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, _command: &Command, context: &mut Context) {
+        context.text.upside_down = true;
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Enable Upside Down Mode",
+        vec![SI],
+        CommandType::TextStyle,
+        DataType::Empty,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,27 @@
+use crate::command::DeviceCommand::SetTextWidth;
+use crate::commands::star::expansion;
+use crate::{command::*, constants::*, context::*};
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn get_device_command(
+        &self,
+        command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        let n = *command.data.first().unwrap_or(&0u8);
+        Some(vec![SetTextWidth(expansion(n))])
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Set Width Expansion",
+        vec![ESC, 'W' as u8],
+        CommandType::TextStyle,
+        DataType::Single,
+        Box::new(Handler {}),
+    )
+}
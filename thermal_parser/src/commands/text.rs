@@ -6,14 +6,11 @@ struct Handler;
 
 impl CommandHandler for Handler {
     fn get_text(&self, command: &Command, context: &Context) -> Option<TextSpan> {
-        let decoded = context.text.decoder.decode_utf8(&command.data as &[u8]);
+        let decoded = context.decode_text(&command.data);
         Some(TextSpan::new(decoded, context))
     }
     fn debug(&self, command: &Command, context: &Context) -> String {
-        format!(
-            "{:?}",
-            context.text.decoder.decode_utf8(&command.data as &[u8])
-        )
+        format!("{:?}", context.decode_text(&command.data))
     }
 }
 
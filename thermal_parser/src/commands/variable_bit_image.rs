@@ -0,0 +1,129 @@
+///
+/// This command prints a bit image with a variable vertical size
+///
+/// GS Q 0 m xL xH yL yH d1...dk
+///
+/// The image is xL + xH * 256 dots wide and (yL + yH * 256) * 8 dots
+/// high, the data is sent by column. The mode scales the image to
+/// double width, double height or both.
+///
+use crate::diagnostic::{Diagnostic, DiagnosticCode, Severity};
+use crate::{command::*, constants::*, context::*, graphics::*};
+
+#[derive(Clone)]
+struct Handler {
+    width: u32,
+    height: u32,
+    capacity: usize,
+    mode: u8,
+    accept_data: bool,
+    params: Vec<u8>,
+}
+
+impl CommandHandler for Handler {
+    fn get_graphics(&self, command: &Command, context: &Context) -> Option<GraphicsCommand> {
+        if self.width == 0 || self.height == 0 {
+            return Some(GraphicsCommand::Error(format!(
+                "A {}x{} bit image can't be printed",
+                self.width, self.height
+            )));
+        }
+
+        if command.data.len() < self.capacity {
+            return Some(GraphicsCommand::Error(format!(
+                "Not enough data for a {}x{} bit image",
+                self.width, self.height
+            )));
+        }
+
+        Some(GraphicsCommand::image_from_column_bytes_single_color(
+            self.width,
+            self.height,
+            bit_image_stretch(self.mode),
+            context.graphics.render_colors.color_for_number(1),
+            ImageFlow::Block,
+            &command.data,
+        ))
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        if !self.accept_data {
+            if data.len() < 4 {
+                data.push(byte);
+                return true;
+            }
+
+            self.mode = data[0];
+            self.width = data[1] as u32 + data[2] as u32 * 256;
+            self.height = (data[3] as u32 + byte as u32 * 256) * 8;
+            self.capacity =
+                usize::try_from(self.width as u64 * self.height as u64 / 8).unwrap_or(usize::MAX);
+            self.params = vec![data[0], data[1], data[2], data[3], byte];
+            self.accept_data = true;
+            data.clear();
+            return true;
+        }
+
+        if data.len() >= self.capacity {
+            return false;
+        }
+        data.push(byte);
+        true
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        self.accept_data && command.data.len() >= self.capacity
+    }
+
+    fn get_diagnostics(&self, command: &Command) -> Vec<Diagnostic> {
+        if self.accept_data && command.data.len() < self.capacity {
+            vec![Diagnostic::new(
+                command,
+                Severity::Error,
+                DiagnosticCode::TruncatedCommand,
+                format!(
+                    "Expected {} bytes of image data but the input ended after {}",
+                    self.capacity,
+                    command.data.len()
+                ),
+            )]
+        } else if self.accept_data && (self.width == 0 || self.height == 0) {
+            vec![Diagnostic::new(
+                command,
+                Severity::Warning,
+                DiagnosticCode::InvalidSize,
+                format!(
+                    "A {}x{} bit image can't be printed",
+                    self.width, self.height
+                ),
+            )]
+        } else {
+            vec![]
+        }
+    }
+
+    //Used when converting commands back into other formats i.e. Thermal format
+    fn get_command_bytes(&self, command: &Command) -> (Vec<u8>, Vec<u8>) {
+        let mut data = self.params.clone();
+        let commands = command.commands.to_vec();
+        data.extend(command.data.clone());
+        (commands, data)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Variable Vertical Size Bit Image",
+        vec![GS, 'Q' as u8, '0' as u8],
+        CommandType::Graphics,
+        DataType::Custom,
+        Box::new(Handler {
+            width: 0,
+            height: 0,
+            capacity: 0,
+            mode: 0,
+            accept_data: false,
+            params: vec![],
+        }),
+    )
+}
@@ -0,0 +1,36 @@
+use crate::command::*;
+use crate::commands::zpl::*;
+use crate::context::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//^BY w,r,h sets the module width and height of the barcodes that follow,
+//the ratio of wide to narrow bars isn't supported
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let params = parameters(&command.data);
+
+        if let Some(width) = number(&params, 0) {
+            context.barcode.width = width.clamp(1, 10) as u8;
+        }
+
+        if let Some(height) = number(&params, 2) {
+            context.barcode.height = height.clamp(1, 255) as u8;
+        }
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_parameter(data, byte)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Bar Code Field Default",
+        vec!['^' as u8, 'B' as u8, 'Y' as u8],
+        CommandType::Context,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,52 @@
+use crate::command::*;
+use crate::commands::zpl::*;
+use crate::context::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//^CF f,h,w sets the font for fields without an ^A
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let (width, height) = size(command, context);
+        context.label.font_width = width;
+        context.label.font_height = height;
+        set_font_size(context, width, height);
+    }
+
+    fn get_device_command(
+        &self,
+        _command: &Command,
+        context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        //The context was applied first
+        Some(font_size_commands(
+            context.label.font_width,
+            context.label.font_height,
+        ))
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_parameter(data, byte)
+    }
+}
+
+fn size(command: &Command, context: &Context) -> (u32, u32) {
+    let params = parameters(&command.data);
+    let font = params
+        .first()
+        .and_then(|f| f.bytes().next())
+        .unwrap_or(b'0');
+
+    font_size(font, number(&params, 1), number(&params, 2), context)
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Change Default Font",
+        vec!['^' as u8, 'C' as u8, 'F' as u8],
+        CommandType::ContextControl,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,73 @@
+use crate::command::*;
+use crate::commands::barcode::BarcodeType;
+use crate::commands::label::barcode_graphics;
+use crate::commands::zpl::*;
+use crate::context::*;
+use crate::graphics::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//The invocation code after a > as Code 128 Auto data,
+//where {1 to {4 are FNC1 to FNC4 and {A {B {C {S pick the subsets
+fn invocation_code(code: u8) -> &'static [u8] {
+    match code {
+        b'<' | b'0' => b">",
+        b'=' => b"~",
+        b'2' => b"{3",
+        b'3' => b"{2",
+        b'4' => b"{S",
+        b'5' | b';' => b"{C",
+        b'6' | b':' => b"{B",
+        b'7' | b'9' => b"{A",
+        b'8' => b"{1",
+        _ => b"",
+    }
+}
+
+//^BC o,h,f,g,e,m
+//The subsets are still picked automatically, the subset
+//invocation codes like >: are read but don't change the symbol
+impl CommandHandler for Handler {
+    fn get_graphics(&self, command: &Command, context: &Context) -> Option<GraphicsCommand> {
+        let params = parameters(&command.data);
+        let mut data = vec![];
+        let mut bytes = field_data(&command.data).into_iter();
+
+        while let Some(byte) = bytes.next() {
+            match byte {
+                b'>' => data.extend_from_slice(invocation_code(bytes.next().unwrap_or(0))),
+                b'{' => data.extend_from_slice(b"{{"),
+                _ => data.push(byte),
+            }
+        }
+
+        barcode_graphics(
+            BarcodeType::Code128Auto,
+            command,
+            context,
+            data,
+            None,
+            number(&params, 1),
+            interpretation_line(&params, 2, 3),
+        )
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_field(data, byte)
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        is_field_complete(&command.data)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Code 128 Bar Code",
+        vec!['^' as u8, 'B' as u8, 'C' as u8],
+        CommandType::Graphics,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,44 @@
+use crate::command::*;
+use crate::commands::barcode::BarcodeType;
+use crate::commands::label::barcode_graphics;
+use crate::commands::zpl::*;
+use crate::context::*;
+use crate::graphics::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//^B3 o,e,h,f,g, the mod 43 check digit isn't supported
+impl CommandHandler for Handler {
+    fn get_graphics(&self, command: &Command, context: &Context) -> Option<GraphicsCommand> {
+        let params = parameters(&command.data);
+
+        barcode_graphics(
+            BarcodeType::Code39,
+            command,
+            context,
+            field_data(&command.data),
+            None,
+            number(&params, 2),
+            interpretation_line(&params, 3, 4),
+        )
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_field(data, byte)
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        is_field_complete(&command.data)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Code 39 Bar Code",
+        vec!['^' as u8, 'B' as u8, '3' as u8],
+        CommandType::Graphics,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
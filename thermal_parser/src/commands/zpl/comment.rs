@@ -0,0 +1,21 @@
+use crate::command::*;
+use crate::commands::zpl::push_parameter;
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_parameter(data, byte)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Comment",
+        vec!['^' as u8, 'F' as u8, 'X' as u8],
+        CommandType::Control,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,44 @@
+use crate::command::*;
+use crate::commands::barcode::BarcodeType;
+use crate::commands::label::barcode_graphics;
+use crate::commands::zpl::*;
+use crate::context::*;
+use crate::graphics::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//^BE o,h,f,g
+impl CommandHandler for Handler {
+    fn get_graphics(&self, command: &Command, context: &Context) -> Option<GraphicsCommand> {
+        let params = parameters(&command.data);
+
+        barcode_graphics(
+            BarcodeType::Ean13,
+            command,
+            context,
+            field_data(&command.data),
+            None,
+            number(&params, 1),
+            interpretation_line(&params, 2, 3),
+        )
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_field(data, byte)
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        is_field_complete(&command.data)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "EAN-13 Bar Code",
+        vec!['^' as u8, 'B' as u8, 'E' as u8],
+        CommandType::Graphics,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,26 @@
+use crate::command::*;
+use crate::commands::label::print_label_commands;
+use crate::context::*;
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn get_device_command(
+        &self,
+        _command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        Some(print_label_commands())
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "End Format",
+        vec!['^' as u8, 'X' as u8, 'Z' as u8],
+        CommandType::Control,
+        DataType::Empty,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,52 @@
+use crate::command::*;
+use crate::commands::zpl::*;
+use crate::context::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//^FB w,l,s,j wraps the field text at the width of the block with the
+//given justification, the number of lines and line spacing are ignored
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let params = parameters(&command.data);
+        let mut area = context.page_mode.logical_area.clone();
+
+        if let Some(width) = number(&params, 0) {
+            area.w = area.w.min(width);
+        }
+
+        context.set_page_area(area);
+    }
+
+    fn get_device_command(
+        &self,
+        command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        let justify = match parameters(&command.data).get(3).map(|j| j.as_str()) {
+            Some("C") => TextJustify::Center,
+            Some("R") => TextJustify::Right,
+            _ => TextJustify::Left,
+        };
+
+        Some(vec![
+            DeviceCommand::ChangePageArea,
+            DeviceCommand::Justify(justify),
+        ])
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_parameter(data, byte)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Field Block",
+        vec!['^' as u8, 'F' as u8, 'B' as u8],
+        CommandType::ContextControl,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
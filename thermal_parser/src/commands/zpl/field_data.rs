@@ -0,0 +1,43 @@
+use crate::command::*;
+use crate::commands::zpl::push_parameter;
+use crate::context::*;
+use crate::text::TextSpan;
+
+#[derive(Clone)]
+struct Handler;
+
+//Line breaks in the data aren't printed
+fn text(command: &Command, context: &Context) -> String {
+    let data: Vec<u8> = command
+        .data
+        .iter()
+        .filter(|b| **b != b'\r' && **b != b'\n')
+        .copied()
+        .collect();
+
+    context.text.decoder.decode_utf8(&data)
+}
+
+impl CommandHandler for Handler {
+    fn get_text(&self, command: &Command, context: &Context) -> Option<TextSpan> {
+        Some(TextSpan::new(text(command, context), context))
+    }
+
+    fn debug(&self, command: &Command, context: &Context) -> String {
+        format!("{:?}", text(command, context))
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_parameter(data, byte)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Field Data",
+        vec!['^' as u8, 'F' as u8, 'D' as u8],
+        CommandType::Text,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,38 @@
+use crate::command::*;
+use crate::commands::label::set_field_origin;
+use crate::commands::zpl::{number, parameters, push_parameter};
+use crate::context::*;
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let params = parameters(&command.data);
+        let x = number(&params, 0).unwrap_or(0);
+        let y = number(&params, 1).unwrap_or(0);
+        set_field_origin(context, x, y);
+    }
+
+    fn get_device_command(
+        &self,
+        _command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        Some(vec![DeviceCommand::ChangePageArea])
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_parameter(data, byte)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Field Origin",
+        vec!['^' as u8, 'F' as u8, 'O' as u8],
+        CommandType::ContextControl,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,34 @@
+use crate::command::*;
+use crate::commands::zpl::*;
+use crate::context::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//Ends the field, the next field starts with the default font
+impl CommandHandler for Handler {
+    fn apply_context(&self, _command: &Command, context: &mut Context) {
+        let (width, height) = (context.label.font_width, context.label.font_height);
+        set_font_size(context, width, height);
+    }
+
+    fn get_device_command(
+        &self,
+        _command: &Command,
+        context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        let mut commands = font_size_commands(context.label.font_width, context.label.font_height);
+        commands.push(DeviceCommand::Justify(TextJustify::Left));
+        Some(commands)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Field Separator",
+        vec!['^' as u8, 'F' as u8, 'S' as u8],
+        CommandType::ContextControl,
+        DataType::Empty,
+        Box::new(Handler {}),
+    )
+}
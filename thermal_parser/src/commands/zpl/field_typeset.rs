@@ -0,0 +1,41 @@
+use crate::command::*;
+use crate::commands::label::set_field_origin;
+use crate::commands::zpl::{number, parameters, push_parameter};
+use crate::context::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//The y of a typeset field is the baseline of the text, the field is moved
+//up by the height of the current font since a later ^A isn't known yet
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let params = parameters(&command.data);
+        let height = context.text.character_height as u32 * context.text.height_mult as u32;
+        let x = number(&params, 0).unwrap_or(0);
+        let y = number(&params, 1).unwrap_or(0).saturating_sub(height);
+        set_field_origin(context, x, y);
+    }
+
+    fn get_device_command(
+        &self,
+        _command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        Some(vec![DeviceCommand::ChangePageArea])
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_parameter(data, byte)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Field Typeset",
+        vec!['^' as u8, 'F' as u8, 'T' as u8],
+        CommandType::ContextControl,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,45 @@
+use crate::command::*;
+use crate::commands::zpl::*;
+use crate::context::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//^A fo,h,w where f is the font and o the orientation, only normal
+//orientation is supported. The font is reset by the ^FS.
+fn size(command: &Command, context: &Context) -> (u32, u32) {
+    let font = *command.data.first().unwrap_or(&b'0');
+    let params = parameters(command.data.get(1..).unwrap_or(&[]));
+
+    font_size(font, number(&params, 1), number(&params, 2), context)
+}
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let (width, height) = size(command, context);
+        set_font_size(context, width, height);
+    }
+
+    fn get_device_command(
+        &self,
+        command: &Command,
+        context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        let (width, height) = size(command, context);
+        Some(font_size_commands(width, height))
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_parameter(data, byte)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Font",
+        vec!['^' as u8, 'A' as u8],
+        CommandType::TextStyle,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,44 @@
+use crate::command::*;
+use crate::commands::label::box_graphics;
+use crate::commands::zpl::*;
+use crate::context::*;
+use crate::graphics::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//^GB w,h,t,c,r, the box is filled when the thickness is as large as
+//the width or height. Rounded corners aren't supported.
+impl CommandHandler for Handler {
+    fn get_graphics(&self, command: &Command, context: &Context) -> Option<GraphicsCommand> {
+        let params = parameters(&command.data);
+        let thickness = number(&params, 2).unwrap_or(1).max(1);
+        let width = number(&params, 0).unwrap_or(thickness).max(thickness);
+        let height = number(&params, 1).unwrap_or(thickness).max(thickness);
+
+        let color = match params.get(3).map(|c| c.as_str()) {
+            Some("W") => context.graphics.render_colors.color_for_number(0),
+            _ => context.graphics.render_colors.color_for_number(1),
+        };
+
+        Some(box_graphics(context, width, height, thickness, color))
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_field(data, byte)
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        is_field_complete(&command.data)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Graphic Box",
+        vec!['^' as u8, 'G' as u8, 'B' as u8],
+        CommandType::Graphics,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,148 @@
+//! ZPL II commands, for Zebra label printers
+//!
+//! Commands start with ^ or ~ followed by two letters, the parameters
+//! are separated by commas and end at the next command. Fields start
+//! with ^FO, set up with commands like ^A or ^BC, take their data from
+//! ^FD and end with ^FS.
+//!
+//! Barcodes and boxes read everything up to the ^FS, so the
+//! field is one command. See command_sets::zpl for the full set.
+
+use crate::command::DeviceCommand;
+use crate::commands::label::font_for_size;
+use crate::context::{Context, HumanReadableInterface};
+
+pub mod bar_code_defaults;
+pub mod change_default_font;
+pub mod code128;
+pub mod code39;
+pub mod comment;
+pub mod ean13;
+pub mod end_format;
+pub mod field_block;
+pub mod field_data;
+pub mod field_origin;
+pub mod field_separator;
+pub mod field_typeset;
+pub mod font;
+pub mod graphic_box;
+pub mod label_length;
+pub mod print_width;
+pub mod qr_code;
+pub mod start_format;
+pub mod unknown;
+pub mod upc_a;
+
+//Parameters go up to the next command
+pub(crate) fn push_parameter(data: &mut Vec<u8>, byte: u8) -> bool {
+    if byte == b'^' || byte == b'~' {
+        return false;
+    }
+
+    data.push(byte);
+    true
+}
+
+//A field goes up to and including the ^FS
+pub(crate) fn push_field(data: &mut Vec<u8>, byte: u8) -> bool {
+    if is_field_complete(data) {
+        return false;
+    }
+
+    data.push(byte);
+    true
+}
+
+pub(crate) fn is_field_complete(data: &[u8]) -> bool {
+    data.ends_with(b"^FS")
+}
+
+//Line breaks between commands are ignored
+pub(crate) fn parameters(data: &[u8]) -> Vec<String> {
+    let end = data.iter().position(|b| *b == b'^').unwrap_or(data.len());
+
+    String::from_utf8_lossy(&data[..end])
+        .replace(['\r', '\n'], "")
+        .split(',')
+        .map(|p| p.trim().to_string())
+        .collect()
+}
+
+pub(crate) fn number(parameters: &[String], index: usize) -> Option<u32> {
+    parameters.get(index).and_then(|p| p.parse::<u32>().ok())
+}
+
+//Yes or no parameters
+pub(crate) fn flag(parameters: &[String], index: usize, default: bool) -> bool {
+    match parameters.get(index).map(|p| p.as_str()) {
+        Some("Y") => true,
+        Some("N") => false,
+        _ => default,
+    }
+}
+
+//The data of the ^FD in a field, up to the next command
+pub(crate) fn field_data(data: &[u8]) -> Vec<u8> {
+    let start = match data.windows(3).position(|w| w == b"^FD") {
+        Some(i) => i + 3,
+        None => return vec![],
+    };
+    let end = data[start..]
+        .iter()
+        .position(|b| *b == b'^')
+        .map_or(data.len(), |i| start + i);
+
+    data[start..end]
+        .iter()
+        .filter(|b| **b != b'\r' && **b != b'\n')
+        .copied()
+        .collect()
+}
+
+//The size in dots of a font, font 0 is scalable and its
+//characters are about half as wide as the given width
+pub(crate) fn font_size(
+    font: u8,
+    height: Option<u32>,
+    width: Option<u32>,
+    context: &Context,
+) -> (u32, u32) {
+    let height = height.unwrap_or(context.label.font_height);
+
+    if font == b'0' {
+        (width.unwrap_or(height) / 2, height)
+    } else {
+        (width.unwrap_or(context.label.font_width), height)
+    }
+}
+
+//Sets the font that fits the size, the width and height
+//multipliers are set with the device commands
+pub(crate) fn set_font_size(context: &mut Context, width: u32, height: u32) {
+    let (font, _, _) = font_for_size(width, height);
+    context.set_font(font);
+}
+
+pub(crate) fn font_size_commands(width: u32, height: u32) -> Vec<DeviceCommand> {
+    let (_, width_mult, height_mult) = font_for_size(width, height);
+
+    vec![
+        DeviceCommand::SetTextWidth(width_mult),
+        DeviceCommand::SetTextHeight(height_mult),
+    ]
+}
+
+//The interpretation line is printed below unless it's above or turned off
+pub(crate) fn interpretation_line(
+    parameters: &[String],
+    line: usize,
+    above: usize,
+) -> HumanReadableInterface {
+    if !flag(parameters, line, true) {
+        HumanReadableInterface::None
+    } else if flag(parameters, above, false) {
+        HumanReadableInterface::Above
+    } else {
+        HumanReadableInterface::Below
+    }
+}
@@ -0,0 +1,38 @@
+use crate::command::*;
+use crate::commands::label::*;
+use crate::commands::zpl::{number, parameters, push_parameter};
+use crate::context::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//The label starts over with the new width, so this goes before the fields
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        let (width, length) = label_size(context);
+        let width = number(&parameters(&command.data), 0).unwrap_or(width);
+        begin_label(context, width, length);
+    }
+
+    fn get_device_command(
+        &self,
+        _command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        Some(begin_label_commands())
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_parameter(data, byte)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Print Width",
+        vec!['^' as u8, 'P' as u8, 'W' as u8],
+        CommandType::ContextControl,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,62 @@
+use crate::command::*;
+use crate::commands::zpl::*;
+use crate::context::*;
+use crate::graphics::*;
+use crate::subcommands::gs_code2d::qr_store::store;
+
+#[derive(Clone)]
+struct Handler;
+
+//^BQ a,b,c where b is the model and c the magnification. The field data
+//starts with the error correction and input mode, like QA,1234, only
+//automatic input is supported.
+impl CommandHandler for Handler {
+    fn get_graphics(&self, command: &Command, context: &Context) -> Option<GraphicsCommand> {
+        let params = parameters(&command.data);
+        let data = field_data(&command.data);
+        let mut context = context.clone();
+
+        context.code2d.qr_model = match number(&params, 1) {
+            Some(1) => QrModel::Model1,
+            _ => QrModel::Model2,
+        };
+        context.code2d.qr_size = number(&params, 2).unwrap_or(2).clamp(1, 10) as u8;
+
+        let data = if data.get(2) == Some(&b',') {
+            context.code2d.qr_error_correction = match data[0] {
+                b'H' => QrErrorCorrection::H,
+                b'Q' => QrErrorCorrection::Q,
+                b'L' => QrErrorCorrection::L,
+                _ => QrErrorCorrection::M,
+            };
+            &data[3..]
+        } else {
+            &data[..]
+        };
+
+        store(data, &mut context);
+
+        match context.code2d.symbol_storage {
+            Some(symbol) => Some(symbol),
+            None => Some(GraphicsCommand::Error("Invalid QR code data".to_string())),
+        }
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_field(data, byte)
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        is_field_complete(&command.data)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "QR Code",
+        vec!['^' as u8, 'B' as u8, 'Q' as u8],
+        CommandType::Graphics,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
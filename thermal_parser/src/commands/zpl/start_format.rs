@@ -0,0 +1,32 @@
+use crate::command::*;
+use crate::commands::label::*;
+use crate::context::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//Each format is a new label the same size as the previous one
+impl CommandHandler for Handler {
+    fn apply_context(&self, _command: &Command, context: &mut Context) {
+        let (width, length) = label_size(context);
+        begin_label(context, width, length);
+    }
+
+    fn get_device_command(
+        &self,
+        _command: &Command,
+        _context: &Context,
+    ) -> Option<Vec<DeviceCommand>> {
+        Some(begin_label_commands())
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "Start Format",
+        vec!['^' as u8, 'X' as u8, 'A' as u8],
+        CommandType::ContextControl,
+        DataType::Empty,
+        Box::new(Handler {}),
+    )
+}
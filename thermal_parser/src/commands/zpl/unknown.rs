@@ -0,0 +1,17 @@
+use crate::command::*;
+
+#[derive(Clone)]
+struct Handler;
+
+impl CommandHandler for Handler {}
+
+//Collects the parameters up to the next command
+pub fn new() -> Command {
+    Command::new(
+        "Unknown Command",
+        vec!['^' as u8, '~' as u8],
+        CommandType::Unknown,
+        DataType::Unknown,
+        Box::new(Handler {}),
+    )
+}
@@ -0,0 +1,44 @@
+use crate::command::*;
+use crate::commands::barcode::BarcodeType;
+use crate::commands::label::barcode_graphics;
+use crate::commands::zpl::*;
+use crate::context::*;
+use crate::graphics::*;
+
+#[derive(Clone)]
+struct Handler;
+
+//^BU o,h,f,g,e, the check digit is always printed
+impl CommandHandler for Handler {
+    fn get_graphics(&self, command: &Command, context: &Context) -> Option<GraphicsCommand> {
+        let params = parameters(&command.data);
+
+        barcode_graphics(
+            BarcodeType::UpcA,
+            command,
+            context,
+            field_data(&command.data),
+            None,
+            number(&params, 1),
+            interpretation_line(&params, 2, 3),
+        )
+    }
+
+    fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
+        push_field(data, byte)
+    }
+
+    fn is_complete(&self, command: &Command) -> bool {
+        is_field_complete(&command.data)
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "UPC-A Bar Code",
+        vec!['^' as u8, 'B' as u8, 'U' as u8],
+        CommandType::Graphics,
+        DataType::Custom,
+        Box::new(Handler {}),
+    )
+}
@@ -9,3 +9,10 @@ pub static FS: u8 = 0x1C;
 pub static DLE: u8 = 0x10;
 pub static CAN: u8 = 0x18;
 pub static US: u8 = 0x1F;
+pub static BEL: u8 = 0x07;
+pub static SO: u8 = 0x0E;
+pub static SI: u8 = 0x0F;
+pub static DC2: u8 = 0x12;
+pub static DC4: u8 = 0x14;
+pub static RS: u8 = 0x1E;
+pub static EOT: u8 = 0x04;
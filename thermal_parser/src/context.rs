@@ -1,7 +1,6 @@
-use crate::decoder::{get_codepage, Codepage};
-use crate::graphics;
-use crate::graphics::{GraphicsCommand, ImageRef, RGBA};
-use crate::text::TextSpan;
+use crate::decoder::{get_codepage, get_multi_byte_encoding, Codepage, MultiByteEncoding};
+use crate::graphics::{GraphicsCommand, ImageRef, ImageRefStorage, RGBA};
+use crate::text::{TextSpan, UserCharacters};
 use std::collections::HashMap;
 use std::mem;
 
@@ -26,7 +25,7 @@ pub enum TextUnderline {
     Double,
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum Font {
     A,
     B,
@@ -50,6 +49,17 @@ impl Font {
             _ => Font::A,
         }
     }
+    pub fn to_raw(&self) -> u8 {
+        match self {
+            Font::A => 0,
+            Font::B => 1,
+            Font::C => 2,
+            Font::D => 3,
+            Font::E => 4,
+            Font::SpecialA => 97,
+            Font::SpecialB => 98,
+        }
+    }
     //Currently the rest of the fonts default to font b
     //We don't have enough information on C D E or the special fonts
     pub fn to_size(&self) -> (u8, u8) {
@@ -77,6 +87,8 @@ pub struct Context {
     pub code2d: Code2DContext,
     pub graphics: GraphicsContext,
     pub page_mode: PageModeContext,
+    pub label: LabelContext,
+    pub macros: MacroContext,
 }
 
 #[derive(Clone)]
@@ -104,6 +116,31 @@ pub struct TextContext {
     pub shadow: bool,
     pub smoothing: bool,
     pub tabs: Vec<u8>,
+
+    //Downloaded characters are only printed when the user-defined set is selected
+    pub user_defined: bool,
+    pub user_characters: UserCharacters,
+
+    pub kanji: KanjiContext,
+}
+
+//Multi byte characters are only decoded while Kanji mode is on
+#[derive(Clone)]
+pub struct KanjiContext {
+    pub enabled: bool,
+    //0 JIS, 1 Shift JIS, 2 Shift JIS-2004
+    pub code_system: u8,
+    //Selected by the international character set and the code system
+    pub encoding: Option<MultiByteEncoding>,
+    pub underline: TextUnderline,
+    pub double_width: bool,
+    pub double_height: bool,
+    //Spacing in dots on each side of a Kanji character
+    pub left_spacing: u8,
+    pub right_spacing: u8,
+    //Set by the memory switch of printers that use Big5
+    //instead of GB18030 for the China character set
+    pub big5: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -128,9 +165,12 @@ impl RenderColors {
 
 #[derive(Clone)]
 pub struct GraphicsContext {
-    //Main rendering area
+    //Main rendering area, the width is the width of the print area
     pub render_area: RenderArea,
 
+    //Start of the print area in standard mode, lines start here
+    pub left_margin: u32,
+
     pub render_colors: RenderColors,
 
     //Paper area (unprintable paper margins)
@@ -153,6 +193,14 @@ pub struct BarcodeContext {
     pub font: Font,
 }
 
+//Settings for label printer languages like ZPL
+#[derive(Clone)]
+pub struct LabelContext {
+    //Font size in dots for fields that don't set their own
+    pub font_width: u32,
+    pub font_height: u32,
+}
+
 #[derive(Clone, Debug)]
 pub enum QrModel {
     Model1, //Numeric data
@@ -168,9 +216,29 @@ pub enum QrErrorCorrection {
     H,
 }
 
+//Macros are kept when the printer is initialized
+#[derive(Clone, Default)]
+pub struct MacroContext {
+    //Commands are recorded between two GS :
+    pub recording: bool,
+    pub data: Vec<u8>,
+}
+
+impl MacroContext {
+    //Printers only have room for this many bytes
+    pub const MAX_LEN: usize = 2048;
+
+    //Bytes past the maximum length are not recorded
+    pub fn record(&mut self, bytes: &[u8]) {
+        let available = Self::MAX_LEN.saturating_sub(self.data.len());
+        self.data
+            .extend_from_slice(&bytes[..bytes.len().min(available)]);
+    }
+}
+
 #[derive(Clone)]
 pub struct Code2DContext {
-    pub symbol_storage: Option<graphics::Code2D>,
+    pub symbol_storage: Option<GraphicsCommand>,
 
     pub qr_model: QrModel,
     pub qr_error_correction: QrErrorCorrection,
@@ -181,6 +249,7 @@ pub struct Code2DContext {
     pub pdf417_width: u8,
     pub pdf417_row_height: u8,
     pub pdf417_err_correction: u8,
+    pub pdf417_err_correction_ratio: u8,
     pub pdf417_is_truncated: bool,
 
     pub maxicode_mode: u8,
@@ -191,6 +260,10 @@ pub struct Code2DContext {
     pub composite_width: u8,
     pub composite_max_width: u32,
     pub composite_font: Font,
+    pub composite_human_readable: HumanReadableInterface,
+    //Symbol type and data of the linear and 2D components
+    pub composite_linear: Option<(u8, Vec<u8>)>,
+    pub composite_2d: Option<(u8, Vec<u8>)>,
 
     pub aztec_mode: u8,
     pub aztec_layers: u8,
@@ -487,6 +560,19 @@ impl Context {
                 shadow_color: render_colors.color_1,
                 smoothing: false,
                 tabs: vec![8; 32], //Every 8 character widths is a tab stop
+                user_defined: false,
+                user_characters: UserCharacters::default(),
+                kanji: KanjiContext {
+                    enabled: false,
+                    code_system: 1,
+                    encoding: None,
+                    underline: TextUnderline::Off,
+                    double_width: false,
+                    double_height: false,
+                    left_spacing: 0,
+                    right_spacing: 0,
+                    big5: false,
+                },
             },
             barcode: BarcodeContext {
                 human_readable: HumanReadableInterface::None,
@@ -501,24 +587,28 @@ impl Context {
                 qr_size: 3,
                 pdf417_columns: 0,
                 pdf417_rows: 0,
-                pdf417_width: 0,
-                pdf417_row_height: 0,
+                pdf417_width: 3,
+                pdf417_row_height: 3,
                 pdf417_err_correction: 0,
+                pdf417_err_correction_ratio: 1,
                 pdf417_is_truncated: false,
                 maxicode_mode: 0,
-                gs1_databar_width: 0,
+                gs1_databar_width: 2,
                 gs1_databar_max_width: 0,
-                composite_width: 0,
+                composite_width: 2,
                 composite_max_width: 0,
                 composite_font: Font::A,
+                composite_human_readable: HumanReadableInterface::None,
+                composite_linear: None,
+                composite_2d: None,
                 aztec_mode: 0,
                 aztec_layers: 0,
-                aztec_size: 0,
-                aztec_error_correction: 0,
+                aztec_size: 3,
+                aztec_error_correction: 23,
                 datamatrix_type: 0,
                 datamatrix_columns: 0,
                 datamatrix_rows: 0,
-                datamatrix_width: 0,
+                datamatrix_width: 3,
             },
             graphics: GraphicsContext {
                 render_colors,
@@ -529,6 +619,7 @@ impl Context {
                     w: render_width,
                     h: 0,
                 },
+                left_margin: 0,
                 paper_area: RenderArea {
                     x: paper_left_margin,
                     y: paper_right_margin,
@@ -568,6 +659,11 @@ impl Context {
                 direction: PrintDirection::TopLeft2Right,
                 previous_direction: PrintDirection::TopLeft2Right,
             },
+            label: LabelContext {
+                font_width: 12,
+                font_height: 24,
+            },
+            macros: MacroContext::default(),
         }
     }
 
@@ -578,12 +674,38 @@ impl Context {
         new_context
     }
 
+    /// Stands in for the memory switch of printers that decode the
+    /// China character set as Big5, the setting is kept by a reset
+    pub fn set_big5(&mut self, big5: bool) {
+        self.text.kanji.big5 = big5;
+
+        if let Some(default) = &mut self.default {
+            default.text.kanji.big5 = big5;
+        }
+
+        self.update_decoder();
+    }
+
     pub fn reset(&mut self) {
         if let Some(default) = &self.default {
             self.text = default.text.clone();
             self.barcode = default.barcode.clone();
             self.code2d = default.code2d.clone();
+
+            //Non-volatile images survive a reset
+            let stored_graphics = mem::take(&mut self.graphics.stored_graphics);
             self.graphics = default.graphics.clone();
+            self.graphics.stored_graphics = stored_graphics
+                .into_iter()
+                .filter(|(k, _)| {
+                    matches!(
+                        k.storage,
+                        ImageRefStorage::Disc | ImageRefStorage::NvBitImage
+                    )
+                })
+                .collect();
+
+            self.label = default.label.clone();
         }
     }
 
@@ -628,7 +750,7 @@ impl Context {
         if self.page_mode.enabled {
             self.page_mode.page_area.x
         } else {
-            0
+            self.graphics.left_margin
         }
     }
 
@@ -680,8 +802,8 @@ impl Context {
             self.page_mode.offset_x_relative(adj_x);
         } else {
             let mut new_x = self.graphics.render_area.x as i32 + adj_x as i32;
-            if new_x < 0 {
-                new_x = 0;
+            if new_x < self.graphics.left_margin as i32 {
+                new_x = self.graphics.left_margin as i32;
             }
             self.graphics.render_area.x = new_x as u32;
         }
@@ -717,13 +839,50 @@ impl Context {
         let mut line_height = self.text.line_spacing as u32;
 
         for span in spans {
-            line_height = line_height.max(span.character_height);
+            line_height = line_height.max(span.get_height());
         }
 
         self.reset_x();
         self.offset_y(line_height);
     }
 
+    //Redefined codes are decoded into the private use area
+    //when the user-defined character set is selected,
+    //in Kanji mode multi byte characters are decoded first
+    pub fn decode_text(&self, bytes: &[u8]) -> String {
+        let text = &self.text;
+
+        if text.kanji.enabled {
+            if let Some(encoding) = &text.kanji.encoding {
+                return encoding.decode_utf8(bytes, |single| self.decode_single_byte(single));
+            }
+        }
+
+        self.decode_single_byte(bytes)
+    }
+
+    fn decode_single_byte(&self, bytes: &[u8]) -> String {
+        let text = &self.text;
+
+        if !text.user_defined {
+            return text.decoder.decode_utf8(bytes);
+        }
+
+        let mut decoded = String::new();
+        let mut start = 0;
+
+        for (i, byte) in bytes.iter().enumerate() {
+            if text.user_characters.is_defined(&text.font, *byte) {
+                decoded.push_str(&text.decoder.decode_utf8(&bytes[start..i]));
+                decoded.push(UserCharacters::to_char(*byte));
+                start = i + 1;
+            }
+        }
+
+        decoded.push_str(&text.decoder.decode_utf8(&bytes[start..]));
+        decoded
+    }
+
     pub fn set_font(&mut self, font: Font) {
         let size = font.to_size();
         self.text.font = font;
@@ -731,11 +890,12 @@ impl Context {
         self.text.character_height = size.1;
     }
 
+    //Relative to the base x
     pub fn set_x(&mut self, x: u32) {
         if self.page_mode.enabled {
             self.page_mode.set_x(x);
         } else {
-            self.graphics.render_area.x = x;
+            self.graphics.render_area.x = self.graphics.left_margin + x;
         }
     }
 
@@ -747,13 +907,13 @@ impl Context {
         }
     }
 
-    //Uses motion units
+    //Uses motion units, in standard mode x is from the left margin
     pub fn set_x_absolute(&mut self, x: u32) {
         let adj_x = x.saturating_div(self.graphics.h_motion_unit as u32);
         if self.page_mode.enabled {
             self.page_mode.set_x_absolute(adj_x);
         } else {
-            self.graphics.render_area.x = adj_x;
+            self.graphics.render_area.x = self.graphics.left_margin + adj_x;
         }
     }
 
@@ -787,6 +947,53 @@ impl Context {
         self.page_mode.logical_area = adj_area;
     }
 
+    //The paper width without the unprintable margins
+    pub fn get_printable_width(&self) -> u32 {
+        let paper = &self.graphics.paper_area;
+        paper.w.saturating_sub(paper.x + paper.y)
+    }
+
+    //Nothing has been printed on the line yet in standard mode
+    pub fn is_line_start(&self) -> bool {
+        self.graphics.render_area.x == self.graphics.left_margin
+    }
+
+    //Uses motion units, the cursor moves to the new margin.
+    //Ignored when the line has already started, like a printer does.
+    pub fn set_left_margin(&mut self, margin: u32) {
+        if !self.is_line_start() {
+            return;
+        }
+
+        let adj_margin = margin.saturating_div(self.graphics.h_motion_unit as u32);
+        let printable_width = self.get_printable_width();
+        let graphics = &mut self.graphics;
+
+        graphics.left_margin = adj_margin.min(printable_width);
+        graphics.render_area.w = graphics
+            .render_area
+            .w
+            .min(printable_width - graphics.left_margin);
+        graphics.render_area.x = graphics.left_margin;
+    }
+
+    //Uses motion units, the print area is at least one character wide
+    //and can't go past the printable area.
+    //Ignored when the line has already started, like a printer does.
+    pub fn set_print_area_width(&mut self, width: u32) {
+        if !self.is_line_start() {
+            return;
+        }
+
+        let adj_width = width.saturating_div(self.graphics.h_motion_unit as u32);
+        let min_width = self.text.character_width as u32 * self.text.width_mult as u32;
+        let max_width = self
+            .get_printable_width()
+            .saturating_sub(self.graphics.left_margin);
+
+        self.graphics.render_area.w = adj_width.max(min_width).min(max_width);
+    }
+
     pub fn get_width(&self) -> u32 {
         if self.page_mode.enabled {
             self.page_mode.render_area.w
@@ -804,14 +1011,8 @@ impl Context {
                     .saturating_sub(self.page_mode.page_area.x),
             )
         } else {
-            if self.graphics.render_area.x <= self.graphics.render_area.w {
-                self.graphics
-                    .render_area
-                    .w
-                    .saturating_sub(self.graphics.render_area.x)
-            } else {
-                0
-            }
+            (self.graphics.left_margin + self.graphics.render_area.w)
+                .saturating_sub(self.graphics.render_area.x)
         }
     }
 
@@ -854,6 +1055,13 @@ impl Context {
 
     pub fn update_decoder(&mut self) {
         self.text.decoder = get_codepage(self.text.code_table, self.text.character_set);
+        self.text.kanji.encoding =
+            match get_multi_byte_encoding(self.text.character_set, self.text.kanji.code_system) {
+                Some(MultiByteEncoding::Gb18030) if self.text.kanji.big5 => {
+                    Some(MultiByteEncoding::Big5)
+                }
+                encoding => encoding,
+            };
 
         //Codepage 255 is used specifically in this project for UTF8 encoded text
         self.text.decoder.use_utf8_table = if self.text.code_table == 255 {
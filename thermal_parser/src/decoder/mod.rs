@@ -9,6 +9,13 @@ mod codepage_4_pc863;
 mod codepage_5_pc865;
 mod codepage_base;
 mod international;
+mod multi_byte;
+mod multi_byte_big5;
+mod multi_byte_euc_kr;
+mod multi_byte_gb18030;
+mod multi_byte_shift_jis;
+
+pub use multi_byte::{get_multi_byte_encoding, MultiByteEncoding};
 
 #[derive(Clone)]
 pub struct Codepage {
@@ -35,6 +42,26 @@ impl Codepage {
 
         decoded.join("")
     }
+
+    //Opposite of decode_utf8, characters that
+    //aren't in the codepage are replaced with ?
+    pub fn encode(&self, text: &str) -> Vec<u8> {
+        if self.use_utf8_table {
+            return text.as_bytes().to_vec();
+        }
+
+        let mut buffer = [0u8; 4];
+
+        text.chars()
+            .map(|c| {
+                let c: &str = c.encode_utf8(&mut buffer);
+                self.table
+                    .iter()
+                    .position(|s| *s == c)
+                    .map_or(b'?', |i| i as u8)
+            })
+            .collect()
+    }
 }
 
 pub fn get_codepage(codepage_index: u8, language_index: u8) -> Codepage {
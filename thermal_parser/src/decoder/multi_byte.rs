@@ -0,0 +1,198 @@
+use super::{multi_byte_big5, multi_byte_euc_kr, multi_byte_gb18030, multi_byte_shift_jis};
+
+/// A two byte code table, rows are indexed by the lead byte
+/// and every row has a character for each trail byte.
+///
+/// Lead bytes with an empty row are not lead bytes.
+pub struct DoubleByteTable {
+    pub name: &'static str,
+    pub first_lead: u8,
+    pub first_trail: u8,
+    pub last_trail: u8,
+    pub rows: &'static [&'static str],
+}
+
+impl DoubleByteTable {
+    fn row(&self, lead: u8) -> Option<&'static str> {
+        let index = lead.checked_sub(self.first_lead)? as usize;
+        self.rows.get(index).filter(|row| !row.is_empty()).copied()
+    }
+
+    pub fn is_lead(&self, byte: u8) -> bool {
+        self.row(byte).is_some()
+    }
+
+    pub fn get(&self, lead: u8, trail: u8) -> Option<char> {
+        if trail < self.first_trail || trail > self.last_trail {
+            return None;
+        }
+
+        self.row(lead)?
+            .chars()
+            .nth((trail - self.first_trail) as usize)
+            .filter(|c| *c != char::REPLACEMENT_CHARACTER)
+    }
+}
+
+/// The multi byte encodings that are decoded while Kanji mode is on
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MultiByteEncoding {
+    Jis,
+    ShiftJis,
+    Gb18030,
+    Big5,
+    EucKr,
+}
+
+impl MultiByteEncoding {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Jis => "JIS",
+            Self::ShiftJis => multi_byte_shift_jis::TABLE.name,
+            Self::Gb18030 => multi_byte_gb18030::TABLE.name,
+            Self::Big5 => multi_byte_big5::TABLE.name,
+            Self::EucKr => multi_byte_euc_kr::TABLE.name,
+        }
+    }
+
+    fn table(&self) -> &'static DoubleByteTable {
+        match self {
+            Self::Jis | Self::ShiftJis => &multi_byte_shift_jis::TABLE,
+            Self::Gb18030 => &multi_byte_gb18030::TABLE,
+            Self::Big5 => &multi_byte_big5::TABLE,
+            Self::EucKr => &multi_byte_euc_kr::TABLE,
+        }
+    }
+
+    //Runs of bytes that are not part of a multi byte character
+    //are decoded by decode_single, like the single byte codepage
+    pub fn decode_utf8(&self, bytes: &[u8], decode_single: impl Fn(&[u8]) -> String) -> String {
+        let mut decoded = String::new();
+        let mut single_start = 0;
+        let mut index = 0;
+
+        while index < bytes.len() {
+            let (char, len) = match self.decode_char(&bytes[index..]) {
+                Some(decoded_char) => decoded_char,
+                None => {
+                    index += 1;
+                    continue;
+                }
+            };
+
+            decoded.push_str(&decode_single(&bytes[single_start..index]));
+            decoded.push(char);
+            index += len;
+            single_start = index;
+        }
+
+        decoded.push_str(&decode_single(&bytes[single_start..]));
+        decoded
+    }
+
+    //Decodes the character at the start of the bytes and how many bytes it used.
+    //Lead bytes without a valid trail are decoded as the replacement character
+    fn decode_char(&self, bytes: &[u8]) -> Option<(char, usize)> {
+        let lead = *bytes.first()?;
+
+        if *self == Self::Jis {
+            return Self::decode_jis(bytes);
+        }
+
+        let table = self.table();
+
+        if !table.is_lead(lead) {
+            return None;
+        }
+
+        let trail = match bytes.get(1) {
+            Some(trail) => *trail,
+            None => return Some((char::REPLACEMENT_CHARACTER, 1)),
+        };
+
+        if *self == Self::Gb18030 && trail.is_ascii_digit() {
+            return Some(Self::decode_gb18030_four_bytes(bytes));
+        }
+
+        match table.get(lead, trail) {
+            Some(char) => Some((char, 2)),
+            //ASCII trail bytes are not consumed
+            None if trail.is_ascii() => Some((char::REPLACEMENT_CHARACTER, 1)),
+            None => Some((char::REPLACEMENT_CHARACTER, 2)),
+        }
+    }
+
+    //JIS codes are two bytes between 0x21 and 0x7E, they are
+    //shifted into Shift JIS to use the same table
+    fn decode_jis(bytes: &[u8]) -> Option<(char, usize)> {
+        let j1 = *bytes.first()?;
+        let j2 = *bytes.get(1)?;
+
+        if !(0x21..=0x7E).contains(&j1) || !(0x21..=0x7E).contains(&j2) {
+            return None;
+        }
+
+        let s1 = ((j1 + 1) >> 1) + if j1 <= 0x5E { 0x70 } else { 0xB0 };
+        let s2 = if j1 % 2 == 1 {
+            j2 + if j2 >= 0x60 { 0x20 } else { 0x1F }
+        } else {
+            j2 + 0x7E
+        };
+
+        let char = multi_byte_shift_jis::TABLE
+            .get(s1, s2)
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
+
+        Some((char, 2))
+    }
+
+    //Four byte sequences are a lead, a digit, a lead and a digit
+    fn decode_gb18030_four_bytes(bytes: &[u8]) -> (char, usize) {
+        let b3 = *bytes.get(2).unwrap_or(&0);
+        let b4 = *bytes.get(3).unwrap_or(&0);
+
+        if !(0x81..=0xFE).contains(&b3) || !b4.is_ascii_digit() {
+            return (char::REPLACEMENT_CHARACTER, 1);
+        }
+
+        let pointer = ((bytes[0] as u32 - 0x81) * 10 + (bytes[1] as u32 - 0x30)) * 1260
+            + (b3 as u32 - 0x81) * 10
+            + (b4 as u32 - 0x30);
+
+        let code_point = if (189000..=1237575).contains(&pointer) {
+            Some(pointer - 189000 + 0x10000)
+        } else {
+            multi_byte_gb18030::FOUR_BYTE_RANGES
+                .iter()
+                .rev()
+                .find(|(start, _)| *start <= pointer)
+                .filter(|_| pointer <= 39419)
+                .map(|(start, code_point)| code_point + pointer - start)
+        };
+
+        let char = code_point
+            .and_then(char::from_u32)
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
+
+        (char, 4)
+    }
+}
+
+/// The multi byte encoding for the international character set
+/// and the Kanji code system.
+///
+/// 8 Japan uses JIS (code system 0) or Shift JIS (1 and 2)
+/// 13 Korea uses EUC-KR
+/// 15 China uses GB18030
+///
+/// Printers with Big5 select it with a memory switch instead,
+/// see Context::set_big5.
+pub fn get_multi_byte_encoding(character_set: u8, code_system: u8) -> Option<MultiByteEncoding> {
+    match (character_set, code_system) {
+        (8, 0) => Some(MultiByteEncoding::Jis),
+        (8, _) => Some(MultiByteEncoding::ShiftJis),
+        (13, _) => Some(MultiByteEncoding::EucKr),
+        (15, _) => Some(MultiByteEncoding::Gb18030),
+        _ => None,
+    }
+}
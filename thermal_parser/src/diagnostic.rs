@@ -0,0 +1,66 @@
+use crate::command::Command;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiagnosticCode {
+    //The command isn't part of the command set
+    UnknownCommand,
+    //The input ended before the command was complete
+    TruncatedCommand,
+    //A size parameter that can't be right
+    InvalidSize,
+    //A mode or parameter value that the command doesn't support
+    InvalidParameter,
+    //A subcommand id that isn't known for the command
+    MissingSubcommand,
+    //The command data is too short for its parameters
+    MissingParameters,
+    //Graphics that could not be created from the command
+    GraphicsError,
+    //The output renderer failed
+    RenderError,
+}
+
+/// A problem with a print job, tied to the command
+/// and the position in the input where it started
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub byte_offset: usize,
+    pub command_name: String,
+    pub severity: Severity,
+    pub code: DiagnosticCode,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(
+        command: &Command,
+        severity: Severity,
+        code: DiagnosticCode,
+        message: String,
+    ) -> Self {
+        Self {
+            byte_offset: command.start,
+            command_name: command.name.to_string(),
+            severity,
+            code,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} {:?} at byte {} ({}): {}",
+            self.severity, self.code, self.byte_offset, self.command_name, self.message
+        )
+    }
+}
@@ -0,0 +1,188 @@
+//! Guesses which command set a print job was written for
+//!
+//! The bytes are parsed with every command set, a command set scores
+//! well when most of the control bytes belong to commands it knows.
+//! Jobs without any control bytes are plain text, unless they are
+//! labels in a printable language like ZPL or EPL.
+
+use crate::command::{Command, CommandType};
+use crate::command_sets::{epl, esc_pos, star_graphic, star_line, zpl, CommandSet};
+use crate::constants::*;
+use crate::parser::Parser;
+use std::collections::HashSet;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Dialect {
+    EscPos,
+    StarLine,
+    StarGraphic,
+    Zpl,
+    Epl,
+    PlainText,
+}
+
+impl Dialect {
+    /// The command set for parsing the dialect, plain text
+    /// is parsed as ESC/POS
+    pub fn command_set(&self) -> CommandSet {
+        match self {
+            Dialect::EscPos | Dialect::PlainText => esc_pos::new(),
+            Dialect::StarLine => star_line::new(),
+            Dialect::StarGraphic => star_graphic::new(),
+            Dialect::Zpl => zpl::new(),
+            Dialect::Epl => epl::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DialectGuess {
+    pub set: Dialect,
+    //From 0 to 1, this is low when the bytes fit more than one dialect
+    pub confidence: f32,
+}
+
+/// Scores the bytes against each dialect and returns the best match.
+///
+/// ESC/POS wins a tie since it's what most printers understand,
+/// a confidence of 0 means nothing told the dialects apart.
+pub fn detect_dialect(bytes: &[u8]) -> DialectGuess {
+    let shared = shared_commands();
+    let mut scores = [
+        (
+            Dialect::EscPos,
+            command_set_score(bytes, Dialect::EscPos, &shared),
+        ),
+        (
+            Dialect::StarLine,
+            command_set_score(bytes, Dialect::StarLine, &shared),
+        ),
+        (
+            Dialect::StarGraphic,
+            command_set_score(bytes, Dialect::StarGraphic, &shared),
+        ),
+        (
+            Dialect::Zpl,
+            command_set_score(bytes, Dialect::Zpl, &shared),
+        ),
+        (
+            Dialect::Epl,
+            command_set_score(bytes, Dialect::Epl, &shared),
+        ),
+        (Dialect::PlainText, plain_text_score(bytes)),
+    ];
+
+    //Labels are printable text too, so they aren't plain text
+    if scores[3].1 > 0.0 || scores[4].1 > 0.0 {
+        scores[5].1 = 0.0;
+    }
+
+    let mut best = scores[0];
+    let mut second = 0.0;
+
+    for (dialect, score) in scores.iter().skip(1) {
+        if *score > best.1 {
+            second = best.1;
+            best = (*dialect, *score);
+        } else if *score > second {
+            second = *score;
+        }
+    }
+
+    if best.1 == 0.0 {
+        return DialectGuess {
+            set: best.0,
+            confidence: 0.0,
+        };
+    }
+
+    DialectGuess {
+        set: best.0,
+        confidence: best.1 * best.1 / (best.1 + second),
+    }
+}
+
+//The share of the commands that are known to the command set, commands
+//with diagnostics count as unknown since the data was probably misread.
+//Commands are counted instead of bytes so that a large image doesn't
+//outweigh everything else. Single control bytes like BEL or SO turn up
+//in image data too, so only longer commands count as known.
+fn command_set_score(bytes: &[u8], dialect: Dialect, shared: &[Vec<u8>]) -> f32 {
+    let mut parser = Parser::new(dialect.command_set());
+    let commands = parser.parse_bytes(bytes);
+    let problems: HashSet<usize> = parser
+        .take_diagnostics()
+        .iter()
+        .map(|d| d.byte_offset)
+        .collect();
+
+    if !has_start(dialect, &commands) {
+        return 0.0;
+    }
+
+    let mut known = 0;
+    let mut unknown = 0;
+
+    for command in commands.iter() {
+        match command.kind {
+            CommandType::Text => {}
+            CommandType::Unknown => unknown += 1,
+            _ if problems.contains(&command.start) => unknown += 1,
+            _ if shared.contains(&command.commands) => {}
+            _ if command.commands.len() > 1 => known += 1,
+            //EPL commands are single letters at the start of a line
+            _ if dialect == Dialect::Epl && !command.commands.is_empty() => known += 1,
+            _ => {}
+        }
+    }
+
+    if known == 0 {
+        return 0.0;
+    }
+
+    known as f32 / (known + unknown) as f32
+}
+
+//Raster lines start with b, which is text in any other dialect, so
+//Star Graphic jobs have to enter raster mode first. Labels are text
+//too, ZPL formats start with ^XA and EPL labels with N on its own line.
+fn has_start(dialect: Dialect, commands: &[Command]) -> bool {
+    let has = |name: &str| commands.iter().any(|c| c.name.as_str() == name);
+
+    match dialect {
+        Dialect::StarGraphic => has("Enter Raster Mode"),
+        Dialect::Zpl => has("Start Format"),
+        Dialect::Epl => commands.iter().any(|c| {
+            c.name.as_str() == "Clear Image Buffer" && c.data.iter().all(|b| *b == CR || *b == LF)
+        }),
+        _ => true,
+    }
+}
+
+//Commands like ESC @ that start with the same bytes in ESC/POS
+//and Star Line don't tell the dialects apart
+fn shared_commands() -> Vec<Vec<u8>> {
+    let star_line = star_line::new();
+
+    esc_pos::new()
+        .commands
+        .iter()
+        .filter(|c| star_line.commands.iter().any(|s| s.commands == c.commands))
+        .map(|c| c.commands.to_vec())
+        .collect()
+}
+
+//The share of bytes that are printable or line breaks, but any
+//command byte means the job isn't plain text
+fn plain_text_score(bytes: &[u8]) -> f32 {
+    if bytes.is_empty() || bytes.iter().any(|b| [ESC, GS, FS, DLE].contains(b)) {
+        return 0.0;
+    }
+
+    let text = bytes
+        .iter()
+        .filter(|b| **b >= 0x20 || [LF, CR, HT, FF].contains(b))
+        .count();
+
+    text as f32 / bytes.len() as f32
+}
@@ -118,6 +118,17 @@ pub struct Code2D {
     pub point_height: u32,
 }
 
+/// Composite symbol, the 2D component rows are stacked
+/// above the linear component rows. The HRI is printed
+/// below the symbol when there is text.
+#[derive(Clone, Debug)]
+pub struct Composite {
+    pub points: Vec<u8>,
+    pub width: u32,
+    pub point_width: u32,
+    pub text: Option<TextSpan>,
+}
+
 #[derive(Clone, Debug, Copy)]
 pub enum ImageFlow {
     Inline, //Image acts somewhat like text, advances x until line is full
@@ -151,6 +162,40 @@ impl Image {
 
         rgb_bytes
     }
+
+    /// Copies the pixels in the x and y direction, used by
+    /// stored images that are scaled when they are printed
+    pub fn stretch(&self, stretch: (u8, u8)) -> Image {
+        let scale_x = stretch.0.max(1) as u32;
+        let scale_y = stretch.1.max(1) as u32;
+        let w = self.w * scale_x;
+        let h = self.h * scale_y;
+        let mut pixels = Vec::with_capacity(w as usize * h as usize);
+
+        for y in 0..h {
+            for x in 0..w {
+                pixels.push(self.pixels[((y / scale_y) * self.w + x / scale_x) as usize]);
+            }
+        }
+
+        Image {
+            pixels,
+            w,
+            h,
+            ..self.clone()
+        }
+    }
+}
+
+/// The stretch for the mode of the legacy bit image commands,
+/// normal, double width, double height or quadruple
+pub fn bit_image_stretch(mode: u8) -> (u8, u8) {
+    match mode {
+        1 | 49 => (2, 1),
+        2 | 50 => (1, 2),
+        3 | 51 => (2, 2),
+        _ => (1, 1),
+    }
 }
 
 impl GraphicsCommand {
@@ -507,12 +552,17 @@ impl ImageRef {
 pub enum ImageRefStorage {
     Disc,
     Ram,
+    //GS *, there is only one with a key code of 0
+    DownloadBitImage,
+    //FS q, the key code is the image number
+    NvBitImage,
 }
 
 #[derive(Clone, Debug)]
 pub enum GraphicsCommand {
     Error(String),
     Code2D(Code2D),
+    Composite(Composite),
     Barcode(Barcode),
     Image(Image),
     Rectangle(Rectangle),
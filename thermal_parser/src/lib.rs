@@ -1,11 +1,16 @@
 use crate::command::Command;
+use crate::diagnostic::Diagnostic;
+use crate::dialect::{detect_dialect, DialectGuess};
 
+pub mod builder;
 pub mod command;
 pub mod command_sets;
 pub mod commands;
 pub mod constants;
 pub mod context;
 pub mod decoder;
+pub mod dialect;
+pub mod diagnostic;
 pub mod graphics;
 pub mod parser;
 pub mod subcommands;
@@ -17,3 +22,16 @@ pub mod text;
 pub fn parse_esc_pos(bytes: &Vec<u8>) -> Vec<Command> {
     parser::Parser::new(command_sets::esc_pos::new()).parse_bytes(bytes)
 }
+
+pub fn parse_esc_pos_with_diagnostics(bytes: &Vec<u8>) -> (Vec<Command>, Vec<Diagnostic>) {
+    let mut parser = parser::Parser::new(command_sets::esc_pos::new());
+    let commands = parser.parse_bytes(bytes);
+    (commands, parser.take_diagnostics())
+}
+
+/// Parses with the command set that the bytes most likely belong to
+pub fn parse_auto(bytes: &Vec<u8>) -> (DialectGuess, Vec<Command>) {
+    let guess = detect_dialect(bytes);
+    let commands = parser::Parser::new(guess.set.command_set()).parse_bytes(bytes);
+    (guess, commands)
+}
@@ -1,4 +1,6 @@
 use crate::command::CommandType;
+use crate::command_sets::trie::{CommandTrie, TrieMatch};
+use crate::diagnostic::{Diagnostic, DiagnosticCode, Severity};
 use crate::{command::Command, command_sets::*};
 use std::mem;
 use std::rc::Rc;
@@ -6,31 +8,76 @@ use std::rc::Rc;
 pub struct Parser {
     cmd_set: CommandSet,
     match_depth: u8,
-    command_matches: Vec<Command>,
+    trie: CommandTrie,
+    //Node of the bytes matched so far
+    trie_node: usize,
     current_command: Option<Command>,
     current_command_is_default: bool,
     command_buffer: Vec<u8>,
     captured_commands: Vec<Command>,
+    //Offset of the byte being parsed and where the command buffer started
+    offset: usize,
+    command_buffer_start: usize,
+    diagnostics: Vec<Diagnostic>,
+    //Bytes fed in previous chunks of the current job
+    position: usize,
+    started: bool,
+    //The current command was emitted as soon as it had all of its data
+    current_command_emitted: bool,
 }
 
 impl Parser {
     pub fn new(cmd_set: CommandSet) -> Self {
+        let trie = CommandTrie::new(&cmd_set.commands);
+
         Self {
+            trie_node: trie.root(),
+            trie,
             cmd_set,
             match_depth: 0,
-            command_matches: Vec::<Command>::new(),
             current_command_is_default: false,
             command_buffer: Vec::<u8>::new(),
             current_command: None,
             captured_commands: vec![],
+            offset: 0,
+            command_buffer_start: 0,
+            diagnostics: vec![],
+            position: 0,
+            started: false,
+            current_command_emitted: false,
         }
     }
 
-    pub fn parse_bytes(&mut self, bytes: &Vec<u8>) -> Vec<Command> {
-        self.emit_command(self.cmd_set.begin_parsing.clone());
+    pub fn parse_bytes(&mut self, bytes: &[u8]) -> Vec<Command> {
+        let mut commands: Vec<Command> = self.feed(bytes).collect();
+        commands.extend(self.finish());
+        commands
+    }
 
-        for byte in bytes {
+    /// Parses the next chunk of a job and returns the commands that are
+    /// complete so far. A command that is cut off by the end of the chunk
+    /// is kept until the next chunk or the call to finish.
+    pub fn feed(&mut self, bytes: &[u8]) -> impl Iterator<Item = Command> {
+        if !self.started {
+            self.started = true;
+            self.emit_command(self.cmd_set.begin_parsing.clone());
+        }
+
+        for (offset, byte) in bytes.iter().enumerate() {
+            self.offset = self.position + offset;
             self.parse(byte);
+            self.emit_if_complete();
+        }
+
+        self.position += bytes.len();
+
+        mem::take(&mut self.captured_commands).into_iter()
+    }
+
+    /// Ends the job, returning the last command and resetting the parser
+    pub fn finish(&mut self) -> impl Iterator<Item = Command> {
+        if !self.started {
+            self.emit_command(self.cmd_set.begin_parsing.clone());
         }
 
         //emit the last command and reset the parser
@@ -38,26 +85,60 @@ impl Parser {
         mem::swap(&mut self.current_command, &mut new_cmd); //new_cmd has become the previous command after the swap
 
         if let Some(new_cmd_unwrapped) = new_cmd {
+            if let Some(len) = new_cmd_unwrapped.fixed_data_len() {
+                if new_cmd_unwrapped.data.len() < len {
+                    self.diagnostics.push(Diagnostic::new(
+                        &new_cmd_unwrapped,
+                        Severity::Error,
+                        DiagnosticCode::TruncatedCommand,
+                        format!(
+                            "Expected {} bytes of data but the input ended after {}",
+                            len,
+                            new_cmd_unwrapped.data.len()
+                        ),
+                    ));
+                }
+            }
+
             self.emit_command(new_cmd_unwrapped);
         }
 
-        self.emit_command(self.cmd_set.end_parsing.clone());
+        let mut end_parsing = self.cmd_set.end_parsing.clone();
+        end_parsing.start = self.position;
+        end_parsing.end = self.position;
+        self.emit_command(end_parsing);
 
         self.match_depth = 0;
+        self.offset = 0;
+        self.position = 0;
+        self.started = false;
+        self.current_command_emitted = false;
         self.command_buffer.clear();
-        self.command_matches.clear();
+        self.trie_node = self.trie.root();
         self.current_command_is_default = false;
 
-        let mut commands: Vec<Command> = Vec::new();
-        mem::swap(&mut self.captured_commands, &mut commands);
-        commands
+        mem::take(&mut self.captured_commands).into_iter()
+    }
+
+    /// Problems found by the previous calls to parse_bytes
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+        mem::swap(&mut self.diagnostics, &mut diagnostics);
+        diagnostics
     }
 
     fn emit_command(&mut self, mut cmd: Command) {
+        self.diagnostics.extend(cmd.handler.get_diagnostics(&cmd));
+
         if cmd.kind == CommandType::Subcommand {
             let command = &mut cmd;
 
             if let Some(mut subcommand) = command.handler.get_subcommand() {
+                subcommand.start = command.start;
+                subcommand.end = command.end;
+                self.diagnostics
+                    .extend(subcommand.handler.get_diagnostics(&subcommand));
+
                 let mut expanded_cmds = vec![];
 
                 for command in command.commands.iter() {
@@ -77,104 +158,114 @@ impl Parser {
         }
     }
 
+    //Commands that know their length don't need to wait for the next command
+    fn emit_if_complete(&mut self) {
+        if self.match_depth != 0 || self.current_command_is_default {
+            return;
+        }
+
+        if let Some(cmd) = &self.current_command {
+            if cmd.is_complete() {
+                if let Some(cmd) = self.current_command.take() {
+                    self.current_command_emitted = true;
+                    self.emit_command(cmd);
+                }
+            }
+        }
+    }
+
     fn parse(&mut self, byte: &u8) {
         //If a command is willing to accept bytes and it is not the
         // default command, we don't need to do any filtering
         if self.match_depth == 0 && !self.current_command_is_default {
             if let Some(cmd) = &mut self.current_command {
                 if cmd.push(*byte) {
+                    cmd.end = self.offset + 1;
                     return;
                 };
             }
         }
 
         //Keep track of the search in case we need to match for an unknown command
+        if self.command_buffer.is_empty() {
+            self.command_buffer_start = self.offset;
+        }
         self.command_buffer.push(*byte);
 
         //Look for matching commands
-        let mut new_command_matches: Vec<Command> = Vec::with_capacity(0);
-        let subset = if self.match_depth == 0 {
-            &self.cmd_set.commands
-        } else {
-            &self.command_matches
-        };
+        let matched = self.trie.step(self.trie_node, *byte);
 
-        for cmd in subset.iter() {
-            if let Some(b) = cmd.commands.get(self.match_depth as usize) {
-                if b == byte {
-                    new_command_matches.push(cmd.clone())
-                }
-            }
+        if let TrieMatch::Partial(node) = matched {
+            self.trie_node = node;
+            self.match_depth += 1;
+            return;
         }
 
-        self.command_matches = new_command_matches;
+        self.trie_node = self.trie.root();
 
-        //if the command subset has one match, we create a new command by cloning the command
-        if self.command_matches.len() == 1 {
-            if let Some(matched_command) = &mut self.command_matches.first() {
-                //Here we make sure all command bytes are matched
-                if matched_command.commands.len() - 1 != self.match_depth as usize {
-                    self.match_depth += 1;
-                    return;
-                }
+        //if there is only one command left, we create a new command by cloning the command
+        if let TrieMatch::Command(index) = matched {
+            self.current_command_is_default = false;
+            self.command_buffer.clear();
+            self.match_depth = 0;
 
-                self.current_command_is_default = false;
-                self.command_buffer.clear();
-                self.match_depth = 0;
+            let mut matched_command = self.cmd_set.commands[index].clone();
+            matched_command.start = self.command_buffer_start;
+            matched_command.end = self.offset + 1;
 
-                let mut new_cmd = Some(matched_command.clone());
-                mem::swap(&mut self.current_command, &mut new_cmd); //new_cmd has become the previous command after the swap
+            let mut new_cmd = Some(matched_command);
+            mem::swap(&mut self.current_command, &mut new_cmd); //new_cmd has become the previous command after the swap
+            self.current_command_emitted = false;
 
-                if let Some(new_cmd_unwrapped) = new_cmd {
-                    self.emit_command(new_cmd_unwrapped);
-                }
+            if let Some(new_cmd_unwrapped) = new_cmd {
+                self.emit_command(new_cmd_unwrapped);
             }
             return;
         }
 
-        //If the matched command set is empty we either make a new default command
+        //Nothing matched, so we either make a new default command
         //or make a new unknown command or append to the last default command
-        if self.command_matches.is_empty() {
-            let mut new_cmd = None;
-
-            if self.command_buffer.len() > 0
-                && self
-                    .cmd_set
-                    .unknown
-                    .commands
-                    .contains(self.command_buffer.first().unwrap())
-            {
-                let mut unknown_command = self.cmd_set.unknown.clone();
-                unknown_command.data = self.command_buffer.clone();
-                new_cmd = Some(unknown_command);
-            } else if self.current_command_is_default {
-                if let Some(cmd) = &mut self.current_command {
-                    cmd.push(*byte);
-                }
-            } else {
-                let mut default_command = self.cmd_set.default.clone();
-                default_command.push(*byte);
-                new_cmd = Some(default_command);
+        let mut new_cmd = None;
+
+        if !self.command_buffer.is_empty()
+            && self
+                .cmd_set
+                .unknown
+                .commands
+                .contains(self.command_buffer.first().unwrap())
+        {
+            let mut unknown_command = self.cmd_set.unknown.clone();
+            unknown_command.data = self.command_buffer.clone();
+            unknown_command.start = self.command_buffer_start;
+            unknown_command.end = self.offset + 1;
+            new_cmd = Some(unknown_command);
+        } else if self.current_command_is_default {
+            if let Some(cmd) = &mut self.current_command {
+                cmd.push(*byte);
+                cmd.end = self.offset + 1;
             }
+        } else {
+            let mut default_command = self.cmd_set.default.clone();
+            default_command.start = self.offset;
+            default_command.end = self.offset + 1;
+            default_command.push(*byte);
+            new_cmd = Some(default_command);
+        }
 
-            self.command_buffer.clear();
-            self.current_command_is_default = true;
-            self.match_depth = 0;
+        self.command_buffer.clear();
+        self.current_command_is_default = true;
+        self.match_depth = 0;
 
-            if new_cmd.is_some() {
-                mem::swap(&mut self.current_command, &mut new_cmd);
-                if new_cmd.is_some() {
-                    //new_command has become the previous command after the swap
-                    self.emit_command(new_cmd.unwrap());
-                } else {
-                    //Generally an unknown command at the start of the binary
-                    self.emit_command(self.cmd_set.unknown.clone());
-                }
-                return;
+        if new_cmd.is_some() {
+            mem::swap(&mut self.current_command, &mut new_cmd);
+            if let Some(new_cmd) = new_cmd {
+                //new_command has become the previous command after the swap
+                self.emit_command(new_cmd);
+            } else if !self.current_command_emitted {
+                //Generally an unknown command at the start of the binary
+                self.emit_command(self.cmd_set.unknown.clone());
             }
-
-            return;
+            self.current_command_emitted = false;
         }
-        self.match_depth += 1;
     }
 }
@@ -1,18 +1,39 @@
-use crate::{command::*, context::*};
+use crate::utils::barcodes::aztec::Aztec;
+use crate::{command::*, context::*, graphics::*};
 
 #[derive(Clone)]
 pub struct Handler;
 
 impl CommandHandler for Handler {
-    fn apply_context(&self, _command: &Command, _context: &mut Context) {
-        //TODO No support for this yet
-        //context.code2d.symbol_storage = Some(Cod2D)
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        //The first byte is m (always 48)
+        let data = command.data.get(1..).unwrap_or(&[]);
+
+        //Mode 0 is full range, 1 is compact
+        let symbol = Aztec::new(
+            data,
+            context.code2d.aztec_mode == 1,
+            context.code2d.aztec_layers,
+            context.code2d.aztec_error_correction,
+        );
+
+        let graphics = match symbol {
+            Ok(symbol) => GraphicsCommand::Code2D(Code2D {
+                points: symbol.points,
+                width: symbol.size as u32,
+                point_width: context.code2d.aztec_size as u32,
+                point_height: context.code2d.aztec_size as u32,
+            }),
+            Err(e) => GraphicsCommand::Error(e),
+        };
+
+        context.code2d.symbol_storage = Some(graphics);
     }
 }
 
 pub fn new() -> Command {
     Command::new(
-        "PDF417 Store the Code2D data",
+        "Aztec Store the Code2D data",
         vec![53, 80],
         CommandType::Context,
         DataType::Subcommand,
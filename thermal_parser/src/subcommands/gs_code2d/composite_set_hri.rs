@@ -6,7 +6,18 @@ pub struct Handler;
 impl CommandHandler for Handler {
     fn apply_context(&self, command: &Command, context: &mut Context) {
         let n = *command.data.get(0).unwrap_or(&0u8);
-        context.code2d.composite_font = Font::from_raw(n);
+
+        //0 turns the HRI off, the others select the font
+        context.code2d.composite_human_readable = match n {
+            0 | 48 => HumanReadableInterface::None,
+            _ => HumanReadableInterface::Below,
+        };
+
+        context.code2d.composite_font = match n {
+            1 | 49 => Font::A,
+            2 | 50 => Font::B,
+            _ => Font::from_raw(n),
+        };
     }
 }
 
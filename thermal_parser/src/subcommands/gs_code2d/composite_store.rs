@@ -1,18 +1,160 @@
-use crate::{command::*, context::*};
+use barcoders::sym::code128::Code128;
+use barcoders::sym::ean13::{EAN13, UPCA};
+use barcoders::sym::ean8::EAN8;
+
+use crate::text::TextSpan;
+use crate::utils::barcodes::code128::gs1_128;
+use crate::utils::barcodes::databar::{gtin_hri, segments_per_row, DataBar, DataBarRow};
+use crate::utils::barcodes::gs1::{check_digit, ElementString};
+use crate::utils::barcodes::upce::UPCE;
+use crate::{command::*, context::*, graphics::*};
+
+//Height in modules of the linear components that don't define their own
+const LINEAR_HEIGHT: u32 = 50;
 
 #[derive(Clone)]
 pub struct Handler;
 
 impl CommandHandler for Handler {
-    fn apply_context(&self, _command: &Command, _context: &mut Context) {
-        //TODO No support for this yet
-        //context.code2d.symbol_storage = Some(Cod2D)
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        //The first byte is m (always 48), followed by the component (a)
+        //and the symbol type of that component (b)
+        let component = *command.data.get(1).unwrap_or(&0u8);
+        let symbol_type = *command.data.get(2).unwrap_or(&0u8);
+        let data = command.data.get(3..).unwrap_or(&[]).to_vec();
+
+        match component {
+            48 => context.code2d.composite_linear = Some((symbol_type, data)),
+            49 => context.code2d.composite_2d = Some((symbol_type, data)),
+            _ => {
+                context.code2d.symbol_storage = Some(GraphicsCommand::Error(format!(
+                    "Unknown composite component {}",
+                    component
+                )));
+                return;
+            }
+        }
+
+        let graphics = match build(context) {
+            Ok(composite) => GraphicsCommand::Composite(composite),
+            Err(e) => GraphicsCommand::Error(e),
+        };
+
+        context.code2d.symbol_storage = Some(graphics);
+    }
+}
+
+fn build(context: &Context) -> Result<Composite, String> {
+    let (symbol_type, data) = match &context.code2d.composite_linear {
+        Some(linear) => linear,
+        None => return Err("Composite has no linear component".to_string()),
+    };
+
+    //TODO the CC-A/CC-B/CC-C component is drawn with MicroPDF417 and
+    //PDF417 rows, which need the bar/space pattern table
+    if context.code2d.composite_2d.is_some() {
+        return Err("Composite 2D component is not supported yet".to_string());
+    }
+
+    let module_width = context.code2d.composite_width.max(1) as u32;
+    let (symbol, hri) = linear_component(*symbol_type, data, context)?;
+
+    let text = match context.code2d.composite_human_readable {
+        HumanReadableInterface::None => None,
+        _ => {
+            let mut span = TextSpan::new(hri, context);
+            span.font = context.code2d.composite_font.clone();
+            Some(span)
+        }
+    };
+
+    Ok(Composite {
+        points: symbol.points(),
+        width: symbol.width,
+        point_width: module_width,
+        text,
+    })
+}
+
+//The linear component symbol and its HRI text
+fn linear_component(
+    symbol_type: u8,
+    data: &[u8],
+    context: &Context,
+) -> Result<(DataBar, String), String> {
+    let text = String::from_utf8_lossy(data).to_string();
+    let module_width = context.code2d.composite_width.max(1) as u32;
+
+    //The linear EAN/UPC symbols are a single row that shows the data
+    //followed by the check digit
+    let single = |digits: usize, modules: Result<Vec<u8>, String>| {
+        modules.map(|modules| {
+            let body = text.get(..digits).unwrap_or(&text);
+            let hri = format!("{}{}", body, check_digit(body.as_bytes()) as char);
+            (single_row(modules), hri)
+        })
+    };
+
+    match symbol_type {
+        65 => single(
+            7,
+            EAN8::new(text.get(..7).unwrap_or(&text))
+                .map(|b| b.encode())
+                .map_err(|e| e.to_string()),
+        ),
+        66 => single(
+            12,
+            EAN13::new(text.get(..12).unwrap_or(&text))
+                .map(|b| b.encode())
+                .map_err(|e| e.to_string()),
+        ),
+        67 => single(
+            11,
+            //UPC-A is encoded as an EAN-13 with a leading zero
+            UPCA::new(format!("0{}", text.get(..11).unwrap_or(&text)))
+                .map(|b| b.encode())
+                .map_err(|e| e.to_string()),
+        ),
+        68 | 69 => UPCE::new(text.clone()).map(|b| (single_row(b.encode()), b.hri())),
+        70 => DataBar::omnidirectional(data).map(|s| (s, gtin_hri(data))),
+        71 => DataBar::truncated(data).map(|s| (s, gtin_hri(data))),
+        72 => DataBar::stacked(data).map(|s| (s, gtin_hri(data))),
+        73 => DataBar::stacked_omnidirectional(data).map(|s| (s, gtin_hri(data))),
+        74 => Err("Composite GS1 DataBar Limited is not supported yet".to_string()),
+        75 | 76 => {
+            let element_string = ElementString::new(&text)?;
+            let segments = if symbol_type == 76 {
+                segments_per_row(context.code2d.composite_max_width, module_width)
+            } else {
+                0
+            };
+
+            DataBar::expanded(&element_string.data, segments).map(|s| (s, element_string.hri))
+        }
+        77 => {
+            let element_string = ElementString::new(&text)?;
+
+            Code128::new(gs1_128(&element_string.data))
+                .map(|b| (single_row(b.encode()), element_string.hri))
+                .map_err(|e| e.to_string())
+        }
+        _ => Err(format!("Unknown composite linear type {}", symbol_type)),
+    }
+}
+
+fn single_row(modules: Vec<u8>) -> DataBar {
+    DataBar {
+        width: modules.len() as u32,
+        rows: vec![DataBarRow {
+            modules,
+            height: LINEAR_HEIGHT,
+        }],
     }
 }
 
 pub fn new() -> Command {
     Command::new(
-        "PDF417 Store the Code2D data",
+        "Composite Store the Code2D data",
         vec![52, 80],
         CommandType::Context,
         DataType::Subcommand,
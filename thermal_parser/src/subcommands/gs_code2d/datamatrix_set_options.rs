@@ -9,30 +9,14 @@ impl CommandHandler for Handler {
         let d1 = *command.data.get(1).unwrap_or(&0u8);
         let d2 = *command.data.get(2).unwrap_or(&0u8);
 
-        let mut columns = 0;
-        let mut rows = 0;
-        let mut symbol_type = 0;
-
-        match m {
-            0 | 48 => {
-                if d1 + d2 > 0 {
-                    columns = d1;
-                    rows = d2;
-                }
-            }
-            1 | 49 => {
-                symbol_type = 2;
-                if d2 == 0 && (d1 == 8 || d1 == 12 || d1 == 16) {
-                    columns = d1;
-                } else {
-                    columns = d1;
-                    rows = d2;
-                }
-            }
-            _ => {
-                return;
-            }
-        }
+        let (symbol_type, rows, columns) = match m {
+            //Square symbol, d1 is the number of rows and columns
+            0 | 48 => (0, d1, d1),
+            //Rectangular symbol, d1 is the number of rows (8, 12 or 16)
+            //and d2 the number of columns
+            1 | 49 => (1, d1, d2),
+            _ => return,
+        };
 
         context.code2d.datamatrix_rows = rows;
         context.code2d.datamatrix_columns = columns;
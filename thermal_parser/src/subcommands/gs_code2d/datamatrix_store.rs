@@ -1,18 +1,38 @@
-use crate::{command::*, context::*};
+use crate::utils::barcodes::datamatrix::DataMatrix;
+use crate::{command::*, context::*, graphics::*};
 
 #[derive(Clone)]
 pub struct Handler;
 
 impl CommandHandler for Handler {
-    fn apply_context(&self, _command: &Command, _context: &mut Context) {
-        //TODO No support for this yet
-        //context.code2d.symbol_storage = Some(Cod2D)
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        //The first byte is m (always 48)
+        let data = command.data.get(1..).unwrap_or(&[]);
+
+        let symbol = DataMatrix::new(
+            data,
+            context.code2d.datamatrix_type == 1,
+            context.code2d.datamatrix_rows,
+            context.code2d.datamatrix_columns,
+        );
+
+        let graphics = match symbol {
+            Ok(symbol) => GraphicsCommand::Code2D(Code2D {
+                points: symbol.points,
+                width: symbol.columns as u32,
+                point_width: context.code2d.datamatrix_width as u32,
+                point_height: context.code2d.datamatrix_width as u32,
+            }),
+            Err(e) => GraphicsCommand::Error(e),
+        };
+
+        context.code2d.symbol_storage = Some(graphics);
     }
 }
 
 pub fn new() -> Command {
     Command::new(
-        "PDF417 Store the Code2D data",
+        "Datamatrix Store the Code2D data",
         vec![54, 80],
         CommandType::Context,
         DataType::Subcommand,
@@ -1,18 +1,46 @@
-use crate::{command::*, context::*};
+use crate::utils::barcodes::databar::{segments_per_row, DataBar};
+use crate::utils::barcodes::gs1::ElementString;
+use crate::{command::*, context::*, graphics::*};
 
 #[derive(Clone)]
 pub struct Handler;
 
 impl CommandHandler for Handler {
-    fn apply_context(&self, _command: &Command, _context: &mut Context) {
-        //TODO No support for this yet
-        //context.code2d.symbol_storage = Some(Cod2D)
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        //The first byte is m (always 48), followed by the symbol type
+        let symbol_type = *command.data.get(1).unwrap_or(&0u8);
+        let data = command.data.get(2..).unwrap_or(&[]);
+        let module_width = context.code2d.gs1_databar_width as u32;
+
+        let symbol = match symbol_type {
+            72 => DataBar::stacked(data),
+            73 => DataBar::stacked_omnidirectional(data),
+            76 => ElementString::new(&String::from_utf8_lossy(data)).and_then(|element_string| {
+                DataBar::expanded(
+                    &element_string.data,
+                    segments_per_row(context.code2d.gs1_databar_max_width, module_width),
+                )
+            }),
+            _ => Err(format!("Unknown GS1 DataBar type {}", symbol_type)),
+        };
+
+        let graphics = match symbol {
+            Ok(symbol) => GraphicsCommand::Code2D(Code2D {
+                points: symbol.points(),
+                width: symbol.width,
+                point_width: module_width,
+                point_height: module_width,
+            }),
+            Err(e) => GraphicsCommand::Error(e),
+        };
+
+        context.code2d.symbol_storage = Some(graphics);
     }
 }
 
 pub fn new() -> Command {
     Command::new(
-        "PDF417 Store the Code2D data",
+        "GS1 DataBar Store the Code2D data",
         vec![51, 80],
         CommandType::Context,
         DataType::Subcommand,
@@ -33,20 +33,22 @@ mod pdf417_set_column_count;
 mod pdf417_set_correction_level;
 mod pdf417_set_options;
 mod pdf417_set_row_count;
+mod pdf417_set_row_height;
 mod pdf417_set_width;
 mod pdf417_store;
 mod pdf417_transmit_size;
-mod qr_print;
+pub mod qr_print;
 mod qr_set_correction_level;
 mod qr_set_model;
 mod qr_set_size;
-mod qr_store;
+pub mod qr_store;
 mod qr_transmit_size;
 
 pub fn all() -> Rc<Vec<Command>> {
     let all: Vec<Command> = vec![
         pdf417_set_column_count::new(),
         pdf417_set_row_count::new(),
+        pdf417_set_row_height::new(),
         pdf417_set_width::new(),
         pdf417_set_correction_level::new(),
         pdf417_set_options::new(),
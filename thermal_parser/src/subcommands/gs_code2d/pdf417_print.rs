@@ -6,8 +6,8 @@ pub struct Handler;
 impl CommandHandler for Handler {
     fn get_graphics(&self, _command: &Command, context: &Context) -> Option<GraphicsCommand> {
         match &context.code2d.symbol_storage {
-            Some(code2d) => {
-                return Some(GraphicsCommand::Code2D(code2d.clone()));
+            Some(symbol) => {
+                return Some(symbol.clone());
             }
             None => return None,
         }
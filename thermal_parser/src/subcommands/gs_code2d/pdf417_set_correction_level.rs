@@ -8,39 +8,19 @@ impl CommandHandler for Handler {
         let m = *command.data.get(0).unwrap_or(&48u8);
         let n = *command.data.get(1).unwrap_or(&48u8);
 
-        let codeword_count = command.data.len() - 2; //Assuming it's one byte per codeword
-        let mut level = m - 48;
-
-        //Ratio based error correction
-        if m == 49 {
-            let a = codeword_count as f32 * n as f32 * 0.01;
-            if a < 4f32 {
-                level = 1
-            }
-            if a < 11f32 {
-                level = 2
-            }
-            if a < 21f32 {
-                level = 3
-            }
-            if a < 46f32 {
-                level = 4
-            }
-            if a < 101f32 {
-                level = 5
-            }
-            if a < 201f32 {
-                level = 6
-            }
-            if a < 401f32 {
-                level = 7
-            }
-            if a > 400f32 {
-                level = 8
-            }
+        match m {
+            //Fixed error correction level 0 - 8
+            48 if (48..=56).contains(&n) => {
+                context.code2d.pdf417_err_correction = n - 48;
+                context.code2d.pdf417_err_correction_ratio = 0;
+            }
+            //Ratio based error correction, n x 10% of the data codewords.
+            //The level can only be determined once the data is known.
+            49 if (1..=40).contains(&n) => {
+                context.code2d.pdf417_err_correction_ratio = n;
+            }
+            _ => {}
         }
-
-        context.code2d.pdf417_err_correction = level;
     }
 }
 
@@ -0,0 +1,20 @@
+use crate::{command::*, context::*};
+
+#[derive(Clone)]
+pub struct Handler;
+
+impl CommandHandler for Handler {
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        context.code2d.pdf417_row_height = *command.data.get(0).unwrap_or(&3u8);
+    }
+}
+
+pub fn new() -> Command {
+    Command::new(
+        "PDF417 Sets the Row Height",
+        vec![48, 68],
+        CommandType::Context,
+        DataType::Subcommand,
+        Box::new(Handler),
+    )
+}
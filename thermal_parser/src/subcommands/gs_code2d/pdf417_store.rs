@@ -1,12 +1,40 @@
-use crate::{command::*, context::*};
+use crate::utils::barcodes::pdf417::PDF417;
+use crate::{command::*, context::*, graphics::*};
 
 #[derive(Clone)]
 pub struct Handler;
 
 impl CommandHandler for Handler {
-    fn apply_context(&self, _command: &Command, _context: &mut Context) {
-        //TODO No support for this yet
-        //context.code2d.symbol_storage = Some(Cod2D)
+    fn apply_context(&self, command: &Command, context: &mut Context) {
+        //The first byte is m (always 48)
+        let data = command.data.get(1..).unwrap_or(&[]);
+
+        let level = if context.code2d.pdf417_err_correction_ratio == 0 {
+            Some(context.code2d.pdf417_err_correction)
+        } else {
+            None
+        };
+
+        let symbol = PDF417::new(
+            data,
+            context.code2d.pdf417_columns,
+            context.code2d.pdf417_rows,
+            level,
+            context.code2d.pdf417_err_correction_ratio,
+            context.code2d.pdf417_row_height,
+        );
+
+        //TODO the codewords are generated but the bar/space pattern
+        //table needed to draw them is not implemented yet
+        let graphics = match symbol {
+            Ok(symbol) => GraphicsCommand::Error(format!(
+                "PDF417 drawing is not supported yet ({} columns x {} rows)",
+                symbol.columns, symbol.rows
+            )),
+            Err(e) => GraphicsCommand::Error(e),
+        };
+
+        context.code2d.symbol_storage = Some(graphics);
     }
 }
 
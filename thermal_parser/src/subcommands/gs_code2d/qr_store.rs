@@ -1,7 +1,7 @@
 extern crate qr_code;
 
 use crate::context::QrModel::Micro;
-use crate::{command::*, context::*, graphics};
+use crate::{command::*, context::*, graphics, graphics::GraphicsCommand};
 use qr_code::{EcLevel, QrCode, Version};
 
 #[derive(Clone)]
@@ -34,55 +34,71 @@ fn minimum_version_for_bytes(byte_len: i16, max_version: i16) -> i16 {
 
 impl CommandHandler for Handler {
     fn apply_context(&self, command: &Command, context: &mut Context) {
-        let data = command.data.to_owned();
-        
-        //Max number of modules per version
-        let max_modules: i16 = match &context.code2d.qr_model {
-            QrModel::Model1 => 14,
-            QrModel::Model2 => 40,
-            Micro => 4,
-        };
-        
-        //Minimum number of modules required to encode the binary length
-        let min_modules = minimum_version_for_bytes(data.len() as i16, max_modules);
-        
-        let version = match &context.code2d.qr_model {
-            QrModel::Model1 => Version::Normal(min_modules),
-            QrModel::Model2 => Version::Normal(min_modules),
-            Micro => Version::Micro(min_modules),
-        };
-
-        let error_correction = match context.code2d.qr_error_correction {
-            QrErrorCorrection::M => EcLevel::M,
-            QrErrorCorrection::Q => EcLevel::Q,
-            QrErrorCorrection::H => EcLevel::H,
-            _ => EcLevel::L,
-        };
-
-        let result = QrCode::with_version(data, version, error_correction);
-
-        match result {
-            Ok(qr) => {
-                let raw = qr.to_vec();
-                let mut converted_points = Vec::<u8>::with_capacity(raw.capacity());
-
-                for b in raw {
-                    let v = if b { 1 } else { 0 };
-                    converted_points.push(v);
-                }
-
-                let qrcode = graphics::Code2D {
-                    points: converted_points,
-                    width: qr.width() as u32,
-                    point_width: context.code2d.qr_size as u32,
-                    point_height: context.code2d.qr_size as u32,
-                };
-
-                context.code2d.symbol_storage = Some(qrcode);
-            }
-            Err(e) => {
-                println!("QR ERROR {} data: {:?}", e, String::from_utf8(command.data.clone()).unwrap_or("".to_string()));
+        store(&command.data, context);
+    }
+}
+
+/// Encodes the data with the QR settings in the context
+/// and keeps the symbol in the context for printing
+pub fn store(data: &[u8], context: &mut Context) {
+    //Max number of modules per version
+    let max_modules: i16 = match &context.code2d.qr_model {
+        QrModel::Model1 => 14,
+        QrModel::Model2 => 40,
+        Micro => 4,
+    };
+
+    //Minimum number of modules required to encode the binary length
+    let min_modules = minimum_version_for_bytes(data.len() as i16, max_modules);
+
+    let version = |modules: i16| match &context.code2d.qr_model {
+        QrModel::Model1 => Version::Normal(modules),
+        QrModel::Model2 => Version::Normal(modules),
+        Micro => Version::Micro(modules),
+    };
+
+    let error_correction = match context.code2d.qr_error_correction {
+        QrErrorCorrection::M => EcLevel::M,
+        QrErrorCorrection::Q => EcLevel::Q,
+        QrErrorCorrection::H => EcLevel::H,
+        _ => EcLevel::L,
+    };
+
+    //The byte capacity doesn't account for the encoding modes a
+    //version supports (M1 is numeric only), so step up until it fits
+    let mut result = QrCode::with_version(data, version(min_modules), error_correction);
+
+    for modules in min_modules + 1..=max_modules {
+        if result.is_ok() {
+            break;
+        }
+        result = QrCode::with_version(data, version(modules), error_correction);
+    }
+
+    match result {
+        Ok(qr) => {
+            let raw = qr.to_vec();
+            let mut converted_points = Vec::<u8>::with_capacity(raw.capacity());
+
+            for b in raw {
+                let v = if b { 1 } else { 0 };
+                converted_points.push(v);
             }
+
+            let qrcode = graphics::Code2D {
+                points: converted_points,
+                width: qr.width() as u32,
+                point_width: context.code2d.qr_size as u32,
+                point_height: context.code2d.qr_size as u32,
+            };
+
+            context.code2d.symbol_storage = Some(GraphicsCommand::Code2D(qrcode));
+        }
+        Err(e) => {
+            context.code2d.symbol_storage = Some(GraphicsCommand::Error(format!(
+                "QR code could not be created: {}",
+                e
+            )));
         }
     }
 }
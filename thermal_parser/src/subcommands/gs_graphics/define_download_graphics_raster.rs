@@ -1,3 +1,4 @@
+use crate::diagnostic::{Diagnostic, DiagnosticCode, Severity};
 use crate::util::parse_u16;
 use crate::{command::*, context::*, graphics::*};
 
@@ -5,9 +6,21 @@ use crate::{command::*, context::*, graphics::*};
 pub struct Handler;
 
 impl CommandHandler for Handler {
+    fn get_diagnostics(&self, command: &Command) -> Vec<Diagnostic> {
+        if command.data.len() < 8 {
+            return vec![Diagnostic::new(
+                command,
+                Severity::Error,
+                DiagnosticCode::MissingParameters,
+                format!("Expected 8 parameter bytes, got {}", command.data.len()),
+            )];
+        }
+        vec![]
+    }
+
     fn apply_context(&self, command: &Command, context: &mut Context) {
+        //Reported as a diagnostic while parsing
         if command.data.len() < 8 {
-            println!("To few parameters");
             return;
         };
 
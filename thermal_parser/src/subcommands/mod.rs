@@ -1,6 +1,7 @@
 use std::mem;
 use std::rc::Rc;
 
+use crate::diagnostic::{Diagnostic, DiagnosticCode, Severity};
 use crate::text::TextSpan;
 use crate::util::{parse_u16, parse_u32};
 use crate::{command::*, context::*, graphics::*};
@@ -18,6 +19,9 @@ pub struct SubCommandHandler {
     capacity: u32,
     accept_data: bool,
     use_m: bool,
+    //Whether all of the data was received
+    complete: bool,
+    problems: Vec<(Severity, DiagnosticCode, String)>,
 }
 
 impl SubCommandHandler {
@@ -52,20 +56,27 @@ impl SubCommandHandler {
     fn parse_meta(&mut self, data: &[u8]) {
         let data_len = data.len();
 
-        if data_len == 4 {
-            self.capacity = parse_u16(&data.to_vec(), 0) as u32;
-            self.capacity -= 2;
+        //The size includes m and the subcommand id. Sizes that
+        //are too big for the input show up as a truncated command
+        let size = if data_len == 6 {
+            self.m = *data.get(4).unwrap();
+            self.subcommand_id = *data.get(5).unwrap();
+            parse_u32(&data.to_vec(), 0)
+        } else {
             self.m = *data.get(2).unwrap();
             self.subcommand_id = *data.get(3).unwrap();
+            parse_u16(&data.to_vec(), 0) as u32
+        };
+
+        if size < 2 {
+            self.problems.push((
+                Severity::Error,
+                DiagnosticCode::InvalidSize,
+                format!("Size {} is too small to hold the subcommand", size),
+            ));
         }
 
-        if data_len == 6 {
-            //TODO why is this number too big
-            self.capacity = parse_u32(&data.to_vec(), 0);
-            self.capacity -= 2;
-            self.m = *data.get(4).unwrap();
-            self.subcommand_id = *data.get(5).unwrap();
-        }
+        self.capacity = size.saturating_sub(2);
 
         if self.use_m {
             self.detect_kind_use_m()
@@ -81,6 +92,22 @@ impl SubCommandHandler {
 
         self.accept_data = true;
     }
+
+    //Move the data into the subcommand
+    fn complete_data(&mut self, data: &mut Vec<u8>) {
+        if let Some(sub) = &mut self.subcommand {
+            mem::swap(&mut sub.data, data);
+        } else {
+            //Not necessarily a bad command, it may not be supported yet
+            self.problems.push((
+                Severity::Warning,
+                DiagnosticCode::MissingSubcommand,
+                format!("Unknown subcommand {} {}", self.m, self.subcommand_id),
+            ));
+        }
+
+        self.complete = true;
+    }
 }
 
 //We are proxying all command handler commands to the subcommand with the exception of parse
@@ -113,42 +140,59 @@ impl CommandHandler for SubCommandHandler {
     }
 
     fn push(&mut self, data: &mut Vec<u8>, byte: u8) -> bool {
-        let mut data_len = data.len();
+        if self.complete {
+            //Stop accepting bytes
+            return false;
+        }
+
+        data.push(byte);
 
         if !self.accept_data {
             // Large subcommands use two extra bytes to determine data size
-            if self.is_large {
-                if data_len < 6 {
-                    data.push(byte);
-                    return true;
-                }
-                self.parse_meta(&data[0..6]);
-            } else {
-                if data_len < 4 {
-                    data.push(byte);
-                    return true;
-                }
-                self.parse_meta(&data[0..4]);
+            let meta_len = if self.is_large { 6 } else { 4 };
+
+            if data.len() == meta_len {
+                self.parse_meta(data);
+                data.clear();
             }
-            data.clear();
-            data_len = 0;
         }
 
-        //Accept data
-        if data_len < (self.capacity as usize) {
-            data.push(byte);
-            return true;
+        //All data is in as soon as the last byte is accepted, so
+        //a subcommand at the end of the input is not lost
+        if self.accept_data && data.len() >= self.capacity as usize {
+            self.complete_data(data);
         }
 
-        //Move the data into the subcommand
-        if let Some(sub) = &mut self.subcommand {
-            mem::swap(&mut sub.data, data);
-        } else {
-            println!("Missing subcommand");
+        true
+    }
+
+    fn is_complete(&self, _command: &Command) -> bool {
+        self.complete
+    }
+
+    fn get_diagnostics(&self, command: &Command) -> Vec<Diagnostic> {
+        let mut diagnostics: Vec<Diagnostic> = self
+            .problems
+            .iter()
+            .map(|(severity, code, message)| {
+                Diagnostic::new(command, *severity, *code, message.to_string())
+            })
+            .collect();
+
+        if !self.complete {
+            diagnostics.push(Diagnostic::new(
+                command,
+                Severity::Error,
+                DiagnosticCode::TruncatedCommand,
+                if self.accept_data {
+                    format!("Expected {} bytes of data", self.capacity)
+                } else {
+                    "The input ended before the subcommand size".to_string()
+                },
+            ));
         }
 
-        //Stop accepting bytes
-        false
+        diagnostics
     }
 
     //Returns a subcommand that can be owned and stubs
@@ -169,6 +213,8 @@ pub fn new(is_large: bool, use_m: bool, commands: Rc<Vec<Command>>) -> Box<SubCo
         capacity: 0,
         accept_data: false,
         use_m,
+        complete: false,
+        problems: vec![],
     })
 }
 
@@ -1,6 +1,9 @@
 use crate::context::{Context, Font, TextJustify, TextStrikethrough, TextUnderline};
 use crate::graphics::RGBA;
+use std::collections::HashMap;
 use std::fmt;
+use std::mem;
+use unicode_width::UnicodeWidthChar;
 
 #[derive(Clone)]
 pub struct TextSpan {
@@ -22,6 +25,12 @@ pub struct TextSpan {
     pub dimensions: Option<Dimensions>,
     pub background_color: RGBA,
     pub text_color: RGBA,
+    //Text that was decoded in Kanji mode has double width characters,
+    //the spacing is added on the left and right of each of them
+    pub multi_byte: bool,
+    pub wide_character_width: u32,
+    pub wide_character_height: u32,
+    pub wide_character_spacing: (u32, u32),
 }
 
 #[derive(Clone, Debug)]
@@ -35,13 +44,38 @@ pub struct Dimensions {
 impl TextSpan {
     pub fn new(text: String, context: &Context) -> Self {
         let style = &context.text;
+        let kanji = &style.kanji;
+        let multi_byte = kanji.enabled && kanji.encoding.is_some();
 
-        let underline = match style.underline {
+        //Kanji print modes only apply to the wide characters
+        let (kanji_width_mult, kanji_height_mult) = if multi_byte {
+            (
+                1 + kanji.double_width as u32,
+                1 + kanji.double_height as u32,
+            )
+        } else {
+            (1, 1)
+        };
+
+        let underline_style = if multi_byte && kanji.underline != TextUnderline::Off {
+            &kanji.underline
+        } else {
+            &style.underline
+        };
+
+        let underline = match underline_style {
             TextUnderline::On => context.points_to_pixels(1.0) as u32,
             TextUnderline::Double => context.points_to_pixels(2.0) as u32,
             _ => 0,
         };
 
+        let height_mult = style.height_mult as u32;
+        let wide_mult = style.width_mult as u32 * kanji_width_mult;
+        let wide_character_spacing = (
+            kanji.left_spacing as u32 * wide_mult,
+            kanji.right_spacing as u32 * wide_mult,
+        );
+
         let strikethrough = match style.strikethrough {
             TextStrikethrough::On => context.points_to_pixels(1.0) as u32,
             TextStrikethrough::Double => context.points_to_pixels(2.0) as u32,
@@ -53,20 +87,24 @@ impl TextSpan {
             base_character_width: style.character_width as u32,
             base_character_height: style.character_height as u32,
             character_width: style.character_width as u32 * style.width_mult as u32,
-            character_height: style.character_height as u32 * style.height_mult as u32,
+            character_height: style.character_height as u32 * height_mult,
             text,
             bold: style.bold,
             italic: style.italic,
             underline,
             strikethrough,
             stretch_width: style.width_mult as f32,
-            stretch_height: style.height_mult as f32,
+            stretch_height: height_mult as f32,
             inverted: style.invert,
             upside_down: style.upside_down,
             justify: context.text.justify.clone(),
             dimensions: None,
             background_color: context.text.background_color,
             text_color: context.text.color,
+            multi_byte,
+            wide_character_width: style.character_width as u32 * 2 * wide_mult,
+            wide_character_height: style.character_height as u32 * height_mult * kanji_height_mult,
+            wide_character_spacing,
         }
     }
 
@@ -81,12 +119,55 @@ impl TextSpan {
             x: context.get_x(),
             y: context.get_y(),
             w: self.get_width(),
-            h: self.character_height,
+            h: self.get_height(),
         });
     }
 
+    //Spans with wide characters can be taller than the rest of the text
+    pub fn get_height(&self) -> u32 {
+        self.text
+            .chars()
+            .map(|c| self.char_height(c))
+            .fold(self.character_height, u32::max)
+    }
+
+    //The vertical stretch of the tallest character
+    pub fn get_stretch_height(&self) -> f32 {
+        match self.base_character_height {
+            0 => self.stretch_height,
+            base => self.get_height() as f32 / base as f32,
+        }
+    }
+
     pub fn get_width(&self) -> u32 {
-        self.character_count() * self.character_width
+        if !self.multi_byte {
+            return self.character_count() * self.character_width;
+        }
+
+        self.text.chars().map(|c| self.char_width(c)).sum()
+    }
+
+    //CJK characters take up two columns, ambiguous characters
+    //like Greek or box drawing are wide in multi byte encodings
+    pub fn is_wide(&self, c: char) -> bool {
+        self.multi_byte && c.width_cjk() == Some(2)
+    }
+
+    pub fn char_width(&self, c: char) -> u32 {
+        if self.is_wide(c) {
+            let (left, right) = self.wide_character_spacing;
+            left + self.wide_character_width + right
+        } else {
+            self.character_width
+        }
+    }
+
+    pub fn char_height(&self, c: char) -> u32 {
+        if self.is_wide(c) {
+            self.wide_character_height
+        } else {
+            self.character_height
+        }
     }
 
     pub fn character_count(&self) -> u32 {
@@ -113,46 +194,38 @@ impl TextSpan {
             dimensions: None,
             background_color: self.background_color,
             text_color: self.text_color,
+            multi_byte: self.multi_byte,
+            wide_character_width: self.wide_character_width,
+            wide_character_height: self.wide_character_height,
+            wide_character_spacing: self.wide_character_spacing,
         };
         clone.text = string;
         clone
     }
 
-    pub fn break_apart(&self, first_line_length: usize, line_length: usize) -> Vec<TextSpan> {
-        if line_length == 0 {
-            panic!("break_apart called with zero line length");
-        }
-
-        let chars: Vec<char> = self.text.chars().collect();
+    //Lines after the first always get at least one character
+    pub fn break_apart(&self, first_line_width: u32, line_width: u32) -> Vec<TextSpan> {
         let mut result = Vec::new();
-        let mut index = 0;
-
-        // First split, which is often smaller
-        if first_line_length > 0 && chars.len() > 0 {
-            let first_chunk = chars
-                .iter()
-                .take(first_line_length)
-                .cloned()
-                .collect::<String>();
-            result.push(self.clone_with(first_chunk));
-            index += first_line_length;
-        }
+        let mut chunk = String::new();
+        let mut chunk_width = 0;
+        let mut max_width = first_line_width;
 
-        //We are always expecting a first line value, even if there isn't one
-        if index == 0 {
-            result.push(self.clone_with("".to_string()));
+        for c in self.text.chars() {
+            let char_width = self.char_width(c);
+
+            //We are always expecting a first line value, even if there isn't one
+            if chunk_width + char_width > max_width && (!chunk.is_empty() || result.is_empty()) {
+                result.push(self.clone_with(mem::take(&mut chunk)));
+                chunk_width = 0;
+                max_width = line_width;
+            }
+
+            chunk.push(c);
+            chunk_width += char_width;
         }
 
-        // Split the rest of the string into chunks
-        while index < chars.len() {
-            let chunk = chars
-                .iter()
-                .skip(index)
-                .take(line_length)
-                .cloned()
-                .collect::<String>();
+        if !chunk.is_empty() || result.is_empty() {
             result.push(self.clone_with(chunk));
-            index += line_length;
         }
 
         result
@@ -171,6 +244,13 @@ impl TextSpan {
                     words.push(self.clone_with(current_word.clone()));
                     current_word.clear();
                 }
+            } else if self.is_wide(c) {
+                //Lines can break between any two wide characters
+                if !current_word.is_empty() {
+                    words.push(self.clone_with(mem::take(&mut current_word)));
+                }
+
+                words.push(self.clone_with(c.to_string()));
             } else {
                 // Add regular characters to the current word
                 current_word.push(c);
@@ -186,6 +266,74 @@ impl TextSpan {
     }
 }
 
+/// A character downloaded with ESC &, the pixels
+/// are stored by row and are true when printed
+#[derive(Clone, Debug, PartialEq)]
+pub struct UserCharacter {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<bool>,
+}
+
+impl UserCharacter {
+    /// Characters are sent as columns of bytes with the top dot in the high bit
+    pub fn from_column_bytes(width: u32, column_bytes: u32, bytes: &[u8]) -> Self {
+        let height = column_bytes * 8;
+        let mut pixels = vec![false; (width * height) as usize];
+
+        for x in 0..width {
+            for y in 0..height {
+                let index = (x * column_bytes + y / 8) as usize;
+                let byte = *bytes.get(index).unwrap_or(&0u8);
+                pixels[(y * width + x) as usize] = byte & (0x80 >> (y % 8)) != 0;
+            }
+        }
+
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+}
+
+/// Downloaded characters for each font
+///
+/// Redefined codes are decoded into the private use area
+/// so that renderers can tell them apart from regular text.
+#[derive(Clone, Default)]
+pub struct UserCharacters {
+    characters: HashMap<(Font, u8), UserCharacter>,
+}
+
+impl UserCharacters {
+    const PRIVATE_USE_START: u32 = 0xE000;
+
+    pub fn define(&mut self, font: &Font, code: u8, character: UserCharacter) {
+        self.characters.insert((font.clone(), code), character);
+    }
+
+    pub fn cancel(&mut self, font: &Font, code: u8) {
+        self.characters.remove(&(font.clone(), code));
+    }
+
+    pub fn is_defined(&self, font: &Font, code: u8) -> bool {
+        self.characters.contains_key(&(font.clone(), code))
+    }
+
+    /// The character that a redefined code is decoded into
+    pub fn to_char(code: u8) -> char {
+        char::from_u32(Self::PRIVATE_USE_START + code as u32).unwrap_or(' ')
+    }
+
+    /// The downloaded character for a decoded character, if there is one
+    pub fn get(&self, font: &Font, char: char) -> Option<&UserCharacter> {
+        let code = (char as u32).checked_sub(Self::PRIVATE_USE_START)?;
+        let code = u8::try_from(code).ok()?;
+        self.characters.get(&(font.clone(), code))
+    }
+}
+
 impl fmt::Debug for TextSpan {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let txt = &self.text.replace("\n", "{LF}");
@@ -8,17 +8,19 @@
 //!
 //! Thermal file format:
 //!
-//! ```'// Comments look like this
+//! ```text
+//! '// Comments look like this
 //! "Quoted values are strings"
 //! "Hex values look like this ->" 0xFF
 //! "Decimal Values look like this -> " 23
 //! "There are a few constant values that can also be used"
-//! NUL ESC HT LF FF CR GS FS DLE CAN "Are all valid"```
+//! NUL ESC HT LF FF CR GS FS DLE CAN "Are all valid"
+//! ```
 //!
 //!
 //! Some examples:
 //!
-//! ```
+//! ```text
 //! '// Initialize
 //! ESC "@"
 //!
@@ -35,78 +37,584 @@
 //! GS "V" 66 30
 //!
 //! ```
-use crate::command::{Command, CommandType};
+//!
+//! Directives and variables:
+//!
+//! ```text
+//! '// Lines from another file, relative to this one
+//! @include "header.thermal"
+//!
+//! '// Names for byte sequences, used like the constants
+//! @define BOLD_ON ESC "E" 1
+//! BOLD_ON
+//!
+//! '// Lines up to @end are repeated, blocks can be nested
+//! @repeat 3
+//! "${item}" LF
+//! @end
+//! ```
+//!
+//! Variables like ${item} are replaced with values from ThermalOptions
+//! before the line is read, so they can also hold tokens or counts.
+//! Write \${ for a literal ${, bytes_to_tokens escapes it this way.
+use crate::command::{Command, CommandType, DeviceCommand};
 use crate::constants;
 use crate::constants::*;
+use crate::context::{Context, TextContext, TextStrikethrough, TextUnderline};
+use crate::diagnostic::Severity;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
 
 pub static COMMENT_PREFIX: &str = "'//";
 pub static HEX_PREFIX: &str = "0x";
 
+//Most times the lines of a @repeat block are parsed,
+//counting the repeats of the blocks around it
+const MAX_REPEATS: usize = 10_000;
+
+/// Options for parse_str_with_options and parse_file
+#[derive(Clone, Default)]
+pub struct ThermalOptions {
+    //Values for ${name} in the file
+    pub variables: HashMap<String, String>,
+    //Where includes are looked up when the text isn't from a file,
+    //defaults to the working directory
+    pub include_dir: Option<PathBuf>,
+}
+
+/// A problem in a thermal file, lines and columns start at 1.
+/// The problem spans the columns from column up to end_column.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThermalSyntaxError {
+    //The file the error is in, None for the text passed to parse_str
+    pub file: Option<PathBuf>,
+    pub line: usize,
+    pub column: usize,
+    pub end_column: usize,
+    //Warning when the parser was lenient and kept going
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl fmt::Display for ThermalSyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        write!(f, "{}:{}: ", self.line, self.column)?;
+
+        if self.severity == Severity::Warning {
+            write!(f, "warning: ")?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
 /// Parse thermal format from string into bytes
+///
+/// Problems are skipped over, use parse_str_with_warnings
+/// or parse_str_strict to get them
 pub fn parse_str(text: &str) -> Vec<u8> {
-    let mut parsed = Vec::new();
+    parse_str_with_warnings(text).0
+}
 
-    for line in text.lines() {
-        //skip comments
-        if line.starts_with(COMMENT_PREFIX) || line.trim().is_empty() {
-            continue;
+/// Parse thermal format from string into bytes, along with warnings
+/// for the problems that were skipped over.
+///
+/// Invalid hex values are left out, decimals that don't fit in a byte
+/// and unknown names are written as text and unterminated strings
+/// run to the end of the line.
+pub fn parse_str_with_warnings(text: &str) -> (Vec<u8>, Vec<ThermalSyntaxError>) {
+    let options = ThermalOptions::default();
+    let mut preprocessor = Preprocessor::new(&options);
+    preprocessor.parse_text(text);
+
+    let mut warnings = preprocessor.errors;
+    for warning in warnings.iter_mut() {
+        warning.severity = Severity::Warning;
+    }
+
+    (preprocessor.bytes, warnings)
+}
+
+/// Parse thermal format from string into bytes, failing
+/// with every problem that parse_str would skip over
+pub fn parse_str_strict(text: &str) -> Result<Vec<u8>, Vec<ThermalSyntaxError>> {
+    parse_str_with_options(text, &ThermalOptions::default())
+}
+
+/// Parse thermal format from string into bytes, with the variables
+/// for ${name} and the directory for includes from the options
+pub fn parse_str_with_options(
+    text: &str,
+    options: &ThermalOptions,
+) -> Result<Vec<u8>, Vec<ThermalSyntaxError>> {
+    let mut preprocessor = Preprocessor::new(options);
+    preprocessor.parse_text(text);
+    preprocessor.finish()
+}
+
+/// Parse a thermal file, includes are relative to the file
+pub fn parse_file(
+    path: &Path,
+    options: &ThermalOptions,
+) -> Result<Vec<u8>, Vec<ThermalSyntaxError>> {
+    let mut preprocessor = Preprocessor::new(options);
+
+    match std::fs::read_to_string(path) {
+        Ok(text) => {
+            preprocessor.files.push(path.to_path_buf());
+            preprocessor.parse_text(&text);
         }
+        Err(e) => preprocessor.error(0, 0, 0, format!("Can't read {}: {}", path.display(), e)),
+    }
 
-        //Parse tokens
-        let tokens = parse_tokens(line);
+    preprocessor.finish()
+}
 
-        //Convert tokens to bytes
-        for token in tokens {
-            match token {
-                "NUL" => parsed.push(NUL),
-                "ESC" => parsed.push(ESC),
-                "HT" => parsed.push(HT),
-                "LF" => parsed.push(LF),
-                "FF" => parsed.push(FF),
-                "CR" => parsed.push(CR),
-                "GS" => parsed.push(GS),
-                "FS" => parsed.push(FS),
-                "DLE" => parsed.push(DLE),
-                "CAN" => parsed.push(CAN),
-                _ => {
-                    //Hex 0xFF for example
-                    if token.starts_with(HEX_PREFIX) {
-                        let maybe_byte = u8::from_str_radix(&token[2..], 16);
-                        if let Ok(byte) = maybe_byte {
-                            parsed.push(byte);
-                        }
+fn constant(token: &str) -> Option<u8> {
+    match token {
+        "NUL" => Some(NUL),
+        "ESC" => Some(ESC),
+        "HT" => Some(HT),
+        "LF" => Some(LF),
+        "FF" => Some(FF),
+        "CR" => Some(CR),
+        "GS" => Some(GS),
+        "FS" => Some(FS),
+        "DLE" => Some(DLE),
+        "CAN" => Some(CAN),
+        _ => None,
+    }
+}
+
+fn is_name(token: &str) -> bool {
+    let mut chars = token.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+//Byte offset of a token that was sliced from the line
+fn offset_of(line: &str, token: &str) -> usize {
+    (token.as_ptr() as usize).saturating_sub(line.as_ptr() as usize)
+}
+
+//Column of a byte offset in the line
+fn column_at(line: &str, offset: usize) -> usize {
+    line.get(..offset).map_or(0, |s| s.chars().count()) + 1
+}
+
+//Columns from the start of the token up to the end of it
+fn span_of(line: &str, token: &str) -> (usize, usize) {
+    let column = column_at(line, offset_of(line, token));
+    (column, column + token.chars().count())
+}
+
+//A line after the variables are substituted. Tokens are sliced from
+//the text, errors are reported at the columns of the source line.
+struct Line<'a> {
+    source: &'a str,
+    text: String,
+    //Range in the source for each byte of the text, the bytes of a
+    //variable's value all come from the whole ${name}
+    origins: Vec<(usize, usize)>,
+}
+
+impl Line<'_> {
+    //Columns in the source from the start of the token up to the end of it
+    fn span(&self, token: &str) -> (usize, usize) {
+        let start = offset_of(&self.text, token);
+        let source_start = self.origins.get(start).map_or(self.source.len(), |o| o.0);
+
+        let source_end = match (start + token.len()).checked_sub(1) {
+            Some(last) if !token.is_empty() => self.origins.get(last).map_or(source_start, |o| o.1),
+            _ => source_start,
+        };
+
+        (
+            column_at(self.source, source_start),
+            column_at(self.source, source_end),
+        )
+    }
+}
+
+//Runs the directives and turns the rest of the lines into bytes
+//
+// @include "other.thermal"   parses another file in place
+// @define NAME ESC "E" 1     NAME can then be used like a constant
+// @repeat 3 ... @end         repeats the lines in between
+// ${name}                    is replaced by a variable from the options
+struct Preprocessor<'a> {
+    options: &'a ThermalOptions,
+    macros: HashMap<String, Vec<u8>>,
+    //Files that are being parsed, the last one is the current file
+    files: Vec<PathBuf>,
+    errors: Vec<ThermalSyntaxError>,
+    bytes: Vec<u8>,
+    //How many times the current lines are parsed by the @repeat blocks
+    repeats: usize,
+}
+
+impl<'a> Preprocessor<'a> {
+    fn new(options: &'a ThermalOptions) -> Self {
+        Self {
+            options,
+            macros: HashMap::new(),
+            files: vec![],
+            errors: vec![],
+            bytes: vec![],
+            repeats: 1,
+        }
+    }
+
+    fn finish(self) -> Result<Vec<u8>, Vec<ThermalSyntaxError>> {
+        if self.errors.is_empty() {
+            Ok(self.bytes)
+        } else {
+            Err(self.errors)
+        }
+    }
+
+    //Lines in a @repeat block are parsed more than once, a problem
+    //is only reported the first time for the span it is in
+    fn error(&mut self, line: usize, column: usize, end_column: usize, message: String) {
+        let file = self.files.last().cloned();
+
+        if self.errors.iter().any(|e| {
+            e.file == file && e.line == line && e.column == column && e.end_column == end_column
+        }) {
+            return;
+        }
+
+        self.errors.push(ThermalSyntaxError {
+            file,
+            line,
+            column,
+            end_column,
+            severity: Severity::Error,
+            message,
+        });
+    }
+
+    //Error that spans a token sliced from the line
+    fn token_error(&mut self, number: usize, line: &Line, token: &str, message: String) {
+        let (column, end_column) = line.span(token);
+        self.error(number, column, end_column, message);
+    }
+
+    fn parse_text(&mut self, text: &str) {
+        let lines: Vec<(usize, &str)> = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line))
+            .collect();
+
+        self.parse_lines(&lines);
+    }
+
+    fn parse_lines(&mut self, lines: &[(usize, &str)]) {
+        let mut i = 0;
+
+        while i < lines.len() {
+            let (number, raw) = lines[i];
+            i += 1;
+
+            //skip comments
+            if raw.starts_with(COMMENT_PREFIX) || raw.trim().is_empty() {
+                continue;
+            }
+
+            let line = self.substitute(number, raw);
+            let (tokens, unterminated) = tokenize(&line.text);
+
+            if let Some(token) = unterminated {
+                self.token_error(number, &line, token, "Unterminated string".to_string());
+            }
+
+            match tokens.first().copied() {
+                Some("@include") => match tokens.get(1) {
+                    Some(path) if path.starts_with('"') => {
+                        let path = PathBuf::from(unescape(path));
+                        let (column, end_column) = line.span(tokens[0]);
+                        self.include(&path, number, column, end_column);
                     }
-                    //raw strings start with quote
-                    else if token.starts_with('"') {
-                        let unescaped = token.replace("\\\\", "\\").replace("\\\"", "\"");
-                        for byte in unescaped[1..].as_bytes() {
-                            parsed.push(*byte);
+                    _ => self.token_error(
+                        number,
+                        &line,
+                        tokens[0],
+                        "@include needs a quoted file name".to_string(),
+                    ),
+                },
+                Some("@define") => self.define(number, &line, &tokens),
+                Some("@repeat") => {
+                    let end = match repeat_end(lines, i) {
+                        Some(end) => end,
+                        None => {
+                            self.token_error(
+                                number,
+                                &line,
+                                tokens[0],
+                                "@repeat without @end".to_string(),
+                            );
+                            lines.len()
                         }
-                    }
-                    //Decimal
-                    else {
-                        let maybe_decimal = token.parse::<u8>();
+                    };
 
-                        //Can parse decimal from string
-                        if let Ok(decimal) = maybe_decimal {
-                            parsed.push(decimal)
-                        }
-                        //Cannot parse decimal, output raw
-                        else {
-                            for byte in token.as_bytes() {
-                                parsed.push(*byte);
+                    match tokens.get(1).and_then(|count| count.parse::<usize>().ok()) {
+                        Some(count) if count.saturating_mul(self.repeats) > MAX_REPEATS => self
+                            .token_error(
+                                number,
+                                &line,
+                                tokens[1],
+                                format!(
+                                    "@repeat {} is over the limit of {} repeats",
+                                    count, MAX_REPEATS
+                                ),
+                            ),
+                        Some(count) => {
+                            let repeats = self.repeats;
+                            self.repeats *= count.max(1);
+
+                            for _ in 0..count {
+                                self.parse_lines(&lines[i..end]);
                             }
+
+                            self.repeats = repeats;
                         }
+                        None => self.token_error(
+                            number,
+                            &line,
+                            tokens[0],
+                            "@repeat needs a count".to_string(),
+                        ),
                     }
+
+                    i = end + 1;
+                }
+                Some("@end") => {
+                    self.token_error(number, &line, tokens[0], "@end without @repeat".to_string())
+                }
+                Some(directive) if directive.starts_with('@') => self.token_error(
+                    number,
+                    &line,
+                    directive,
+                    format!("Unknown directive {}", directive),
+                ),
+                _ => {
+                    let bytes = self.tokens_to_bytes(number, &line, &tokens);
+                    self.bytes.extend(bytes);
                 }
             }
         }
     }
 
-    parsed
+    //Replaces ${name} with the variable, \${ is a literal ${.
+    //Unknown variables are kept as they are.
+    fn substitute<'l>(&mut self, number: usize, source: &'l str) -> Line<'l> {
+        let mut line = Line {
+            source,
+            text: String::with_capacity(source.len()),
+            origins: Vec::with_capacity(source.len()),
+        };
+
+        //Copies source[from..to] as it is
+        let copy = |line: &mut Line, from: usize, to: usize| {
+            line.text.push_str(&source[from..to]);
+            line.origins.extend((from..to).map(|o| (o, o + 1)));
+        };
+
+        let mut position = 0;
+
+        while let Some(found) = source[position..].find("${") {
+            let start = position + found;
+
+            //An odd number of backslashes escapes the $, an even
+            //number are escaped backslashes in a string
+            let backslashes = source[position..start]
+                .bytes()
+                .rev()
+                .take_while(|b| *b == b'\\')
+                .count();
+
+            if backslashes % 2 == 1 {
+                copy(&mut line, position, start - 1);
+                copy(&mut line, start, start + 2);
+                position = start + 2;
+                continue;
+            }
+
+            let end = match source[start..].find('}') {
+                Some(end) => start + end + 1,
+                None => break,
+            };
+
+            copy(&mut line, position, start);
+            let name = &source[start + 2..end - 1];
+
+            match self.options.variables.get(name) {
+                Some(value) => {
+                    line.text.push_str(value);
+                    line.origins
+                        .extend(std::iter::repeat_n((start, end), value.len()));
+                }
+                None => {
+                    let (column, end_column) = span_of(source, &source[start..end]);
+                    self.error(
+                        number,
+                        column,
+                        end_column,
+                        format!("Unknown variable {}", name),
+                    );
+                    copy(&mut line, start, end);
+                }
+            }
+
+            position = end;
+        }
+
+        copy(&mut line, position, source.len());
+        line
+    }
+
+    fn define(&mut self, number: usize, line: &Line, tokens: &[&str]) {
+        let name = match tokens.get(1) {
+            Some(name) if is_name(name) && constant(name).is_none() => name.to_string(),
+            Some(name) => {
+                self.token_error(
+                    number,
+                    line,
+                    name,
+                    format!("{} can't be used as a name", name),
+                );
+                return;
+            }
+            None => {
+                self.token_error(number, line, tokens[0], "@define needs a name".to_string());
+                return;
+            }
+        };
+
+        let bytes = self.tokens_to_bytes(number, line, &tokens[2..]);
+        self.macros.insert(name, bytes);
+    }
+
+    fn include(&mut self, path: &Path, number: usize, column: usize, end_column: usize) {
+        //Relative to the file with the include
+        let path = match self.files.last().and_then(|file| file.parent()) {
+            Some(dir) => dir.join(path),
+            None => match &self.options.include_dir {
+                Some(dir) => dir.join(path),
+                None => path.to_path_buf(),
+            },
+        };
+
+        let canonical = path.canonicalize().ok();
+
+        if self
+            .files
+            .iter()
+            .any(|file| file.canonicalize().ok() == canonical && canonical.is_some())
+        {
+            self.error(
+                number,
+                column,
+                end_column,
+                format!("{} includes itself", path.display()),
+            );
+            return;
+        }
+
+        match std::fs::read_to_string(&path) {
+            Ok(text) => {
+                self.files.push(path);
+                self.parse_text(&text);
+                self.files.pop();
+            }
+            Err(e) => self.error(
+                number,
+                column,
+                end_column,
+                format!("Can't read {}: {}", path.display(), e),
+            ),
+        }
+    }
+
+    fn tokens_to_bytes(&mut self, number: usize, line: &Line, tokens: &[&str]) -> Vec<u8> {
+        let mut parsed = Vec::new();
+
+        //Convert tokens to bytes
+        for token in tokens {
+            if let Some(byte) = constant(token) {
+                parsed.push(byte);
+            } else if let Some(bytes) = self.macros.get(*token) {
+                parsed.extend(bytes);
+            }
+            //Hex 0xFF for example
+            else if token.starts_with(HEX_PREFIX) {
+                let maybe_byte = u8::from_str_radix(&token[2..], 16);
+                if let Ok(byte) = maybe_byte {
+                    parsed.push(byte);
+                } else {
+                    self.token_error(number, line, token, format!("Invalid hex value {}", token));
+                }
+            }
+            //raw strings start with quote
+            else if token.starts_with('"') {
+                parsed.extend(unescape(token).as_bytes());
+            }
+            //Decimal
+            else if let Ok(decimal) = token.parse::<u8>() {
+                parsed.push(decimal)
+            }
+            //Cannot parse decimal, output raw
+            else {
+                let message = if token.chars().all(|c| c.is_ascii_digit()) {
+                    format!("Decimal value {} doesn't fit in a byte", token)
+                } else if is_name(token) {
+                    format!("Unknown name {}", token)
+                } else {
+                    format!("Unquoted text {}", token)
+                };
+
+                self.token_error(number, line, token, message);
+                parsed.extend(token.as_bytes());
+            }
+        }
+
+        parsed
+    }
+}
+
+//The contents of a quoted string token
+fn unescape(token: &str) -> String {
+    let unescaped = token.replace("\\\\", "\\").replace("\\\"", "\"");
+    unescaped[1..].to_string()
+}
+
+//Index of the @end for a @repeat, lines start after the @repeat
+fn repeat_end(lines: &[(usize, &str)], start: usize) -> Option<usize> {
+    let mut depth = 0;
+
+    for (i, (_, line)) in lines.iter().enumerate().skip(start) {
+        match parse_tokens(line).first().copied() {
+            Some("@repeat") => depth += 1,
+            Some("@end") if depth == 0 => return Some(i),
+            Some("@end") => depth -= 1,
+            _ => {}
+        }
+    }
+
+    None
 }
 
 pub fn parse_tokens(line: &str) -> Vec<&str> {
+    tokenize(line).0
+}
+
+//Tokens of a line, along with the last token if it is
+//a string that is missing the closing quote
+fn tokenize(line: &str) -> (Vec<&str>, Option<&str>) {
     let mut tokens = Vec::new();
     let mut span = (0, 0);
     let mut gobble_quoted = false;
@@ -186,14 +694,268 @@ pub fn parse_tokens(line: &str) -> Vec<&str> {
         tokens.push(&line[span.0..span.1])
     }
 
-    tokens
+    //Still in a string at the end of the line
+    let unterminated = match gobble_quoted {
+        true => tokens.last().copied(),
+        false => None,
+    };
+
+    (tokens, unterminated)
 }
 
-pub fn parse_binary(_bytes: Vec<u8>) -> Vec<String> {
-    let lines: Vec<String> = vec![];
+/// Decompile ESC/POS bytes into lines of the thermal format.
+/// Parsing the joined lines with parse_str gives back the same bytes.
+///
+/// Every command gets a comment with its name and what it does,
+/// and text gets a comment with the text decoded in the active codepage.
+pub fn parse_binary(bytes: Vec<u8>) -> Vec<String> {
+    let commands = crate::parse_esc_pos(&bytes);
+    let mut context = Context::new();
+    let mut lines: Vec<String> = vec![];
+
+    //Bytes up to here are in the lines
+    let mut position = 0;
+
+    for cmd in commands.iter() {
+        let comment = describe_command(cmd, &mut context);
+
+        //Begin and end parsing don't have any bytes and subcommands
+        //share the bytes of the command they came from
+        if cmd.end <= position {
+            continue;
+        }
+
+        let start = cmd.start.max(position);
+
+        if start > position {
+            lines.push(format!("{} Not part of a command", COMMENT_PREFIX));
+            lines.push(bytes_to_tokens(&bytes[position..start]).join(" "));
+            lines.push(String::new());
+        }
+
+        let cmd_bytes = &bytes[start..cmd.end];
+
+        match cmd.kind {
+            CommandType::Text => {
+                if let Some(comment) = comment {
+                    lines.push(format!("{} {}", COMMENT_PREFIX, comment));
+                }
+                lines.push(bytes_to_tokens(cmd_bytes).join(" "));
+            }
+            _ => {
+                if let Some(comment) = comment {
+                    lines.push(format!("{} {}", COMMENT_PREFIX, comment));
+                }
+
+                //Unknown commands don't have a known prefix
+                let prefix_len = if cmd.kind == CommandType::Unknown || start != cmd.start {
+                    1
+                } else {
+                    cmd.commands.len().clamp(1, cmd_bytes.len())
+                };
+
+                lines.extend(command_bytes_to_lines(
+                    &cmd_bytes[..prefix_len],
+                    &cmd_bytes[prefix_len..],
+                ));
+                lines.push(String::new());
+            }
+        }
+
+        position = cmd.end;
+    }
+
+    if position < bytes.len() {
+        lines.push(format!("{} Not part of a command", COMMENT_PREFIX));
+        lines.push(bytes_to_tokens(&bytes[position..]).join(" "));
+    }
+
     lines
 }
 
+//A comment for the command, applying the command to the context
+//the same way the renderer does so text is decoded correctly
+fn describe_command(cmd: &Command, context: &mut Context) -> Option<String> {
+    if cmd.kind == CommandType::Text {
+        let decoded = context.text.decoder.decode_utf8(&cmd.data);
+
+        //Only worth a comment when the text doesn't read the same as the bytes
+        return match std::str::from_utf8(&cmd.data) {
+            Ok(text) if text == decoded && !text.chars().any(|c| c.is_control()) => None,
+            _ => Some(format!("{:?}", decoded)),
+        };
+    }
+
+    let mut description = cmd.name.to_string();
+    let before = context.text.clone();
+
+    match cmd.kind {
+        CommandType::Context | CommandType::ContextControl | CommandType::TextStyle => {
+            cmd.handler.apply_context(cmd, context);
+        }
+        _ => {}
+    }
+
+    let mut notes = vec![];
+
+    if let Some(device_commands) = cmd.handler.get_device_command(cmd, context) {
+        notes.extend(device_commands.iter().map(|d| d.as_string()));
+
+        if device_commands.contains(&DeviceCommand::Initialize) {
+            context.reset();
+        }
+    }
+
+    for change in text_changes(&before, &context.text) {
+        if !notes.contains(&change) {
+            notes.push(change);
+        }
+    }
+
+    if !notes.is_empty() {
+        description.push_str(&format!(" ({})", notes.join(", ")));
+    }
+
+    //Keep the comment on one line
+    Some(description.replace(|c: char| c.is_control(), " "))
+}
+
+//How the text style changed
+fn text_changes(before: &TextContext, after: &TextContext) -> Vec<String> {
+    let on_off = |on: bool| if on { "On" } else { "Off" };
+    let mut changes = vec![];
+
+    if before.decoder.name != after.decoder.name
+        || before.decoder.language != after.decoder.language
+    {
+        changes.push(format!(
+            "{}, {}",
+            after.decoder.name, after.decoder.language
+        ));
+    }
+    if before.bold != after.bold {
+        changes.push(format!("Bold {}", on_off(after.bold)));
+    }
+    if before.italic != after.italic {
+        changes.push(format!("Italic {}", on_off(after.italic)));
+    }
+    if before.underline != after.underline {
+        changes.push(
+            match after.underline {
+                TextUnderline::Off => "Underline Off",
+                TextUnderline::On => "Underline On",
+                TextUnderline::Double => "Underline Double",
+            }
+            .to_string(),
+        );
+    }
+    if before.strikethrough != after.strikethrough {
+        changes.push(
+            match after.strikethrough {
+                TextStrikethrough::Off => "Strikethrough Off",
+                TextStrikethrough::On => "Strikethrough On",
+                TextStrikethrough::Double => "Strikethrough Double",
+            }
+            .to_string(),
+        );
+    }
+    if before.font != after.font {
+        changes.push(format!("Font {:?}", after.font));
+    }
+    if before.width_mult != after.width_mult || before.height_mult != after.height_mult {
+        changes.push(format!("Size {}x{}", after.width_mult, after.height_mult));
+    }
+    if before.invert != after.invert {
+        changes.push(format!("Invert {}", on_off(after.invert)));
+    }
+    if before.upside_down != after.upside_down {
+        changes.push(format!("Upside Down {}", on_off(after.upside_down)));
+    }
+    if before.justify != after.justify {
+        changes.push(format!("Justify {:?}", after.justify));
+    }
+    if before.line_spacing != after.line_spacing {
+        changes.push(format!("Line Spacing {}", after.line_spacing));
+    }
+
+    changes
+}
+
+//The command bytes followed by the data. Small data lists are
+//best expressed as digits and large ones are often graphics,
+//which are best expressed with hex
+fn command_bytes_to_lines(prefix: &[u8], data: &[u8]) -> Vec<String> {
+    let mut cmd_str = bytes_to_tokens(prefix).join(" ");
+
+    if data.len() < 10 {
+        for b in data.iter() {
+            cmd_str.push_str(&format!(" {}", b));
+        }
+        return vec![cmd_str];
+    }
+
+    let mut lines = vec![cmd_str];
+
+    for chunk in data.chunks(32) {
+        let data_str: Vec<String> = chunk.iter().map(|b| format!("0x{:02X}", b)).collect();
+        lines.push(data_str.join(" "));
+    }
+
+    lines
+}
+
+/// Tokens for the bytes, printable characters are grouped into
+/// strings and the rest are constants or hex.
+/// A $ before a { is escaped as \$ so it isn't read as a variable.
+pub fn bytes_to_tokens(bytes: &[u8]) -> Vec<String> {
+    let named = [
+        (NUL, "NUL"),
+        (ESC, "ESC"),
+        (HT, "HT"),
+        (LF, "LF"),
+        (FF, "FF"),
+        (CR, "CR"),
+        (GS, "GS"),
+        (FS, "FS"),
+        (DLE, "DLE"),
+        (CAN, "CAN"),
+    ];
+    let mut tokens = vec![];
+    let mut string = String::new();
+
+    for (i, byte) in bytes.iter().enumerate() {
+        //Quotes and backslashes are escaped in their own string
+        if (0x20..0x7F).contains(byte) && *byte != b'"' && *byte != b'\\' {
+            if *byte == b'$' && bytes.get(i + 1) == Some(&b'{') {
+                string.push('\\');
+            }
+
+            string.push(*byte as char);
+            continue;
+        }
+
+        if !string.is_empty() {
+            tokens.push(format!("\"{}\"", string));
+            string.clear();
+        }
+
+        tokens.push(match *byte {
+            b'"' => "\"\\\"\"".to_string(),
+            b'\\' => "\"\\\\\"".to_string(),
+            _ => match named.iter().find(|(b, _)| b == byte) {
+                Some((_, name)) => name.to_string(),
+                None => format!("0x{:02X}", byte),
+            },
+        });
+    }
+
+    if !string.is_empty() {
+        tokens.push(format!("\"{}\"", string));
+    }
+
+    tokens
+}
+
 /// Utility for converting commands into the human readable
 /// Thermal file format.
 pub fn cmds_to_thermal(cmds: &Vec<Command>) -> String {
@@ -214,8 +976,7 @@ pub fn cmd_to_thermal(cmd: &Command) -> String {
             return "LF \n".to_string();
         }
 
-        let text = String::from_utf8_lossy(data.as_slice());
-        return format!("\"{}\"\n", text);
+        return format!("{}\n", bytes_to_tokens(&data).join(" "));
     } else if cmd.kind == CommandType::Unknown {
         return format!("'// Unknown command \n {}\n\n", explain_unknown(&data)).to_string();
     }
@@ -0,0 +1,535 @@
+use crate::utils::barcodes::reed_solomon::ReedSolomon;
+
+// Aztec code generation
+//
+// Text is encoded with the upper, lower, mixed, punctuation and digit
+// tables (switching modes greedily) and anything else is binary shifted.
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Upper,
+    Lower,
+    Mixed,
+    Punctuation,
+    Digit,
+}
+
+impl Mode {
+    fn bits(&self) -> usize {
+        if *self == Mode::Digit {
+            4
+        } else {
+            5
+        }
+    }
+
+    // Code for a character in this mode's table
+    fn code(&self, c: u8) -> Option<u16> {
+        match self {
+            Mode::Upper => match c {
+                b' ' => Some(1),
+                b'A'..=b'Z' => Some((c - b'A' + 2) as u16),
+                _ => None,
+            },
+            Mode::Lower => match c {
+                b' ' => Some(1),
+                b'a'..=b'z' => Some((c - b'a' + 2) as u16),
+                _ => None,
+            },
+            Mode::Digit => match c {
+                b' ' => Some(1),
+                b'0'..=b'9' => Some((c - b'0' + 2) as u16),
+                b',' => Some(12),
+                b'.' => Some(13),
+                _ => None,
+            },
+            _ if c == 0 => None,
+            Mode::Mixed => MIXED.iter().position(|m| *m == c).map(|p| p as u16),
+            Mode::Punctuation => PUNCTUATION.iter().position(|p| *p == c).map(|p| p as u16),
+        }
+    }
+
+    // Latch codes to get from this mode to another
+    fn latch(&self, to: Mode) -> Vec<(u16, usize)> {
+        match (self, to) {
+            (Mode::Upper, Mode::Lower) => vec![(28, 5)],
+            (Mode::Upper, Mode::Mixed) => vec![(29, 5)],
+            (Mode::Upper, Mode::Digit) => vec![(30, 5)],
+            (Mode::Upper, Mode::Punctuation) => vec![(29, 5), (30, 5)],
+            (Mode::Lower, Mode::Upper) => vec![(30, 5), (14, 4)],
+            (Mode::Lower, Mode::Mixed) => vec![(29, 5)],
+            (Mode::Lower, Mode::Digit) => vec![(30, 5)],
+            (Mode::Lower, Mode::Punctuation) => vec![(29, 5), (30, 5)],
+            (Mode::Mixed, Mode::Upper) => vec![(29, 5)],
+            (Mode::Mixed, Mode::Lower) => vec![(28, 5)],
+            (Mode::Mixed, Mode::Punctuation) => vec![(30, 5)],
+            (Mode::Mixed, Mode::Digit) => vec![(29, 5), (30, 5)],
+            (Mode::Punctuation, Mode::Upper) => vec![(31, 5)],
+            (Mode::Digit, Mode::Upper) => vec![(14, 4)],
+            (from, to) if from == &to => vec![],
+            //Everything else goes through the upper mode
+            (from, to) => {
+                let mut codes = from.latch(Mode::Upper);
+                codes.extend(Mode::Upper.latch(to));
+                codes
+            }
+        }
+    }
+}
+
+// Mixed table, index 0 is the punctuation shift
+const MIXED: [u8; 28] = [
+    0, b' ', 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 27, 28, 29, 30, 31, b'@', b'\\', b'^',
+    b'_', b'`', b'|', b'~', 127,
+];
+
+// Punctuation table, indexes 0 and 2 - 5 are not single characters
+const PUNCTUATION: [u8; 31] = [
+    0, b'\r', 0, 0, 0, 0, b'!', b'"', b'#', b'$', b'%', b'&', b'\'', b'(', b')', b'*', b'+', b',',
+    b'-', b'.', b'/', b':', b';', b'<', b'=', b'>', b'?', b'[', b']', b'{', b'}',
+];
+
+const SEARCH_ORDER: [Mode; 5] = [
+    Mode::Upper,
+    Mode::Lower,
+    Mode::Digit,
+    Mode::Mixed,
+    Mode::Punctuation,
+];
+
+const PUNCTUATION_SHIFT: u16 = 0;
+const UPPER_SHIFT: u16 = 28;
+const BINARY_SHIFT: u16 = 31;
+
+pub struct Aztec {
+    pub size: usize,
+    /// One byte per module, 1 is dark
+    pub points: Vec<u8>,
+}
+
+impl Aztec {
+    /// Creates a new symbol
+    /// layers of 0 selects the smallest symbol that fits the data
+    /// ecc_percent is the minimum percentage of error correction (5 - 95)
+    pub fn new(data: &[u8], compact: bool, layers: u8, ecc_percent: u8) -> Result<Aztec, String> {
+        let bits = encode_high_level(data);
+        let ecc_bits = bits.len() * ecc_percent as usize / 100 + 11;
+        let max_layers = if compact { 4 } else { 32 };
+
+        let candidates: Vec<usize> = if layers == 0 {
+            (1..=max_layers).collect()
+        } else if layers as usize > max_layers {
+            return Err(format!(
+                "Invalid Aztec: {} layers is more than the maximum of {}.",
+                layers, max_layers
+            ));
+        } else {
+            vec![layers as usize]
+        };
+
+        for layers in candidates {
+            let total_bits = total_bits_in_layers(layers, compact);
+            let word_size = word_size(layers);
+            let usable_bits = total_bits - total_bits % word_size;
+            let stuffed = stuff_bits(&bits, word_size);
+            let data_words = stuffed.len() / word_size;
+
+            if stuffed.len() + ecc_bits > usable_bits {
+                continue;
+            }
+
+            //The mode message can only describe 64 (compact) or 2048 data words
+            if (compact && data_words > 64) || data_words > 2048 {
+                continue;
+            }
+
+            let message = add_check_words(&stuffed, total_bits, word_size);
+            let mode_message = mode_message(compact, layers, data_words);
+
+            return Ok(draw(compact, layers, &message, &mode_message));
+        }
+
+        Err(format!(
+            "Invalid Aztec: {} bytes of data do not fit into the requested number of layers.",
+            data.len()
+        ))
+    }
+}
+
+fn append_bits(bits: &mut Vec<bool>, value: u16, count: usize) {
+    for i in (0..count).rev() {
+        bits.push((value >> i) & 1 == 1);
+    }
+}
+
+fn encode_high_level(data: &[u8]) -> Vec<bool> {
+    let mut bits = vec![];
+    let mut mode = Mode::Upper;
+    let mut i = 0;
+
+    while i < data.len() {
+        let c = data[i];
+
+        if let Some(code) = mode.code(c) {
+            append_bits(&mut bits, code, mode.bits());
+            i += 1;
+            continue;
+        }
+
+        //Single upper case letter while in lower case
+        if mode == Mode::Lower {
+            if let Some(code) = Mode::Upper.code(c) {
+                append_bits(&mut bits, UPPER_SHIFT, 5);
+                append_bits(&mut bits, code, 5);
+                i += 1;
+                continue;
+            }
+        }
+
+        //Single punctuation character
+        if mode != Mode::Punctuation {
+            if let Some(code) = Mode::Punctuation.code(c) {
+                let next_is_punctuation = data
+                    .get(i + 1)
+                    .is_some_and(|n| Mode::Punctuation.code(*n).is_some() && mode.code(*n).is_none());
+
+                if !next_is_punctuation {
+                    append_bits(&mut bits, PUNCTUATION_SHIFT, mode.bits());
+                    append_bits(&mut bits, code, 5);
+                    i += 1;
+                    continue;
+                }
+            }
+        }
+
+        if let Some(to) = SEARCH_ORDER.iter().find(|m| m.code(c).is_some()) {
+            for (code, count) in mode.latch(*to) {
+                append_bits(&mut bits, code, count);
+            }
+            mode = *to;
+            continue;
+        }
+
+        //Binary shift is not available in the digit and punctuation modes
+        if mode == Mode::Digit || mode == Mode::Punctuation {
+            for (code, count) in mode.latch(Mode::Upper) {
+                append_bits(&mut bits, code, count);
+            }
+            mode = Mode::Upper;
+        }
+
+        let length = data[i..]
+            .iter()
+            .take_while(|b| SEARCH_ORDER.iter().all(|m| m.code(**b).is_none()))
+            .take(2078)
+            .count();
+
+        append_bits(&mut bits, BINARY_SHIFT, 5);
+
+        if length <= 31 {
+            append_bits(&mut bits, length as u16, 5);
+        } else {
+            append_bits(&mut bits, 0, 5);
+            append_bits(&mut bits, (length - 31) as u16, 11);
+        }
+
+        for b in &data[i..i + length] {
+            append_bits(&mut bits, *b as u16, 8);
+        }
+
+        i += length;
+    }
+
+    bits
+}
+
+fn total_bits_in_layers(layers: usize, compact: bool) -> usize {
+    ((if compact { 88 } else { 112 }) + 16 * layers) * layers
+}
+
+fn word_size(layers: usize) -> usize {
+    match layers {
+        0..=2 => 6,
+        3..=8 => 8,
+        9..=22 => 10,
+        _ => 12,
+    }
+}
+
+fn galois_field(word_size: usize) -> ReedSolomon {
+    match word_size {
+        4 => ReedSolomon::new(4, 0x13, 1),
+        6 => ReedSolomon::new(6, 0x43, 1),
+        8 => ReedSolomon::new(8, 0x12D, 1),
+        10 => ReedSolomon::new(10, 0x409, 1),
+        _ => ReedSolomon::new(12, 0x1069, 1),
+    }
+}
+
+// Words that are all zeros or all ones are not allowed, a complementary
+// bit is stuffed in and the remaining bit moves to the next word
+fn stuff_bits(bits: &[bool], word_size: usize) -> Vec<bool> {
+    let mut out = vec![];
+    let mask = (1u16 << word_size) - 2;
+    let mut i = 0;
+
+    while i < bits.len() {
+        let mut word = 0u16;
+
+        for j in 0..word_size {
+            if bits.get(i + j).copied().unwrap_or(true) {
+                word |= 1 << (word_size - 1 - j);
+            }
+        }
+
+        if word & mask == mask {
+            append_bits(&mut out, word & mask, word_size);
+            i += word_size - 1;
+        } else if word & mask == 0 {
+            append_bits(&mut out, word | 1, word_size);
+            i += word_size - 1;
+        } else {
+            append_bits(&mut out, word, word_size);
+            i += word_size;
+        }
+    }
+
+    out
+}
+
+fn to_words(bits: &[bool], word_size: usize) -> Vec<u16> {
+    bits.chunks(word_size)
+        .map(|w| w.iter().fold(0u16, |acc, b| (acc << 1) | *b as u16))
+        .collect()
+}
+
+// Appends the error correction words, the message is
+// padded at the start to fill out the total bits
+fn add_check_words(bits: &[bool], total_bits: usize, word_size: usize) -> Vec<bool> {
+    let words = to_words(bits, word_size);
+    let total_words = total_bits / word_size;
+    let ecc = galois_field(word_size).encode(&words, total_words - words.len());
+
+    let mut message = vec![false; total_bits % word_size];
+
+    for w in words.iter().chain(ecc.iter()) {
+        append_bits(&mut message, *w, word_size);
+    }
+
+    message
+}
+
+fn mode_message(compact: bool, layers: usize, data_words: usize) -> Vec<bool> {
+    let mut bits = vec![];
+
+    if compact {
+        append_bits(&mut bits, (layers - 1) as u16, 2);
+        append_bits(&mut bits, (data_words - 1) as u16, 6);
+        add_check_words(&bits, 28, 4)
+    } else {
+        append_bits(&mut bits, (layers - 1) as u16, 5);
+        append_bits(&mut bits, (data_words - 1) as u16, 11);
+        add_check_words(&bits, 40, 4)
+    }
+}
+
+struct Matrix {
+    size: usize,
+    points: Vec<u8>,
+}
+
+impl Matrix {
+    fn set(&mut self, x: usize, y: usize) {
+        self.points[y * self.size + x] = 1;
+    }
+}
+
+fn draw(compact: bool, layers: usize, message: &[bool], mode_message: &[bool]) -> Aztec {
+    let base_size = if compact { 11 } else { 14 } + layers * 4;
+
+    //Full range symbols have reference grid lines every 16 modules
+    //which shifts the position of the data modules
+    let (size, alignment) = if compact {
+        (base_size, (0..base_size).collect::<Vec<usize>>())
+    } else {
+        let size = base_size + 1 + 2 * ((base_size / 2 - 1) / 15);
+        let original_center = base_size / 2;
+        let center = size / 2;
+        let mut alignment = vec![0; base_size];
+
+        for i in 0..original_center {
+            let offset = i + i / 15;
+            alignment[original_center - i - 1] = center - offset - 1;
+            alignment[original_center + i] = center + offset + 1;
+        }
+
+        (size, alignment)
+    };
+
+    let mut matrix = Matrix {
+        size,
+        points: vec![0u8; size * size],
+    };
+
+    let bit = |i: usize| *message.get(i).unwrap_or(&false);
+    let mut row_offset = 0;
+
+    for i in 0..layers {
+        let row_size = (layers - i) * 4 + if compact { 9 } else { 12 };
+
+        for j in 0..row_size {
+            let column_offset = j * 2;
+
+            for k in 0..2 {
+                if bit(row_offset + column_offset + k) {
+                    matrix.set(alignment[i * 2 + k], alignment[i * 2 + j]);
+                }
+                if bit(row_offset + row_size * 2 + column_offset + k) {
+                    matrix.set(alignment[i * 2 + j], alignment[base_size - 1 - i * 2 - k]);
+                }
+                if bit(row_offset + row_size * 4 + column_offset + k) {
+                    matrix.set(
+                        alignment[base_size - 1 - i * 2 - k],
+                        alignment[base_size - 1 - i * 2 - j],
+                    );
+                }
+                if bit(row_offset + row_size * 6 + column_offset + k) {
+                    matrix.set(alignment[base_size - 1 - i * 2 - j], alignment[i * 2 + k]);
+                }
+            }
+        }
+
+        row_offset += row_size * 8;
+    }
+
+    let center = size / 2;
+    draw_mode_message(&mut matrix, compact, center, mode_message);
+
+    if compact {
+        draw_bullseye(&mut matrix, center, 5);
+    } else {
+        draw_bullseye(&mut matrix, center, 7);
+
+        let mut i = 0;
+        let mut j = 0;
+
+        while i < base_size / 2 - 1 {
+            let mut k = center & 1;
+
+            while k < size {
+                matrix.set(center - j, k);
+                matrix.set(center + j, k);
+                matrix.set(k, center - j);
+                matrix.set(k, center + j);
+                k += 2;
+            }
+
+            i += 15;
+            j += 16;
+        }
+    }
+
+    Aztec {
+        size,
+        points: matrix.points,
+    }
+}
+
+fn draw_mode_message(matrix: &mut Matrix, compact: bool, center: usize, mode_message: &[bool]) {
+    if compact {
+        for i in 0..7 {
+            let offset = center - 3 + i;
+            if mode_message[i] {
+                matrix.set(offset, center - 5);
+            }
+            if mode_message[i + 7] {
+                matrix.set(center + 5, offset);
+            }
+            if mode_message[20 - i] {
+                matrix.set(offset, center + 5);
+            }
+            if mode_message[27 - i] {
+                matrix.set(center - 5, offset);
+            }
+        }
+    } else {
+        for i in 0..10 {
+            let offset = center - 5 + i + i / 5;
+            if mode_message[i] {
+                matrix.set(offset, center - 7);
+            }
+            if mode_message[i + 10] {
+                matrix.set(center + 7, offset);
+            }
+            if mode_message[29 - i] {
+                matrix.set(offset, center + 7);
+            }
+            if mode_message[39 - i] {
+                matrix.set(center - 7, offset);
+            }
+        }
+    }
+}
+
+fn draw_bullseye(matrix: &mut Matrix, center: usize, size: usize) {
+    for i in (0..size).step_by(2) {
+        for j in center - i..=center + i {
+            matrix.set(j, center - i);
+            matrix.set(j, center + i);
+            matrix.set(center - i, j);
+            matrix.set(center + i, j);
+        }
+    }
+
+    //Orientation marks
+    matrix.set(center - size, center - size);
+    matrix.set(center - size + 1, center - size);
+    matrix.set(center - size, center - size + 1);
+    matrix.set(center + size, center - size);
+    matrix.set(center + size, center - size + 1);
+    matrix.set(center + size, center + size - 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::barcodes::aztec::*;
+
+    fn bits_to_string(bits: &[bool]) -> String {
+        bits.iter().map(|b| if *b { '1' } else { '0' }).collect()
+    }
+
+    #[test]
+    fn high_level_encoding() {
+        //U/L A B, D/L 1
+        assert_eq!(
+            bits_to_string(&encode_high_level(b"AB1")),
+            "0001000011111100011"
+        );
+        //Lower latch a, upper shift B
+        assert_eq!(
+            bits_to_string(&encode_high_level(b"aB")),
+            "11100000101110000011"
+        );
+    }
+
+    #[test]
+    fn bit_stuffing() {
+        let bits: Vec<bool> = "000000111111".chars().map(|c| c == '1').collect();
+        assert_eq!(
+            bits_to_string(&stuff_bits(&bits, 6)),
+            "000001011111111110"
+        );
+    }
+
+    #[test]
+    fn symbol_size() {
+        let symbol = Aztec::new(b"123456", true, 0, 23).unwrap();
+        assert_eq!(symbol.size, 15);
+
+        let symbol = Aztec::new(b"123456", false, 0, 23).unwrap();
+        assert_eq!(symbol.size, 19);
+
+        let too_much = vec![b'A'; 200];
+        assert!(Aztec::new(&too_much, true, 1, 23).is_err());
+    }
+}
@@ -0,0 +1,258 @@
+// Code 128 character set selection
+//
+// The barcoders Code128 symbology expects the data to be prefixed with the
+// character set to use and for every set change to be spelled out in the data.
+// These helpers pick the character sets for plain data, using the fewest
+// codewords so the symbol has the same width a printer would produce.
+
+use crate::utils::barcodes::gs1::FNC1;
+
+pub const SET_A: char = 'À';
+pub const SET_B: char = 'Ɓ';
+pub const SET_C: char = 'Ć';
+pub const BARCODERS_FNC1: char = '\u{0179}';
+const BARCODERS_FNC: [char; 4] = ['\u{0179}', '\u{017A}', '\u{017B}', '\u{017C}'];
+const BARCODERS_SHIFT: char = '\u{017D}';
+
+#[derive(Clone, Copy, PartialEq)]
+enum Token {
+    Char(u8),
+    //FNC1 to FNC4
+    Fnc(u8),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Set {
+    A,
+    B,
+    C,
+}
+
+impl Set {
+    fn char(self) -> char {
+        match self {
+            Set::A => SET_A,
+            Set::B => SET_B,
+            Set::C => SET_C,
+        }
+    }
+}
+
+//Order of preference when sets cost the same
+const SETS: [Set; 3] = [Set::C, Set::B, Set::A];
+
+/// Data for a GS1-128 symbol in the barcoders format,
+/// element string data with FNC1 in the first position
+pub fn gs1_128(data: &str) -> String {
+    let mut tokens = vec![Token::Fnc(1)];
+
+    for c in data.chars() {
+        tokens.push(if c == FNC1 {
+            Token::Fnc(1)
+        } else {
+            Token::Char(c as u8)
+        });
+    }
+
+    with_sets(&tokens)
+}
+
+/// Data for a Code 128 symbol with automatic character sets in the
+/// barcoders format, along with the HRI text. {1 to {4 are FNC1 to FNC4
+/// and {{ is a single {. A {S shift or {A {B {C set selection is
+/// accepted, but the sets and shifts are always picked automatically.
+pub fn auto(data: &str) -> Result<(String, String), String> {
+    let mut tokens = vec![];
+    let mut hri = String::new();
+    let mut chars = data.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '{' {
+            match chars.peek() {
+                Some(n @ '1'..='4') => {
+                    tokens.push(Token::Fnc(*n as u8 - b'0'));
+                    chars.next();
+                    continue;
+                }
+                Some('S' | 'A' | 'B' | 'C') => {
+                    chars.next();
+                    continue;
+                }
+                Some('{') => {
+                    chars.next();
+                }
+                _ => {}
+            }
+        }
+
+        if !c.is_ascii() {
+            return Err(format!("Character {} can't be encoded", c));
+        }
+
+        tokens.push(Token::Char(c as u8));
+        hri.push(if c.is_ascii_control() { ' ' } else { c });
+    }
+
+    if tokens.is_empty() {
+        return Err("No data".to_string());
+    }
+
+    Ok((with_sets(&tokens), hri))
+}
+
+//Codewords and set changes, symbols with fewer changes
+//are preferred when they have the same width
+type Cost = (usize, usize);
+
+const SWITCH: Cost = (1, 1);
+
+fn add(a: Cost, b: Cost) -> Cost {
+    (a.0 + b.0, a.1 + b.1)
+}
+
+//Cost of the token at i in a set without switching,
+//and how many tokens that uses up
+fn encode_cost(tokens: &[Token], i: usize, set: Set) -> Option<(Cost, usize)> {
+    match (tokens[i], set) {
+        (Token::Fnc(1), Set::C) => Some(((1, 0), 1)),
+        (Token::Fnc(_), Set::C) => None,
+        (Token::Fnc(_), _) => Some(((1, 0), 1)),
+        (Token::Char(c), Set::C) => match tokens.get(i + 1) {
+            Some(Token::Char(d)) if c.is_ascii_digit() && d.is_ascii_digit() => Some(((1, 0), 2)),
+            _ => None,
+        },
+        (Token::Char(c), Set::A) if c < 0x60 => Some(((1, 0), 1)),
+        (Token::Char(c), Set::B) if c >= 0x20 => Some(((1, 0), 1)),
+        //Shift to the other set for a single character
+        (Token::Char(_), _) => Some(((2, 1), 1)),
+    }
+}
+
+fn with_sets(tokens: &[Token]) -> String {
+    let n = tokens.len();
+
+    //Fewest codewords for the tokens from i on, while in a set
+    let mut best = vec![[(0, 0); 3]; n + 1];
+
+    //Same as best, but the token at i has to be encoded in the set
+    let direct = |best: &Vec<[Cost; 3]>, i: usize, set: Set| {
+        encode_cost(tokens, i, set).map(|(cost, used)| add(cost, best[i + used][set as usize]))
+    };
+
+    for i in (0..n).rev() {
+        for set in SETS {
+            best[i][set as usize] = SETS
+                .iter()
+                .filter_map(|&to| {
+                    let switch = if to == set { (0, 0) } else { SWITCH };
+                    direct(&best, i, to).map(|cost| add(cost, switch))
+                })
+                .min()
+                .unwrap_or((usize::MAX / 2, 0));
+        }
+    }
+
+    let mut set = *SETS
+        .iter()
+        .min_by_key(|&&set| direct(&best, 0, set).unwrap_or((usize::MAX, 0)))
+        .unwrap_or(&Set::B);
+
+    let mut out = String::new();
+    out.push(set.char());
+
+    let mut i = 0;
+
+    while i < n {
+        //Stay in the current set unless switching is cheaper
+        let target = best[i][set as usize];
+
+        if direct(&best, i, set) != Some(target) {
+            set = *SETS
+                .iter()
+                .find(|&&to| {
+                    to != set && direct(&best, i, to).map(|c| add(c, SWITCH)) == Some(target)
+                })
+                .unwrap_or(&set);
+            out.push(set.char());
+        }
+
+        let used = encode_cost(tokens, i, set).map_or(1, |(_, used)| used);
+
+        match (tokens[i], set) {
+            (Token::Fnc(f), _) => out.push(BARCODERS_FNC[f as usize - 1]),
+            (Token::Char(c), Set::C) => {
+                out.push(c as char);
+                if let Some(Token::Char(d)) = tokens.get(i + 1) {
+                    out.push(*d as char);
+                }
+            }
+            (Token::Char(c), Set::A) if c >= 0x60 => {
+                out.push(BARCODERS_SHIFT);
+                out.push((c - 0x60) as char);
+            }
+            (Token::Char(c), Set::B) if c < 0x20 => {
+                out.push(BARCODERS_SHIFT);
+                out.push(set_b_char(c + 0x60));
+            }
+            (Token::Char(c), Set::B) => out.push(set_b_char(c)),
+            (Token::Char(c), _) => out.push(c as char),
+        }
+
+        i += used;
+    }
+
+    out
+}
+
+//DEL is spelled as ÷ in the barcoders set B
+fn set_b_char(c: u8) -> char {
+    if c == 0x7F {
+        '\u{00F7}'
+    } else {
+        c as char
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::barcodes::code128::{auto, gs1_128};
+    use crate::utils::barcodes::gs1::FNC1;
+    use barcoders::sym::code128::Code128;
+
+    #[test]
+    fn gs1_sets() {
+        assert_eq!(gs1_128("0100012345678905"), "ĆŹ0100012345678905");
+        assert_eq!(
+            gs1_128(&format!("10ABC{}3103001750", FNC1)),
+            "ƁŹ10ABCŹĆ3103001750"
+        );
+        assert_eq!(gs1_128("10A1"), "ƁŹ10A1");
+        assert_eq!(gs1_128("21X12345"), "ƁŹ21X1Ć2345");
+    }
+
+    #[test]
+    fn auto_sets() {
+        assert_eq!(auto("1234").unwrap().0, "Ć1234");
+        assert_eq!(auto("123").unwrap().0, "Ɓ123");
+        assert_eq!(auto("AB123456").unwrap().0, "ƁABĆ123456");
+        assert_eq!(auto("AB12345").unwrap().0, "ƁAB1Ć2345");
+        assert_eq!(auto("a\tb").unwrap().0, "ƁaŽib");
+        assert_eq!(auto("\t\na").unwrap().0, "À\t\nŽ\u{1}");
+        assert_eq!(auto("\t\nab").unwrap().0, "À\t\nƁab");
+
+        let (data, hri) = auto("{1{{12{S34").unwrap();
+        assert_eq!(data, "ƁŹ{Ć1234");
+        assert_eq!(hri, "{1234");
+
+        assert!(auto("ñ").is_err());
+        assert!(auto("").is_err());
+    }
+
+    #[test]
+    fn auto_encodes() {
+        for data in ["1234", "AB12345", "a\tb", "\t\na", "{1{{12{S34", "x\u{7f}"] {
+            let (data, _) = auto(data).unwrap();
+            assert!(Code128::new(&data).is_ok(), "{}", data);
+        }
+    }
+}
@@ -0,0 +1,904 @@
+use crate::utils::barcodes::gs1::{check_digit, FNC1};
+
+// GS1 DataBar (formerly RSS) generation
+//
+// Data characters are turned into bar and space widths with the RSS
+// combination algorithm and are placed around finder patterns.
+// Omnidirectional, truncated and stacked symbols hold a 13 digit GTIN,
+// expanded symbols hold a GS1 element string and can be stacked in rows.
+
+// Omnidirectional characters left and right of the finders
+const OUTSIDE_SUM: [i32; 5] = [0, 161, 961, 2015, 2715];
+const OUTSIDE_EVEN_TOTAL: [i32; 5] = [1, 10, 34, 70, 126];
+const OUTSIDE_ODD_MODULES: [i32; 5] = [12, 10, 8, 6, 4];
+const OUTSIDE_ODD_WIDEST: [i32; 5] = [8, 6, 4, 3, 1];
+
+// Omnidirectional characters between the finders
+const INSIDE_SUM: [i32; 4] = [0, 336, 1036, 1516];
+const INSIDE_ODD_TOTAL: [i32; 4] = [4, 20, 48, 81];
+const INSIDE_ODD_MODULES: [i32; 4] = [5, 7, 9, 11];
+const INSIDE_ODD_WIDEST: [i32; 4] = [2, 4, 6, 8];
+
+const OMNI_FINDERS: [[u8; 5]; 9] = [
+    [3, 8, 2, 1, 1],
+    [3, 5, 5, 1, 1],
+    [3, 3, 7, 1, 1],
+    [3, 1, 9, 1, 1],
+    [2, 7, 4, 1, 1],
+    [2, 5, 6, 1, 1],
+    [2, 3, 8, 1, 1],
+    [1, 5, 7, 1, 1],
+    [1, 3, 9, 1, 1],
+];
+
+const EXPANDED_SUM: [i32; 5] = [0, 348, 1388, 2948, 3988];
+const EXPANDED_EVEN_TOTAL: [i32; 5] = [4, 20, 52, 104, 204];
+const EXPANDED_ODD_MODULES: [i32; 5] = [12, 10, 8, 6, 4];
+const EXPANDED_ODD_WIDEST: [i32; 5] = [7, 5, 4, 3, 1];
+
+// Finders A to F
+const EXPANDED_FINDERS: [[u8; 5]; 6] = [
+    [1, 8, 4, 1, 1],
+    [3, 6, 4, 1, 1],
+    [3, 4, 6, 1, 1],
+    [3, 2, 8, 1, 1],
+    [2, 6, 5, 1, 1],
+    [2, 2, 9, 1, 1],
+];
+
+// Finder sequences by the number of character pairs (2 - 11)
+const EXPANDED_SEQUENCES: [&[usize]; 10] = [
+    &[0, 0],
+    &[0, 1, 1],
+    &[0, 2, 1, 3],
+    &[0, 4, 1, 3, 2],
+    &[0, 4, 1, 3, 3, 5],
+    &[0, 4, 1, 3, 4, 5, 5],
+    &[0, 0, 1, 1, 2, 2, 3, 3],
+    &[0, 0, 1, 1, 2, 2, 3, 4, 4],
+    &[0, 0, 1, 1, 2, 2, 3, 4, 5, 5],
+    &[0, 0, 1, 1, 2, 3, 3, 4, 4, 5, 5],
+];
+
+const EXPANDED_MAX_CHARACTERS: usize = 22;
+
+pub struct DataBarRow {
+    pub modules: Vec<u8>,
+    /// Height of the row in modules
+    pub height: u32,
+}
+
+pub struct DataBar {
+    pub width: u32,
+    pub rows: Vec<DataBarRow>,
+}
+
+impl DataBar {
+    /// Single row omnidirectional symbol for a 13 digit GTIN
+    pub fn omnidirectional(data: &[u8]) -> Result<DataBar, String> {
+        let modules = to_modules(&omni_elements(data)?, false);
+        Ok(DataBar::new(vec![DataBarRow {
+            modules,
+            height: 33,
+        }]))
+    }
+
+    /// Omnidirectional symbol that is only 13 modules tall
+    pub fn truncated(data: &[u8]) -> Result<DataBar, String> {
+        let mut symbol = DataBar::omnidirectional(data)?;
+        symbol.rows[0].height = 13;
+        Ok(symbol)
+    }
+
+    /// Two row symbol with a single separator row
+    pub fn stacked(data: &[u8]) -> Result<DataBar, String> {
+        let (top, bottom) = stacked_rows(data)?;
+
+        let mut separator = vec![0u8; top.len()];
+        for i in 4..top.len() - 4 {
+            separator[i] = if top[i] == bottom[i] {
+                (top[i] == 0) as u8
+            } else {
+                (separator[i - 1] == 0) as u8
+            };
+        }
+
+        Ok(DataBar::new(vec![
+            DataBarRow {
+                modules: top,
+                height: 5,
+            },
+            DataBarRow {
+                modules: separator,
+                height: 1,
+            },
+            DataBarRow {
+                modules: bottom,
+                height: 7,
+            },
+        ]))
+    }
+
+    /// Two full height rows with a three row separator
+    pub fn stacked_omnidirectional(data: &[u8]) -> Result<DataBar, String> {
+        let (top, bottom) = stacked_rows(data)?;
+        let width = top.len();
+
+        let mut top_separator = complement(&top);
+        finder_separator(&top, &mut top_separator, 18..31);
+
+        let mut bottom_separator = complement(&bottom);
+        finder_separator(&bottom, &mut bottom_separator, 19..32);
+
+        Ok(DataBar::new(vec![
+            DataBarRow {
+                modules: top,
+                height: 33,
+            },
+            DataBarRow {
+                modules: top_separator,
+                height: 1,
+            },
+            DataBarRow {
+                modules: alternating(width),
+                height: 1,
+            },
+            DataBarRow {
+                modules: bottom_separator,
+                height: 1,
+            },
+            DataBarRow {
+                modules: bottom,
+                height: 33,
+            },
+        ]))
+    }
+
+    /// Expanded symbol for an element string (see gs1::ElementString)
+    /// segments per row of 0 creates a single row, otherwise
+    /// an expanded stacked symbol is created (2 - 20, even)
+    pub fn expanded(data: &str, segments: usize) -> Result<DataBar, String> {
+        if segments != 0 && (!(2..=20).contains(&segments) || !segments.is_multiple_of(2)) {
+            return Err(format!(
+                "Invalid GS1 DataBar Expanded Stacked: {} segments per row",
+                segments
+            ));
+        }
+
+        let characters = expanded_characters(data, segments)?;
+        let pairs = characters.len().div_ceil(2);
+        let sequence = EXPANDED_SEQUENCES[pairs - 2];
+        let pairs_per_row = if segments == 0 { pairs } else { segments / 2 };
+        let row_count = pairs.div_ceil(pairs_per_row);
+        let mut rows: Vec<DataBarRow> = vec![];
+        let mut previous: Option<Vec<u8>> = None;
+
+        for r in 0..row_count {
+            let first = r * pairs_per_row;
+            let last = pairs.min(first + pairs_per_row);
+
+            let mut elements = vec![1u8, 1];
+            let mut finders = vec![];
+
+            for p in first..last {
+                let left = &characters[p * 2];
+                elements.extend_from_slice(left);
+
+                finders.push(elements.iter().map(|e| *e as usize).sum::<usize>());
+                let finder = EXPANDED_FINDERS[sequence[p]];
+                if p % 2 == 0 {
+                    elements.extend_from_slice(&finder);
+                } else {
+                    elements.extend(finder.iter().rev());
+                }
+
+                if let Some(right) = characters.get(p * 2 + 1) {
+                    elements.extend(right.iter().rev());
+                }
+            }
+
+            elements.extend_from_slice(&[1, 1]);
+
+            //Rows start on a space, unless the first pair has a reversed finder
+            let mut modules = to_modules(&elements, first % 2 == 1);
+
+            //Every other row is printed right to left when the rows have an even
+            //number of pairs, except for a short last row with an odd number of
+            //missing pairs, which is shifted one module instead.
+            let even_row = r % 2 == 1;
+            let missing = pairs_per_row - (last - first);
+            let reversed = even_row && pairs_per_row % 2 == 0 && missing % 2 == 0;
+            let shifted = even_row && pairs_per_row % 2 == 0 && missing % 2 == 1;
+
+            let mut separator = complement(&modules);
+            for f in &finders {
+                finder_separator_expanded(&modules, &mut separator, *f..*f + 15);
+            }
+
+            if reversed {
+                modules.reverse();
+                separator.reverse();
+            }
+
+            if shifted {
+                modules.insert(0, 0);
+                separator.insert(0, 0);
+            }
+
+            if let Some(above) = previous.take() {
+                let width = modules.len().max(above.len());
+                rows.push(DataBarRow {
+                    modules: above,
+                    height: 1,
+                });
+                rows.push(DataBarRow {
+                    modules: alternating(width),
+                    height: 1,
+                });
+                rows.push(DataBarRow {
+                    modules: separator.clone(),
+                    height: 1,
+                });
+            }
+
+            previous = Some(separator);
+            rows.push(DataBarRow {
+                modules,
+                height: 34,
+            });
+        }
+
+        Ok(DataBar::new(rows))
+    }
+
+    fn new(mut rows: Vec<DataBarRow>) -> DataBar {
+        let width = rows.iter().map(|r| r.modules.len()).max().unwrap_or(0);
+
+        for row in rows.iter_mut() {
+            row.modules.resize(width, 0);
+        }
+
+        DataBar {
+            width: width as u32,
+            rows,
+        }
+    }
+
+    /// All rows, with each row repeated for its height
+    pub fn points(&self) -> Vec<u8> {
+        let mut points = vec![];
+
+        for row in &self.rows {
+            for _ in 0..row.height {
+                points.extend_from_slice(&row.modules);
+            }
+        }
+
+        points
+    }
+}
+
+/// Most segments that fit in a row of the max width,
+/// a max width of 0 puts all segments in a single row
+pub fn segments_per_row(max_width: u32, module_width: u32) -> usize {
+    if max_width == 0 {
+        return 0;
+    }
+
+    //Each pair of segments is 49 modules, plus 4 for the guards
+    let pairs = (max_width / module_width.max(1)).saturating_sub(4) / 49;
+    (pairs as usize * 2).clamp(2, 20)
+}
+
+/// HRI text for omnidirectional, truncated and stacked symbols
+pub fn gtin_hri(data: &[u8]) -> String {
+    format!(
+        "(01){}{}",
+        String::from_utf8_lossy(data),
+        check_digit(data) as char
+    )
+}
+
+fn combinations(n: i32, r: i32) -> i32 {
+    if r < 0 || r > n {
+        return 0;
+    }
+
+    let r = r.min(n - r);
+    let mut value = 1i64;
+
+    for i in 0..r as i64 {
+        value = value * (n as i64 - i) / (i + 1);
+    }
+
+    value as i32
+}
+
+// Widths of the odd or even elements of a character for a value.
+// no_narrow excludes the combinations without a single module element.
+fn rss_widths(value: i32, modules: i32, elements: i32, max_width: i32, no_narrow: bool) -> Vec<u8> {
+    let mut widths = vec![0u8; elements as usize];
+    let mut value = value;
+    let mut n = modules;
+    let mut narrow_mask = 0;
+
+    for bar in 0..elements - 1 {
+        let mut element_width = 1;
+        let mut sub_value;
+        narrow_mask |= 1 << bar;
+
+        loop {
+            //All combinations
+            sub_value = combinations(n - element_width - 1, elements - bar - 2);
+
+            //Less the combinations without a narrow element
+            if no_narrow
+                && narrow_mask == 0
+                && n - element_width - (elements - bar - 1) >= elements - bar - 1
+            {
+                sub_value -= combinations(n - element_width - (elements - bar), elements - bar - 2);
+            }
+
+            //Less the combinations with elements that are too wide
+            if elements - bar - 1 > 1 {
+                let mut less_value = 0;
+                let mut widest = n - element_width - (elements - bar - 2);
+
+                while widest > max_width {
+                    less_value += combinations(n - element_width - widest - 1, elements - bar - 3);
+                    widest -= 1;
+                }
+
+                sub_value -= less_value * (elements - 1 - bar);
+            } else if n - element_width > max_width {
+                sub_value -= 1;
+            }
+
+            value -= sub_value;
+
+            if value < 0 {
+                break;
+            }
+
+            element_width += 1;
+            narrow_mask &= !(1 << bar);
+        }
+
+        value += sub_value;
+        n -= element_width;
+        widths[bar as usize] = element_width as u8;
+    }
+
+    widths[elements as usize - 1] = n as u8;
+    widths
+}
+
+// Element widths in the order odd, even, odd, even...
+fn interleave(odd: &[u8], even: &[u8]) -> [u8; 8] {
+    let mut widths = [0u8; 8];
+
+    for i in 0..4 {
+        widths[i * 2] = odd[i];
+        widths[i * 2 + 1] = even[i];
+    }
+
+    widths
+}
+
+fn group(sums: &[i32], value: i32) -> usize {
+    sums.iter().rposition(|s| value >= *s).unwrap_or(0)
+}
+
+fn outside_character(value: i32) -> [u8; 8] {
+    let g = group(&OUTSIDE_SUM, value);
+    let value = value - OUTSIDE_SUM[g];
+    let odd = rss_widths(
+        value / OUTSIDE_EVEN_TOTAL[g],
+        OUTSIDE_ODD_MODULES[g],
+        4,
+        OUTSIDE_ODD_WIDEST[g],
+        false,
+    );
+    let even = rss_widths(
+        value % OUTSIDE_EVEN_TOTAL[g],
+        16 - OUTSIDE_ODD_MODULES[g],
+        4,
+        9 - OUTSIDE_ODD_WIDEST[g],
+        true,
+    );
+
+    interleave(&odd, &even)
+}
+
+fn inside_character(value: i32) -> [u8; 8] {
+    let g = group(&INSIDE_SUM, value);
+    let value = value - INSIDE_SUM[g];
+    let odd = rss_widths(
+        value % INSIDE_ODD_TOTAL[g],
+        INSIDE_ODD_MODULES[g],
+        4,
+        INSIDE_ODD_WIDEST[g],
+        true,
+    );
+    let even = rss_widths(
+        value / INSIDE_ODD_TOTAL[g],
+        15 - INSIDE_ODD_MODULES[g],
+        4,
+        9 - INSIDE_ODD_WIDEST[g],
+        false,
+    );
+
+    interleave(&odd, &even)
+}
+
+fn expanded_character(value: i32) -> [u8; 8] {
+    let g = group(&EXPANDED_SUM, value);
+    let value = value - EXPANDED_SUM[g];
+    let odd = rss_widths(
+        value / EXPANDED_EVEN_TOTAL[g],
+        EXPANDED_ODD_MODULES[g],
+        4,
+        EXPANDED_ODD_WIDEST[g],
+        true,
+    );
+    let even = rss_widths(
+        value % EXPANDED_EVEN_TOTAL[g],
+        17 - EXPANDED_ODD_MODULES[g],
+        4,
+        9 - EXPANDED_ODD_WIDEST[g],
+        false,
+    );
+
+    interleave(&odd, &even)
+}
+
+// Expands element widths into modules, alternating light and dark
+fn to_modules(elements: &[u8], dark_first: bool) -> Vec<u8> {
+    let mut modules = vec![];
+    let mut dark = dark_first;
+
+    for width in elements {
+        modules.extend(std::iter::repeat_n(dark as u8, *width as usize));
+        dark = !dark;
+    }
+
+    modules
+}
+
+// Separator row that is the complement of a row, leaving
+// the first and last four modules light
+fn complement(row: &[u8]) -> Vec<u8> {
+    let mut separator = vec![0u8; row.len()];
+
+    for i in 4..row.len().saturating_sub(4) {
+        separator[i] = (row[i] == 0) as u8;
+    }
+
+    separator
+}
+
+// Separator row of alternating modules
+fn alternating(width: usize) -> Vec<u8> {
+    let mut separator = vec![0u8; width];
+
+    for i in (5..width.saturating_sub(4)).step_by(2) {
+        separator[i] = 1;
+    }
+
+    separator
+}
+
+// Above and below a finder the light modules of the separator
+// alternate instead of forming a solid bar
+fn finder_separator(row: &[u8], separator: &mut [u8], finder: std::ops::Range<usize>) {
+    let mut dark = true;
+
+    for i in finder {
+        if row[i] == 0 {
+            separator[i] = dark as u8;
+            dark = !dark;
+        } else {
+            separator[i] = 0;
+            dark = true;
+        }
+    }
+}
+
+fn finder_separator_expanded(row: &[u8], separator: &mut [u8], finder: std::ops::Range<usize>) {
+    for i in finder {
+        if i < row.len() && row[i] == 0 && row[i - 1] == 0 && separator[i - 1] == 1 {
+            separator[i] = 0;
+        }
+    }
+}
+
+// Element widths of an omnidirectional symbol
+fn omni_elements(data: &[u8]) -> Result<[u8; 46], String> {
+    if data.len() != 13 || !data.iter().all(|b| b.is_ascii_digit()) {
+        return Err(format!(
+            "Invalid GS1 DataBar data {}, 13 digits are required",
+            String::from_utf8_lossy(data)
+        ));
+    }
+
+    let value: i64 = data.iter().fold(0, |v, d| v * 10 + (d - b'0') as i64);
+    let left = (value / 4537077) as i32;
+    let right = (value % 4537077) as i32;
+
+    let characters = [
+        outside_character(left / 1597),
+        inside_character(left % 1597),
+        outside_character(right / 1597),
+        inside_character(right % 1597),
+    ];
+
+    let mut checksum = 0;
+    let mut weight = 1;
+
+    for c in &characters {
+        for width in c {
+            checksum += *width as u32 * weight;
+            weight = weight * 3 % 79;
+        }
+    }
+
+    let mut checksum = checksum % 79;
+
+    if checksum >= 8 {
+        checksum += 1;
+    }
+    if checksum >= 72 {
+        checksum += 1;
+    }
+
+    let left_finder = OMNI_FINDERS[checksum as usize / 9];
+    let right_finder = OMNI_FINDERS[checksum as usize % 9];
+
+    let mut elements = [0u8; 46];
+    elements[0] = 1;
+    elements[1] = 1;
+    elements[44] = 1;
+    elements[45] = 1;
+
+    for i in 0..8 {
+        elements[i + 2] = characters[0][i];
+        elements[i + 15] = characters[1][7 - i];
+        elements[i + 23] = characters[3][i];
+        elements[i + 36] = characters[2][7 - i];
+    }
+
+    for i in 0..5 {
+        elements[i + 10] = left_finder[i];
+        elements[i + 31] = right_finder[4 - i];
+    }
+
+    Ok(elements)
+}
+
+// Top and bottom rows of the stacked symbols
+fn stacked_rows(data: &[u8]) -> Result<(Vec<u8>, Vec<u8>), String> {
+    let elements = omni_elements(data)?;
+
+    let mut top = to_modules(&elements[..23], false);
+    top.extend_from_slice(&[1, 0]);
+
+    let mut bottom = vec![1, 0];
+    bottom.extend(to_modules(&elements[23..], true));
+
+    Ok((top, bottom))
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Mode {
+    Numeric,
+    Alphanumeric,
+    Iso646,
+}
+
+fn push_bits(bits: &mut Vec<bool>, value: u32, count: usize) {
+    for i in (0..count).rev() {
+        bits.push(value >> i & 1 == 1);
+    }
+}
+
+fn is_numeric(c: char) -> bool {
+    c.is_ascii_digit() || c == FNC1
+}
+
+fn numeric_value(c: char) -> u32 {
+    if c == FNC1 {
+        10
+    } else {
+        c as u32 - '0' as u32
+    }
+}
+
+fn alphanumeric_value(c: char) -> Option<(u32, usize)> {
+    match c {
+        '0'..='9' => Some((c as u32 - '0' as u32 + 5, 5)),
+        'A'..='Z' => Some((c as u32 - 'A' as u32 + 32, 6)),
+        '*' => Some((58, 6)),
+        ',' => Some((59, 6)),
+        '-' => Some((60, 6)),
+        '.' => Some((61, 6)),
+        '/' => Some((62, 6)),
+        _ => None,
+    }
+}
+
+fn iso646_value(c: char) -> Option<(u32, usize)> {
+    const SPECIAL: &str = "!\"%&'()*+,-./:;<=>?_ ";
+
+    match c {
+        '0'..='9' => Some((c as u32 - '0' as u32 + 5, 5)),
+        'A'..='Z' => Some((c as u32 - 'A' as u32 + 64, 7)),
+        'a'..='z' => Some((c as u32 - 'a' as u32 + 90, 7)),
+        _ => SPECIAL.find(c).map(|p| (p as u32 + 232, 8)),
+    }
+}
+
+// Number of characters from i that can be encoded in numeric mode
+fn numeric_run(data: &[char], i: usize) -> usize {
+    data[i..].iter().take_while(|c| is_numeric(**c)).count()
+}
+
+// General purpose data compaction, FNC1 is always encoded in
+// numeric mode so the alphanumeric and ISO 646 FNC1 is never used.
+// Returns the mode at the end of the data for padding.
+fn encode_general(data: &[char], bits: &mut Vec<bool>) -> Result<Mode, String> {
+    let mut mode = Mode::Numeric;
+    let mut i = 0;
+
+    while i < data.len() {
+        let c = data[i];
+
+        match mode {
+            Mode::Numeric => {
+                let next = data.get(i + 1).copied();
+
+                match next {
+                    Some(n) if is_numeric(c) && is_numeric(n) && !(c == FNC1 && n == FNC1) => {
+                        push_bits(bits, numeric_value(c) * 11 + numeric_value(n) + 8, 7);
+                        i += 2;
+                    }
+                    //A single digit at the end is paired with FNC1
+                    None if c.is_ascii_digit() => {
+                        push_bits(bits, numeric_value(c) * 11 + 10 + 8, 7);
+                        i += 1;
+                    }
+                    _ if c == FNC1 => {
+                        return Err(
+                            "FNC1 has to be followed by an application identifier".to_string()
+                        );
+                    }
+                    _ => {
+                        push_bits(bits, 0, 4);
+                        mode = Mode::Alphanumeric;
+                    }
+                }
+            }
+            Mode::Alphanumeric | Mode::Iso646 => {
+                if c == FNC1 || numeric_run(data, i) >= 4 {
+                    push_bits(bits, 0, 3);
+                    mode = Mode::Numeric;
+                    continue;
+                }
+
+                if mode == Mode::Alphanumeric {
+                    match alphanumeric_value(c) {
+                        Some((value, count)) => {
+                            push_bits(bits, value, count);
+                            i += 1;
+                        }
+                        None => {
+                            push_bits(bits, 4, 5);
+                            mode = Mode::Iso646;
+                        }
+                    }
+                    continue;
+                }
+
+                let alphanumeric_run = data[i..]
+                    .iter()
+                    .take_while(|c| alphanumeric_value(**c).is_some())
+                    .count();
+
+                if alphanumeric_run >= 5 {
+                    push_bits(bits, 4, 5);
+                    mode = Mode::Alphanumeric;
+                    continue;
+                }
+
+                match iso646_value(c) {
+                    Some((value, count)) => {
+                        push_bits(bits, value, count);
+                        i += 1;
+                    }
+                    None => return Err(format!("Invalid GS1 DataBar character {:?}", c)),
+                }
+            }
+        }
+    }
+
+    Ok(mode)
+}
+
+// Encodes the element string and returns the element widths of the
+// check character followed by the data characters
+fn expanded_characters(data: &str, segments: usize) -> Result<Vec<[u8; 8]>, String> {
+    let data: Vec<char> = data.chars().collect();
+    let mut bits = vec![];
+    let digits = |from: usize, to: usize| -> Option<Vec<u8>> {
+        let d = data.get(from..to)?;
+        d.iter()
+            .all(|c| c.is_ascii_digit())
+            .then(|| d.iter().map(|c| *c as u8).collect::<Vec<u8>>())
+    };
+
+    //(01) with a valid check digit uses the compressed GTIN method,
+    //everything else the general purpose method
+    let gtin = match digits(0, 16) {
+        Some(d) if d.starts_with(b"01") && check_digit(&d[2..15]) == d[15] => Some(d),
+        _ => None,
+    };
+
+    let (variable_length, rest) = match &gtin {
+        Some(gtin) => {
+            push_bits(&mut bits, 0b0100, 4);
+            push_bits(&mut bits, (gtin[2] - b'0') as u32, 4);
+
+            for group in gtin[3..15].chunks(3) {
+                let value = group.iter().fold(0, |v, d| v * 10 + (d - b'0') as u32);
+                push_bits(&mut bits, value, 10);
+            }
+
+            (2, &data[16..])
+        }
+        None => {
+            push_bits(&mut bits, 0, 5);
+            (3, &data[..])
+        }
+    };
+
+    let rest: Vec<char> = rest.iter().skip_while(|c| **c == FNC1).copied().collect();
+    let mode = encode_general(&rest, &mut bits)?;
+
+    let mut data_characters = bits.len().div_ceil(12).max(3);
+
+    //The last row of a stacked symbol needs at least two characters
+    if segments > 0 && (data_characters + 1) % segments == 1 {
+        data_characters += 1;
+    }
+
+    if data_characters + 1 > EXPANDED_MAX_CHARACTERS {
+        return Err("Too much data for GS1 DataBar Expanded".to_string());
+    }
+
+    //Variable length symbol field, odd number of characters and more than 14
+    let total = data_characters + 1;
+    bits[variable_length] = total % 2 == 1;
+    bits[variable_length + 1] = total > 14;
+
+    let mut padding = vec![];
+    if mode == Mode::Numeric && !rest.is_empty() {
+        push_bits(&mut padding, 0, 4);
+    }
+    while padding.len() < data_characters * 12 - bits.len() {
+        push_bits(&mut padding, 4, 5);
+    }
+    padding.truncate(data_characters * 12 - bits.len());
+    bits.extend(padding);
+
+    let mut characters = vec![[0u8; 8]];
+
+    for chunk in bits.chunks(12) {
+        let value = chunk.iter().fold(0, |v, b| v << 1 | *b as i32);
+        characters.push(expanded_character(value));
+    }
+
+    //Checksum weights depend on the finder next to each character
+    let sequence = EXPANDED_SEQUENCES[total.div_ceil(2) - 2];
+    let mut checksum = 0;
+
+    for (i, c) in characters.iter().enumerate().skip(1) {
+        let pair = i / 2;
+        let odd_pattern = if pair % 2 == 0 { 0 } else { 2 };
+        let right_character = i % 2;
+        let row = 4 * sequence[pair] + odd_pattern + right_character - 1;
+        let mut weight = (0..row * 8).fold(1, |w, _| w * 3 % 211);
+
+        for width in c {
+            checksum += *width as i32 * weight;
+            weight = weight * 3 % 211;
+        }
+    }
+
+    characters[0] = expanded_character(211 * (total as i32 - 4) + checksum % 211);
+
+    Ok(characters)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::barcodes::databar::*;
+
+    fn check_characters<F: Fn(i32) -> [u8; 8]>(count: i32, modules: u32, character: F) {
+        let mut seen = std::collections::HashSet::new();
+
+        for value in 0..count {
+            let widths = character(value);
+            let total: u32 = widths.iter().map(|w| *w as u32).sum();
+            assert_eq!(total, modules, "value {}", value);
+            assert!(widths.iter().all(|w| *w >= 1 && *w <= 9));
+            assert!(seen.insert(widths), "value {} is not unique", value);
+        }
+    }
+
+    #[test]
+    fn combination_algorithm() {
+        assert_eq!(combinations(5, 2), 10);
+        assert_eq!(combinations(11, 3), 165);
+        assert_eq!(combinations(3, 4), 0);
+        assert_eq!(rss_widths(0, 12, 4, 8, false), vec![1, 1, 2, 8]);
+    }
+
+    #[test]
+    fn characters_are_unique() {
+        check_characters(2841, 16, outside_character);
+        check_characters(1597, 15, inside_character);
+        check_characters(4096, 17, expanded_character);
+    }
+
+    #[test]
+    fn omnidirectional() {
+        let symbol = DataBar::omnidirectional(b"0001234567890").unwrap();
+        assert_eq!(symbol.width, 96);
+        assert_eq!(symbol.rows.len(), 1);
+        assert_eq!(&symbol.rows[0].modules[..2], &[0, 1]);
+        assert_eq!(symbol.rows[0].modules[95], 1);
+
+        assert!(DataBar::omnidirectional(b"123").is_err());
+        assert_eq!(gtin_hri(b"0001234567890"), "(01)00012345678905");
+    }
+
+    #[test]
+    fn stacked() {
+        let symbol = DataBar::stacked(b"0001234567890").unwrap();
+        assert_eq!(symbol.width, 50);
+        assert_eq!(symbol.points().len(), 50 * 13);
+
+        let symbol = DataBar::stacked_omnidirectional(b"0001234567890").unwrap();
+        assert_eq!(symbol.rows.len(), 5);
+        assert_eq!(symbol.points().len(), 50 * 69);
+    }
+
+    #[test]
+    fn general_purpose() {
+        let mut bits = vec![];
+        let data: Vec<char> = "12A".chars().collect();
+        encode_general(&data, &mut bits).unwrap();
+        let s: String = bits.iter().map(|b| if *b { '1' } else { '0' }).collect();
+        //12 as numeric, latch to alphanumeric, A
+        assert_eq!(s, "0010101".to_string() + "0000" + "100000");
+
+        let data: Vec<char> = format!("10A{}21", FNC1).chars().collect();
+        bits.clear();
+        assert_eq!(encode_general(&data, &mut bits).unwrap(), Mode::Numeric);
+    }
+
+    #[test]
+    fn expanded() {
+        let symbol = DataBar::expanded("0100012345678905", 0).unwrap();
+        //Check character and 4 data characters in 3 pairs
+        assert_eq!(symbol.rows.len(), 1);
+        assert_eq!(symbol.width, 4 + 49 * 2 + 17 + 15);
+
+        let symbol = DataBar::expanded("0100012345678905", 2).unwrap();
+        //3 rows with 3 separator rows between each
+        assert_eq!(symbol.rows.len(), 3 + 3 * 2);
+        assert_eq!(symbol.width, 4 + 49);
+
+        assert!(DataBar::expanded("01000123456789051", 3).is_err());
+        assert!(DataBar::expanded(&"9".repeat(120), 0).is_err());
+    }
+}
@@ -0,0 +1,454 @@
+use crate::utils::barcodes::reed_solomon::ReedSolomon;
+
+// Data Matrix ECC 200 symbol attributes
+struct SymbolSize {
+    rows: usize,
+    columns: usize,
+    region_rows: usize,
+    region_columns: usize,
+    regions_vertical: usize,
+    regions_horizontal: usize,
+    data_codewords: usize,
+    ecc_codewords: usize,
+    blocks: usize,
+}
+
+const fn size(
+    rows: usize,
+    columns: usize,
+    regions_vertical: usize,
+    regions_horizontal: usize,
+    data_codewords: usize,
+    ecc_codewords: usize,
+    blocks: usize,
+) -> SymbolSize {
+    SymbolSize {
+        rows,
+        columns,
+        region_rows: rows / regions_vertical - 2,
+        region_columns: columns / regions_horizontal - 2,
+        regions_vertical,
+        regions_horizontal,
+        data_codewords,
+        ecc_codewords,
+        blocks,
+    }
+}
+
+const SQUARE_SIZES: [SymbolSize; 24] = [
+    size(10, 10, 1, 1, 3, 5, 1),
+    size(12, 12, 1, 1, 5, 7, 1),
+    size(14, 14, 1, 1, 8, 10, 1),
+    size(16, 16, 1, 1, 12, 12, 1),
+    size(18, 18, 1, 1, 18, 14, 1),
+    size(20, 20, 1, 1, 22, 18, 1),
+    size(22, 22, 1, 1, 30, 20, 1),
+    size(24, 24, 1, 1, 36, 24, 1),
+    size(26, 26, 1, 1, 44, 28, 1),
+    size(32, 32, 2, 2, 62, 36, 1),
+    size(36, 36, 2, 2, 86, 42, 1),
+    size(40, 40, 2, 2, 114, 48, 1),
+    size(44, 44, 2, 2, 144, 56, 1),
+    size(48, 48, 2, 2, 174, 68, 1),
+    size(52, 52, 2, 2, 204, 84, 2),
+    size(64, 64, 4, 4, 280, 112, 2),
+    size(72, 72, 4, 4, 368, 144, 4),
+    size(80, 80, 4, 4, 456, 192, 4),
+    size(88, 88, 4, 4, 576, 224, 4),
+    size(96, 96, 4, 4, 696, 272, 4),
+    size(104, 104, 4, 4, 816, 336, 6),
+    size(120, 120, 6, 6, 1050, 408, 6),
+    size(132, 132, 6, 6, 1304, 496, 8),
+    size(144, 144, 6, 6, 1558, 620, 10),
+];
+
+const RECTANGLE_SIZES: [SymbolSize; 6] = [
+    size(8, 18, 1, 1, 5, 7, 1),
+    size(8, 32, 1, 2, 10, 11, 1),
+    size(12, 26, 1, 1, 16, 14, 1),
+    size(12, 36, 1, 2, 22, 18, 1),
+    size(16, 36, 1, 2, 32, 24, 1),
+    size(16, 48, 1, 2, 49, 28, 1),
+];
+
+const PAD: u8 = 129;
+const UPPER_SHIFT: u8 = 235;
+
+pub struct DataMatrix {
+    pub rows: usize,
+    pub columns: usize,
+    /// One byte per module, 1 is dark
+    pub points: Vec<u8>,
+}
+
+impl DataMatrix {
+    /// Creates a new symbol
+    /// rows and columns of 0 select the smallest symbol that fits the data
+    pub fn new(data: &[u8], rectangle: bool, rows: u8, columns: u8) -> Result<DataMatrix, String> {
+        let codewords = encode_ascii(data);
+        let sizes: &[SymbolSize] = if rectangle {
+            &RECTANGLE_SIZES
+        } else {
+            &SQUARE_SIZES
+        };
+
+        let symbol = sizes
+            .iter()
+            .filter(|s| rows == 0 || s.rows == rows as usize)
+            .filter(|s| columns == 0 || s.columns == columns as usize)
+            .find(|s| s.data_codewords >= codewords.len());
+
+        let symbol = match symbol {
+            Some(symbol) => symbol,
+            None => {
+                return Err(format!(
+                    "Invalid Data Matrix: {} codewords do not fit into the requested symbol size.",
+                    codewords.len()
+                ))
+            }
+        };
+
+        let codewords = add_error_correction(pad(codewords, symbol.data_codewords), symbol);
+        let mapping = place_modules(
+            &codewords,
+            symbol.region_rows * symbol.regions_vertical,
+            symbol.region_columns * symbol.regions_horizontal,
+        );
+
+        Ok(DataMatrix {
+            rows: symbol.rows,
+            columns: symbol.columns,
+            points: add_finder_patterns(&mapping, symbol),
+        })
+    }
+}
+
+// ASCII encodation, digit pairs are compacted into a single codeword
+fn encode_ascii(data: &[u8]) -> Vec<u8> {
+    let mut codewords = vec![];
+    let mut i = 0;
+
+    while i < data.len() {
+        let b = data[i];
+
+        if b.is_ascii_digit() && i + 1 < data.len() && data[i + 1].is_ascii_digit() {
+            codewords.push(130 + (b - b'0') * 10 + (data[i + 1] - b'0'));
+            i += 2;
+            continue;
+        }
+
+        if b > 127 {
+            codewords.push(UPPER_SHIFT);
+            codewords.push(b - 127);
+        } else {
+            codewords.push(b + 1);
+        }
+
+        i += 1;
+    }
+
+    codewords
+}
+
+// The first pad is 129, the following pads are randomized
+fn pad(mut codewords: Vec<u8>, capacity: usize) -> Vec<u8> {
+    if codewords.len() < capacity {
+        codewords.push(PAD);
+    }
+
+    while codewords.len() < capacity {
+        let position = codewords.len() + 1;
+        let mut value = PAD as usize + (149 * position) % 253 + 1;
+
+        if value > 254 {
+            value -= 254;
+        }
+
+        codewords.push(value as u8);
+    }
+
+    codewords
+}
+
+// Error correction is calculated per interleaved block
+fn add_error_correction(data: Vec<u8>, symbol: &SymbolSize) -> Vec<u8> {
+    let rs = ReedSolomon::new(8, 0x12D, 1);
+    let blocks = symbol.blocks;
+    let ecc_per_block = symbol.ecc_codewords / blocks;
+    let mut ecc = vec![0u8; symbol.ecc_codewords];
+
+    for block in 0..blocks {
+        let block_data: Vec<u16> = data
+            .iter()
+            .skip(block)
+            .step_by(blocks)
+            .map(|b| *b as u16)
+            .collect();
+
+        for (i, e) in rs.encode(&block_data, ecc_per_block).iter().enumerate() {
+            ecc[block + i * blocks] = *e as u8;
+        }
+    }
+
+    let mut codewords = data;
+    codewords.extend(ecc);
+    codewords
+}
+
+// The ECC 200 module placement algorithm, works on the
+// mapping matrix which excludes the finder patterns
+struct Placement<'a> {
+    rows: isize,
+    columns: isize,
+    codewords: &'a [u8],
+    modules: Vec<Option<bool>>,
+}
+
+impl Placement<'_> {
+    fn module(&mut self, mut row: isize, mut column: isize, position: usize, bit: u8) {
+        if row < 0 {
+            row += self.rows;
+            column += 4 - ((self.rows + 4) % 8);
+        }
+        if column < 0 {
+            column += self.columns;
+            row += 4 - ((self.columns + 4) % 8);
+        }
+
+        let codeword = *self.codewords.get(position).unwrap_or(&0);
+        let dark = (codeword >> (8 - bit)) & 1 == 1;
+        self.modules[(row * self.columns + column) as usize] = Some(dark);
+    }
+
+    fn is_empty(&self, row: isize, column: isize) -> bool {
+        self.modules[(row * self.columns + column) as usize].is_none()
+    }
+
+    fn place(&mut self, positions: [(isize, isize); 8], position: usize) {
+        for (i, (row, column)) in positions.iter().enumerate() {
+            self.module(*row, *column, position, i as u8 + 1);
+        }
+    }
+
+    fn utah(&mut self, r: isize, c: isize, position: usize) {
+        self.place(
+            [
+                (r - 2, c - 2),
+                (r - 2, c - 1),
+                (r - 1, c - 2),
+                (r - 1, c - 1),
+                (r - 1, c),
+                (r, c - 2),
+                (r, c - 1),
+                (r, c),
+            ],
+            position,
+        );
+    }
+
+    fn corner(&mut self, kind: u8, position: usize) {
+        let (r, c) = (self.rows, self.columns);
+
+        let positions = match kind {
+            1 => [
+                (r - 1, 0),
+                (r - 1, 1),
+                (r - 1, 2),
+                (0, c - 2),
+                (0, c - 1),
+                (1, c - 1),
+                (2, c - 1),
+                (3, c - 1),
+            ],
+            2 => [
+                (r - 3, 0),
+                (r - 2, 0),
+                (r - 1, 0),
+                (0, c - 4),
+                (0, c - 3),
+                (0, c - 2),
+                (0, c - 1),
+                (1, c - 1),
+            ],
+            3 => [
+                (r - 3, 0),
+                (r - 2, 0),
+                (r - 1, 0),
+                (0, c - 2),
+                (0, c - 1),
+                (1, c - 1),
+                (2, c - 1),
+                (3, c - 1),
+            ],
+            _ => [
+                (r - 1, 0),
+                (r - 1, c - 1),
+                (0, c - 3),
+                (0, c - 2),
+                (0, c - 1),
+                (1, c - 3),
+                (1, c - 2),
+                (1, c - 1),
+            ],
+        };
+
+        self.place(positions, position);
+    }
+}
+
+fn place_modules(codewords: &[u8], rows: usize, columns: usize) -> Vec<bool> {
+    let mut p = Placement {
+        rows: rows as isize,
+        columns: columns as isize,
+        codewords,
+        modules: vec![None; rows * columns],
+    };
+
+    let (nrow, ncol) = (p.rows, p.columns);
+    let mut position = 0;
+    let mut row: isize = 4;
+    let mut column: isize = 0;
+
+    loop {
+        if row == nrow && column == 0 {
+            p.corner(1, position);
+            position += 1;
+        }
+        if row == nrow - 2 && column == 0 && ncol % 4 != 0 {
+            p.corner(2, position);
+            position += 1;
+        }
+        if row == nrow - 2 && column == 0 && ncol % 8 == 4 {
+            p.corner(3, position);
+            position += 1;
+        }
+        if row == nrow + 4 && column == 2 && ncol % 8 == 0 {
+            p.corner(4, position);
+            position += 1;
+        }
+
+        //Sweep upward diagonally
+        loop {
+            if row < nrow && column >= 0 && p.is_empty(row, column) {
+                p.utah(row, column, position);
+                position += 1;
+            }
+            row -= 2;
+            column += 2;
+
+            if row < 0 || column >= ncol {
+                break;
+            }
+        }
+        row += 1;
+        column += 3;
+
+        //Sweep downward diagonally
+        loop {
+            if row >= 0 && column < ncol && p.is_empty(row, column) {
+                p.utah(row, column, position);
+                position += 1;
+            }
+            row += 2;
+            column -= 2;
+
+            if row >= nrow || column < 0 {
+                break;
+            }
+        }
+        row += 3;
+        column += 1;
+
+        if row >= nrow && column >= ncol {
+            break;
+        }
+    }
+
+    //Fixed pattern for the unfilled bottom right corner
+    if p.is_empty(nrow - 1, ncol - 1) {
+        let last = ((nrow - 1) * ncol + ncol - 1) as usize;
+        let before = ((nrow - 2) * ncol + ncol - 2) as usize;
+        p.modules[last] = Some(true);
+        p.modules[before] = Some(true);
+    }
+
+    p.modules.iter().map(|m| m.unwrap_or(false)).collect()
+}
+
+// Splits the mapping matrix into data regions and surrounds
+// each one with the solid and alternating finder patterns
+fn add_finder_patterns(mapping: &[bool], symbol: &SymbolSize) -> Vec<u8> {
+    let mapping_columns = symbol.region_columns * symbol.regions_horizontal;
+    let mut points = vec![0u8; symbol.rows * symbol.columns];
+
+    for y in 0..symbol.rows {
+        for x in 0..symbol.columns {
+            let region_y = y % (symbol.region_rows + 2);
+            let region_x = x % (symbol.region_columns + 2);
+
+            let dark = if region_x == 0 || region_y == symbol.region_rows + 1 {
+                true
+            } else if region_y == 0 {
+                region_x.is_multiple_of(2)
+            } else if region_x == symbol.region_columns + 1 {
+                region_y % 2 == 1
+            } else {
+                let my = (y / (symbol.region_rows + 2)) * symbol.region_rows + region_y - 1;
+                let mx = (x / (symbol.region_columns + 2)) * symbol.region_columns + region_x - 1;
+                mapping[my * mapping_columns + mx]
+            };
+
+            points[y * symbol.columns + x] = dark as u8;
+        }
+    }
+
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::barcodes::datamatrix::*;
+
+    #[test]
+    fn ascii_encodation() {
+        assert_eq!(encode_ascii(b"123456"), [142, 164, 186]);
+        assert_eq!(encode_ascii(b"A1"), [66, 50]);
+        assert_eq!(encode_ascii(&[200]), [235, 73]);
+    }
+
+    #[test]
+    fn padding() {
+        assert_eq!(pad(vec![66], 4), [66, 129, 70, 220]);
+    }
+
+    #[test]
+    fn error_correction() {
+        let codewords = add_error_correction(vec![142, 164, 186], &SQUARE_SIZES[0]);
+        assert_eq!(codewords, [142, 164, 186, 114, 25, 5, 88, 102]);
+    }
+
+    #[test]
+    fn symbol_size() {
+        let symbol = DataMatrix::new(b"123456", false, 0, 0).unwrap();
+        assert_eq!((symbol.rows, symbol.columns), (10, 10));
+
+        let symbol = DataMatrix::new(b"Hello World", true, 0, 0).unwrap();
+        assert_eq!((symbol.rows, symbol.columns), (12, 26));
+
+        assert!(DataMatrix::new(b"Hello World", false, 10, 10).is_err());
+    }
+
+    #[test]
+    fn finder_patterns() {
+        let symbol = DataMatrix::new(b"A", false, 0, 0).unwrap();
+
+        //Solid left and bottom edges
+        for y in 0..10 {
+            assert_eq!(symbol.points[y * 10], 1);
+        }
+        assert!(symbol.points[90..100].iter().all(|p| *p == 1));
+
+        //Alternating top edge
+        assert_eq!(&symbol.points[0..10], [1, 0, 1, 0, 1, 0, 1, 0, 1, 0]);
+    }
+}
@@ -0,0 +1,269 @@
+// GS1 element strings
+//
+// GS1 symbologies carry application identifiers (AI) followed by their
+// data. Variable length AIs have to be terminated with FNC1 when
+// another AI follows them, fixed length AIs don't.
+
+/// Stands in for FNC1 inside of an element string
+pub const FNC1: char = '\u{1D}';
+
+// First two digits of the predefined fixed length AIs and
+// the length of the AI and its data together
+const PREDEFINED_LENGTHS: [(&str, usize); 20] = [
+    ("00", 20),
+    ("01", 16),
+    ("02", 16),
+    ("03", 16),
+    ("04", 18),
+    ("11", 8),
+    ("12", 8),
+    ("13", 8),
+    ("14", 8),
+    ("15", 8),
+    ("16", 8),
+    ("17", 8),
+    ("18", 8),
+    ("19", 8),
+    ("20", 4),
+    ("31", 10),
+    ("32", 10),
+    ("33", 10),
+    ("34", 10),
+    ("41", 16),
+];
+
+// Length of the AI by its leading digits, the longest prefix wins
+const AI_LENGTHS: [(&str, usize); 14] = [
+    ("0", 2),
+    ("1", 2),
+    ("2", 2),
+    ("23", 3),
+    ("24", 3),
+    ("25", 3),
+    ("3", 4),
+    ("30", 2),
+    ("37", 2),
+    ("4", 3),
+    ("7", 4),
+    ("71", 3),
+    ("8", 4),
+    ("9", 2),
+];
+
+// AIs with fixed length data that are not predefined, so
+// the sender may or may not have terminated them with FNC1
+const FIXED_DATA_LENGTHS: [(&str, usize); 15] = [
+    ("394", 4),
+    ("395", 6),
+    ("402", 17),
+    ("422", 3),
+    ("424", 3),
+    ("426", 3),
+    ("7001", 13),
+    ("7003", 10),
+    ("7006", 6),
+    ("8001", 14),
+    ("8005", 6),
+    ("8006", 18),
+    ("8017", 18),
+    ("8018", 18),
+    ("8026", 18),
+];
+
+pub struct ElementString {
+    /// Data without brackets, with FNC1 after variable length AIs
+    pub data: String,
+    /// Human readable text with the AIs in brackets
+    pub hri: String,
+}
+
+impl ElementString {
+    /// Parses GS1 data like (01)00012345678905(10)ABC123
+    /// Data without brackets is split into AIs where possible,
+    /// {1 or the GS character can be used as FNC1
+    pub fn new(data: &str) -> Result<ElementString, String> {
+        if data.starts_with('(') {
+            return parse_bracketed(data);
+        }
+
+        let raw = data.replace("{1", &FNC1.to_string());
+        let parts: Vec<&str> = raw.split(FNC1).filter(|p| !p.is_empty()).collect();
+
+        if parts.is_empty() {
+            return Err("No GS1 data".to_string());
+        }
+
+        match parse_raw(&parts) {
+            Some(elements) => Ok(join(&elements)),
+            //Data that doesn't follow the AI rules is kept as is
+            None => Ok(ElementString {
+                data: parts.join(&FNC1.to_string()),
+                hri: parts.concat(),
+            }),
+        }
+    }
+}
+
+// Splits FNC1 separated data into AIs and their data
+fn parse_raw<'a>(parts: &[&'a str]) -> Option<Vec<(&'a str, &'a str)>> {
+    let mut elements = vec![];
+
+    for part in parts {
+        let mut rest = *part;
+
+        while !rest.is_empty() {
+            let ai_length = ai_length(rest)?;
+            let ai = rest.get(..ai_length)?;
+
+            if !ai.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+
+            let data_length = fixed_data_length(ai).unwrap_or(rest.len() - ai_length);
+            let value = rest.get(ai_length..ai_length + data_length)?;
+
+            if value.is_empty() {
+                return None;
+            }
+
+            elements.push((ai, value));
+            rest = &rest[ai_length + data_length..];
+        }
+    }
+
+    Some(elements)
+}
+
+fn ai_length(data: &str) -> Option<usize> {
+    AI_LENGTHS
+        .iter()
+        .filter(|(prefix, _)| data.starts_with(prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, length)| *length)
+}
+
+// Length of the data for fixed length AIs
+fn fixed_data_length(ai: &str) -> Option<usize> {
+    predefined_length(ai)
+        .map(|length| length - ai.len())
+        .or_else(|| {
+            FIXED_DATA_LENGTHS
+                .iter()
+                .filter(|(prefix, _)| ai.starts_with(prefix))
+                .max_by_key(|(prefix, _)| prefix.len())
+                .map(|(_, length)| *length)
+        })
+}
+
+fn parse_bracketed(data: &str) -> Result<ElementString, String> {
+    let mut elements: Vec<(&str, &str)> = vec![];
+    let mut rest = data;
+
+    while !rest.is_empty() {
+        let end = match (rest.starts_with('('), rest.find(')')) {
+            (true, Some(end)) => end,
+            _ => return Err(format!("Invalid GS1 application identifier in {}", data)),
+        };
+
+        let ai = &rest[1..end];
+
+        if ai.len() < 2 || ai.len() > 4 || !ai.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("Invalid GS1 application identifier ({})", ai));
+        }
+
+        rest = &rest[end + 1..];
+        let value_end = rest.find('(').unwrap_or(rest.len());
+        let value = &rest[..value_end];
+        rest = &rest[value_end..];
+
+        if value.is_empty() {
+            return Err(format!("No data for GS1 application identifier ({})", ai));
+        }
+
+        elements.push((ai, value));
+    }
+
+    Ok(join(&elements))
+}
+
+// Element string for a list of AIs and their data
+fn join(elements: &[(&str, &str)]) -> ElementString {
+    let mut element_string = ElementString {
+        data: String::new(),
+        hri: String::new(),
+    };
+
+    for (i, (ai, value)) in elements.iter().enumerate() {
+        element_string.data.push_str(ai);
+        element_string.data.push_str(value);
+        element_string.hri.push_str(&format!("({}){}", ai, value));
+
+        if i + 1 < elements.len() && predefined_length(ai).is_none() {
+            element_string.data.push(FNC1);
+        }
+    }
+
+    element_string
+}
+
+/// Total length of a predefined fixed length AI and its data
+pub fn predefined_length(ai: &str) -> Option<usize> {
+    PREDEFINED_LENGTHS
+        .iter()
+        .find(|(prefix, _)| ai.starts_with(prefix))
+        .map(|(_, length)| *length)
+}
+
+/// Mod 10 check digit for GTINs and other GS1 keys
+pub fn check_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, d)| (d - b'0') as u32 * if i % 2 == 0 { 3 } else { 1 })
+        .sum();
+
+    b'0' + ((10 - sum % 10) % 10) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::barcodes::gs1::{check_digit, ElementString, FNC1};
+
+    #[test]
+    fn check_digits() {
+        assert_eq!(check_digit(b"0001234567890"), b'5');
+        assert_eq!(check_digit(b"2001234567890"), b'9');
+        assert_eq!(check_digit(b"629104150021"), b'3');
+    }
+
+    #[test]
+    fn bracketed() {
+        let es = ElementString::new("(01)00012345678905(10)ABC(3103)001750").unwrap();
+        assert_eq!(es.data, format!("010001234567890510ABC{}3103001750", FNC1));
+        assert_eq!(es.hri, "(01)00012345678905(10)ABC(3103)001750");
+
+        assert!(ElementString::new("(01").is_err());
+        assert!(ElementString::new("(1)123").is_err());
+        assert!(ElementString::new("(10)").is_err());
+    }
+
+    #[test]
+    fn raw() {
+        let es = ElementString::new("{110ABC{121XYZ").unwrap();
+        assert_eq!(es.data, format!("10ABC{}21XYZ", FNC1));
+        assert_eq!(es.hri, "(10)ABC(21)XYZ");
+
+        let es = ElementString::new("0100012345678905172012311012345").unwrap();
+        assert_eq!(es.data, "0100012345678905172012311012345");
+        assert_eq!(es.hri, "(01)00012345678905(17)201231(10)12345");
+
+        let es = ElementString::new("3103001750{14001234").unwrap();
+        assert_eq!(es.hri, "(3103)001750(400)1234");
+
+        //Not valid AIs, kept as is
+        let es = ElementString::new("01123").unwrap();
+        assert_eq!(es.data, "01123");
+        assert_eq!(es.hri, "01123");
+    }
+}
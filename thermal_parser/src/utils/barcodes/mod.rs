@@ -1 +1,8 @@
-pub mod upce;
\ No newline at end of file
+pub mod aztec;
+pub mod code128;
+pub mod databar;
+pub mod datamatrix;
+pub mod gs1;
+pub mod pdf417;
+pub mod reed_solomon;
+pub mod upce;
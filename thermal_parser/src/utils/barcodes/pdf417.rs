@@ -0,0 +1,577 @@
+// PDF417 codeword generation
+//
+// This covers everything up to the bar/space patterns: high level
+// compaction (text, byte and numeric), the GF(929) error correction
+// codewords, the row/column layout and the row indicator codewords.
+
+const MODULO: u32 = 929;
+const MAX_CODEWORDS: usize = 928;
+const MIN_ROWS: usize = 3;
+const MAX_ROWS: usize = 90;
+const MIN_COLUMNS: usize = 1;
+const MAX_COLUMNS: usize = 30;
+
+const LATCH_TO_TEXT: u16 = 900;
+const LATCH_TO_BYTE_PADDED: u16 = 901;
+const LATCH_TO_NUMERIC: u16 = 902;
+const SHIFT_TO_BYTE: u16 = 913;
+const LATCH_TO_BYTE: u16 = 924;
+const PAD: u16 = 900;
+
+// Text compaction sub modes
+#[derive(Clone, Copy, PartialEq)]
+enum SubMode {
+    Alpha,
+    Lower,
+    Mixed,
+    Punctuation,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Compaction {
+    Text,
+    Byte,
+    Numeric,
+}
+
+// Mixed sub mode, index 25 is the punctuation latch (pl)
+const MIXED: [u8; 27] = [
+    b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', b'&', b'\r', b'\t', b',', b':',
+    b'#', b'-', b'.', b'$', b'/', b'+', b'%', b'*', b'=', b'^', 0, b' ',
+];
+
+// Punctuation sub mode, index 29 is the alpha latch (al)
+const PUNCTUATION: [u8; 29] = [
+    b';', b'<', b'>', b'@', b'[', b'\\', b']', b'_', b'`', b'~', b'!', b'\r', b'\t', b',', b':',
+    b'\n', b'-', b'.', b'$', b'/', b'"', b'|', b'*', b'(', b')', b'?', b'{', b'}', b'\'',
+];
+
+pub struct PDF417 {
+    pub columns: usize,
+    pub rows: usize,
+    pub error_correction_level: u8,
+    /// Data, padding and error correction codewords in symbol order
+    pub codewords: Vec<u16>,
+}
+
+impl PDF417 {
+    /// Creates a new symbol
+    /// columns and rows of 0 are calculated automatically
+    /// error_correction_level is 0 - 8, None uses the ratio
+    /// (n x 10% of the data codewords) used by the printers
+    /// module_ratio is the row height divided by the module width
+    pub fn new(
+        data: &[u8],
+        columns: u8,
+        rows: u8,
+        error_correction_level: Option<u8>,
+        error_correction_ratio: u8,
+        module_ratio: u8,
+    ) -> Result<PDF417, String> {
+        if data.is_empty() {
+            return Err(String::from("Invalid PDF417: No data to encode."));
+        }
+
+        let mut codewords = encode_high_level(data);
+
+        //The symbol length descriptor is added to the data
+        let data_count = codewords.len() + 1;
+
+        let level = match error_correction_level {
+            Some(level) => level.min(8),
+            None => level_for_ratio(data_count, error_correction_ratio),
+        };
+
+        let ecc_count = 2usize << level;
+
+        if data_count + ecc_count > MAX_CODEWORDS {
+            return Err(format!(
+                "Invalid PDF417: {} data codewords with error correction level {} exceeds the symbol capacity.",
+                data_count, level
+            ));
+        }
+
+        let (columns, rows) = determine_dimensions(
+            data_count + ecc_count,
+            columns as usize,
+            rows as usize,
+            module_ratio.max(1) as usize,
+        )?;
+
+        //Pad out the remaining space and prepend the length descriptor
+        let total_data = columns * rows - ecc_count;
+        codewords.resize(total_data - 1, PAD);
+        codewords.insert(0, total_data as u16);
+
+        let ecc = error_correction_codewords(&codewords, level);
+        codewords.extend(ecc);
+
+        Ok(PDF417 {
+            columns,
+            rows,
+            error_correction_level: level,
+            codewords,
+        })
+    }
+
+    /// Left and right row indicator codewords for a row
+    pub fn row_indicators(&self, row: usize) -> (u16, u16) {
+        let base = (30 * (row / 3)) as u16;
+        let rows = (self.rows - 1) as u16;
+        let columns = (self.columns - 1) as u16;
+        let level = self.error_correction_level as u16 * 3;
+
+        match row % 3 {
+            0 => (base + rows / 3, base + columns),
+            1 => (base + level + rows % 3, base + rows / 3),
+            _ => (base + columns, base + level + rows % 3),
+        }
+    }
+
+    /// Codewords for a single row including the row indicators
+    pub fn row_codewords(&self, row: usize) -> Vec<u16> {
+        let (left, right) = self.row_indicators(row);
+        let start = row * self.columns;
+
+        let mut row_codewords = Vec::with_capacity(self.columns + 2);
+        row_codewords.push(left);
+        row_codewords.extend_from_slice(&self.codewords[start..start + self.columns]);
+        row_codewords.push(right);
+        row_codewords
+    }
+}
+
+// Error correction level selection when the ratio mode is used
+fn level_for_ratio(data_count: usize, ratio: u8) -> u8 {
+    let codewords = data_count * ratio as usize / 10;
+
+    match codewords {
+        0..=3 => 1,
+        4..=10 => 2,
+        11..=20 => 3,
+        21..=45 => 4,
+        46..=100 => 5,
+        101..=200 => 6,
+        201..=400 => 7,
+        _ => 8,
+    }
+}
+
+// Finds a columns x rows layout that fits the codewords.
+// When neither is provided, the layout that is closest to
+// a 3:1 width to height ratio is used.
+fn determine_dimensions(
+    codeword_count: usize,
+    columns: usize,
+    rows: usize,
+    module_ratio: usize,
+) -> Result<(usize, usize), String> {
+    let rows_for = |c: usize| codeword_count.div_ceil(c).max(MIN_ROWS);
+    let columns_for = |r: usize| codeword_count.div_ceil(r).max(MIN_COLUMNS);
+
+    let (columns, rows) = match (columns, rows) {
+        (0, 0) => {
+            let mut best = None;
+            let mut best_ratio = f32::MAX;
+
+            for c in MIN_COLUMNS..=MAX_COLUMNS {
+                let r = rows_for(c);
+
+                if r > MAX_ROWS {
+                    continue;
+                }
+
+                let width = (17 * c + 69) as f32;
+                let height = (r * module_ratio) as f32;
+                let ratio = (width / height - 3.0).abs();
+
+                if ratio < best_ratio {
+                    best_ratio = ratio;
+                    best = Some((c, r));
+                }
+            }
+
+            match best {
+                Some(dimensions) => dimensions,
+                None => (MAX_COLUMNS, rows_for(MAX_COLUMNS)),
+            }
+        }
+        (0, r) => (columns_for(r.max(MIN_ROWS)), r.max(MIN_ROWS)),
+        (c, 0) => (c, rows_for(c)),
+        (c, r) => (c, r.max(MIN_ROWS)),
+    };
+
+    if !(MIN_COLUMNS..=MAX_COLUMNS).contains(&columns)
+        || rows > MAX_ROWS
+        || columns * rows < codeword_count
+        || columns * rows > MAX_CODEWORDS
+    {
+        return Err(format!(
+            "Invalid PDF417: {} codewords do not fit into {} columns and {} rows.",
+            codeword_count, columns, rows
+        ));
+    }
+
+    Ok((columns, rows))
+}
+
+fn is_digit(b: u8) -> bool {
+    b.is_ascii_digit()
+}
+
+fn is_text(b: u8) -> bool {
+    b == b'\t' || b == b'\n' || b == b'\r' || (32..=126).contains(&b)
+}
+
+fn is_alpha_upper(b: u8) -> bool {
+    b == b' ' || b.is_ascii_uppercase()
+}
+
+fn is_alpha_lower(b: u8) -> bool {
+    b == b' ' || b.is_ascii_lowercase()
+}
+
+fn mixed_index(b: u8) -> Option<u8> {
+    if b == 0 {
+        return None;
+    }
+    MIXED.iter().position(|m| *m == b).map(|p| p as u8)
+}
+
+fn punctuation_index(b: u8) -> Option<u8> {
+    PUNCTUATION.iter().position(|p| *p == b).map(|p| p as u8)
+}
+
+fn consecutive_digits(data: &[u8], start: usize) -> usize {
+    data[start..].iter().take_while(|b| is_digit(**b)).count()
+}
+
+// Number of text encodable bytes, stopping at any run of 13+ digits
+fn consecutive_text(data: &[u8], start: usize) -> usize {
+    let mut idx = start;
+
+    while idx < data.len() {
+        let digits = consecutive_digits(data, idx);
+
+        if digits >= 13 {
+            return idx - start;
+        }
+
+        if digits > 0 {
+            idx += digits;
+            continue;
+        }
+
+        if !is_text(data[idx]) {
+            break;
+        }
+
+        idx += 1;
+    }
+
+    idx - start
+}
+
+// Number of bytes that are better off in byte compaction
+fn consecutive_binary(data: &[u8], start: usize) -> usize {
+    let mut idx = start;
+
+    while idx < data.len() {
+        if consecutive_digits(data, idx) >= 13 {
+            break;
+        }
+
+        let text = data[idx..]
+            .iter()
+            .take(5)
+            .take_while(|b| is_text(**b))
+            .count();
+
+        if text >= 5 {
+            break;
+        }
+
+        idx += 1;
+    }
+
+    idx - start
+}
+
+fn encode_high_level(data: &[u8]) -> Vec<u16> {
+    let mut codewords = vec![];
+    let mut compaction = Compaction::Text;
+    let mut sub_mode = SubMode::Alpha;
+    let mut position = 0;
+
+    while position < data.len() {
+        let digits = consecutive_digits(data, position);
+
+        if digits >= 13 {
+            codewords.push(LATCH_TO_NUMERIC);
+            compaction = Compaction::Numeric;
+            sub_mode = SubMode::Alpha;
+            encode_numeric(&data[position..position + digits], &mut codewords);
+            position += digits;
+            continue;
+        }
+
+        let text = consecutive_text(data, position);
+
+        if text >= 5 || digits == data.len() {
+            if compaction != Compaction::Text {
+                codewords.push(LATCH_TO_TEXT);
+                compaction = Compaction::Text;
+                sub_mode = SubMode::Alpha;
+            }
+            sub_mode = encode_text(&data[position..position + text], sub_mode, &mut codewords);
+            position += text;
+            continue;
+        }
+
+        let bytes = consecutive_binary(data, position).max(1);
+
+        if bytes == 1 && compaction == Compaction::Text {
+            codewords.push(SHIFT_TO_BYTE);
+            codewords.push(data[position] as u16);
+        } else {
+            encode_bytes(&data[position..position + bytes], &mut codewords);
+            compaction = Compaction::Byte;
+            sub_mode = SubMode::Alpha;
+        }
+
+        position += bytes;
+    }
+
+    codewords
+}
+
+fn encode_text(data: &[u8], initial: SubMode, codewords: &mut Vec<u16>) -> SubMode {
+    let mut values: Vec<u8> = vec![];
+    let mut sub_mode = initial;
+    let mut idx = 0;
+
+    while idx < data.len() {
+        let b = data[idx];
+
+        match sub_mode {
+            SubMode::Alpha => {
+                if is_alpha_upper(b) {
+                    values.push(if b == b' ' { 26 } else { b - b'A' });
+                } else if is_alpha_lower(b) {
+                    sub_mode = SubMode::Lower;
+                    values.push(27);
+                    continue;
+                } else if mixed_index(b).is_some() {
+                    sub_mode = SubMode::Mixed;
+                    values.push(28);
+                    continue;
+                } else {
+                    values.push(29);
+                    values.push(punctuation_index(b).unwrap_or(0));
+                }
+            }
+            SubMode::Lower => {
+                if is_alpha_lower(b) {
+                    values.push(if b == b' ' { 26 } else { b - b'a' });
+                } else if is_alpha_upper(b) {
+                    //Alpha shift
+                    values.push(27);
+                    values.push(b - b'A');
+                } else if mixed_index(b).is_some() {
+                    sub_mode = SubMode::Mixed;
+                    values.push(28);
+                    continue;
+                } else {
+                    values.push(29);
+                    values.push(punctuation_index(b).unwrap_or(0));
+                }
+            }
+            SubMode::Mixed => {
+                if let Some(index) = mixed_index(b) {
+                    values.push(index);
+                } else if is_alpha_upper(b) {
+                    sub_mode = SubMode::Alpha;
+                    values.push(28);
+                    continue;
+                } else if is_alpha_lower(b) {
+                    sub_mode = SubMode::Lower;
+                    values.push(27);
+                    continue;
+                } else if idx + 1 < data.len() && punctuation_index(data[idx + 1]).is_some() {
+                    sub_mode = SubMode::Punctuation;
+                    values.push(25);
+                    continue;
+                } else {
+                    values.push(29);
+                    values.push(punctuation_index(b).unwrap_or(0));
+                }
+            }
+            SubMode::Punctuation => {
+                if let Some(index) = punctuation_index(b) {
+                    values.push(index);
+                } else {
+                    sub_mode = SubMode::Alpha;
+                    values.push(29);
+                    continue;
+                }
+            }
+        }
+
+        idx += 1;
+    }
+
+    //Two text values per codeword, padded with a punctuation shift
+    for pair in values.chunks(2) {
+        let high = pair[0] as u16;
+        let low = *pair.get(1).unwrap_or(&29) as u16;
+        codewords.push(high * 30 + low);
+    }
+
+    sub_mode
+}
+
+fn encode_bytes(data: &[u8], codewords: &mut Vec<u16>) {
+    codewords.push(if data.len().is_multiple_of(6) {
+        LATCH_TO_BYTE
+    } else {
+        LATCH_TO_BYTE_PADDED
+    });
+
+    let mut chunks = data.chunks_exact(6);
+
+    //Six bytes are stored as five base 900 codewords
+    for chunk in chunks.by_ref() {
+        let mut value = chunk.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64);
+        let mut group = [0u16; 5];
+
+        for cw in group.iter_mut().rev() {
+            *cw = (value % 900) as u16;
+            value /= 900;
+        }
+
+        codewords.extend_from_slice(&group);
+    }
+
+    for b in chunks.remainder() {
+        codewords.push(*b as u16);
+    }
+}
+
+fn encode_numeric(digits: &[u8], codewords: &mut Vec<u16>) {
+    //Groups of up to 44 digits, each with a leading 1
+    for group in digits.chunks(44) {
+        let mut decimal: Vec<u32> = vec![1];
+        decimal.extend(group.iter().map(|d| (*d - b'0') as u32));
+
+        let mut base_900 = vec![];
+
+        while !decimal.is_empty() {
+            let mut remainder = 0u32;
+            let mut quotient = Vec::with_capacity(decimal.len());
+
+            for d in decimal {
+                let value = remainder * 10 + d;
+                let q = value / 900;
+                remainder = value % 900;
+
+                if !quotient.is_empty() || q > 0 {
+                    quotient.push(q);
+                }
+            }
+
+            base_900.push(remainder as u16);
+            decimal = quotient;
+        }
+
+        codewords.extend(base_900.iter().rev());
+    }
+}
+
+// Coefficients of the generator polynomial (x - 3)(x - 3^2)...(x - 3^k)
+// lowest order first, the leading 1 is omitted
+fn generator_coefficients(k: usize) -> Vec<u32> {
+    let mut generator = vec![1u32];
+    let mut root = 1u32;
+
+    for _ in 0..k {
+        root = root * 3 % MODULO;
+        let mut next = vec![0u32; generator.len() + 1];
+
+        for (i, c) in generator.iter().enumerate() {
+            next[i + 1] = (next[i + 1] + c) % MODULO;
+            next[i] = (next[i] + MODULO - c * root % MODULO) % MODULO;
+        }
+
+        generator = next;
+    }
+
+    generator.pop();
+    generator
+}
+
+fn error_correction_codewords(data: &[u16], level: u8) -> Vec<u16> {
+    let k = 2usize << level;
+    let coefficients = generator_coefficients(k);
+    let mut e = vec![0u32; k];
+
+    for d in data {
+        let t1 = (*d as u32 + e[k - 1]) % MODULO;
+
+        for j in (1..k).rev() {
+            e[j] = (e[j - 1] + MODULO - t1 * coefficients[j] % MODULO) % MODULO;
+        }
+
+        e[0] = (MODULO - t1 * coefficients[0] % MODULO) % MODULO;
+    }
+
+    e.iter()
+        .rev()
+        .map(|v| ((MODULO - v) % MODULO) as u16)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::barcodes::pdf417::*;
+
+    #[test]
+    fn text_compaction() {
+        assert_eq!(encode_high_level(b"PDF417"), [453, 178, 121, 239]);
+    }
+
+    #[test]
+    fn numeric_compaction() {
+        let mut codewords = vec![];
+        encode_numeric(b"000213298174000", &mut codewords);
+        assert_eq!(codewords, [1, 624, 434, 632, 282, 200]);
+    }
+
+    #[test]
+    fn byte_compaction() {
+        let mut codewords = vec![];
+        encode_bytes(&[0, 0, 0, 0, 0, 1, 255], &mut codewords);
+        assert_eq!(codewords, [901, 0, 0, 0, 0, 1, 255]);
+    }
+
+    #[test]
+    fn generator() {
+        assert_eq!(generator_coefficients(2), [27, 917]);
+        assert_eq!(generator_coefficients(4), [522, 568, 723, 809]);
+    }
+
+    #[test]
+    fn error_correction() {
+        let ecc = error_correction_codewords(&[5, 453, 178, 121, 239], 1);
+        assert_eq!(ecc, [452, 327, 657, 619]);
+    }
+
+    #[test]
+    fn dimensions() {
+        let symbol = PDF417::new(b"PDF417", 2, 0, Some(1), 0, 3).unwrap();
+        assert_eq!(symbol.columns, 2);
+        assert_eq!(symbol.rows, 5);
+        assert_eq!(symbol.codewords.len(), 10);
+        assert_eq!(symbol.codewords[0], 6);
+        assert_eq!(symbol.row_codewords(0), [1, 6, 453, 1]);
+    }
+}
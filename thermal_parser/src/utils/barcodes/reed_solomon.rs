@@ -0,0 +1,90 @@
+// Reed-Solomon error correction over GF(2^m)
+//
+// Shared by the 2D symbologies that use a binary galois field
+// (Data Matrix, Aztec, MaxiCode). Each of them only differs in
+// the field size, the primitive polynomial and the first root
+// of the generator polynomial.
+
+pub struct ReedSolomon {
+    exp: Vec<u16>,
+    log: Vec<u16>,
+    size: usize,
+    first_root: usize,
+}
+
+impl ReedSolomon {
+    /// Creates a new encoder for GF(2^bits) using the given
+    /// primitive polynomial, ex 0x12D for Data Matrix
+    pub fn new(bits: u32, primitive: u32, first_root: usize) -> ReedSolomon {
+        let size = 1usize << bits;
+        let mut exp = vec![0u16; size];
+        let mut log = vec![0u16; size];
+        let mut x = 1u32;
+
+        for (i, e) in exp.iter_mut().enumerate().take(size - 1) {
+            *e = x as u16;
+            log[x as usize] = i as u16;
+            x <<= 1;
+
+            if x as usize >= size {
+                x ^= primitive;
+            }
+        }
+
+        ReedSolomon {
+            exp,
+            log,
+            size,
+            first_root,
+        }
+    }
+
+    fn multiply(&self, a: u16, b: u16) -> u16 {
+        if a == 0 || b == 0 {
+            return 0;
+        }
+        let l = (self.log[a as usize] as usize + self.log[b as usize] as usize) % (self.size - 1);
+        self.exp[l]
+    }
+
+    // Generator polynomial coefficients, highest order first
+    // without the leading 1
+    fn generator(&self, ecc_len: usize) -> Vec<u16> {
+        let mut generator = vec![1u16];
+
+        for i in 0..ecc_len {
+            let root = self.exp[(self.first_root + i) % (self.size - 1)];
+            let mut next = vec![0u16; generator.len() + 1];
+
+            for (j, c) in generator.iter().enumerate() {
+                next[j] ^= c;
+                next[j + 1] ^= self.multiply(*c, root);
+            }
+
+            generator = next;
+        }
+
+        generator.remove(0);
+        generator
+    }
+
+    /// Calculates the error correction codewords for the data
+    pub fn encode(&self, data: &[u16], ecc_len: usize) -> Vec<u16> {
+        let generator = self.generator(ecc_len);
+        let mut ecc = vec![0u16; ecc_len];
+
+        for d in data {
+            let feedback = d ^ ecc[0];
+            ecc.remove(0);
+            ecc.push(0);
+
+            if feedback != 0 {
+                for (e, g) in ecc.iter_mut().zip(generator.iter()) {
+                    *e ^= self.multiply(feedback, *g);
+                }
+            }
+        }
+
+        ecc
+    }
+}
@@ -253,6 +253,11 @@ impl UPCE {
         ))
     }
 
+    /// The number system, six digits and check digit as printed under the symbol
+    pub fn hri(&self) -> String {
+        self.0.iter().map(|d| (b'0' + d) as char).collect()
+    }
+
     fn get_parity_encoding(&self) -> [usize; 6] {
         // Uses the UPC-A check digit in the parity table
         UPCE_PARITY_TABLE[self.0[0] as usize][self.0[7] as usize]
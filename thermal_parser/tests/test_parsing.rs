@@ -1,6 +1,17 @@
 use std::path::PathBuf;
+use thermal_parser::builder::EscPosBuilder;
+use thermal_parser::command::{Command, DeviceCommand};
+use thermal_parser::command_sets::trie::{CommandTrie, TrieMatch};
+use thermal_parser::command_sets::{epl, esc_pos, star_graphic, star_line, zpl};
+use thermal_parser::commands::barcode::BarcodeType;
+use thermal_parser::decoder::{get_multi_byte_encoding, MultiByteEncoding};
+use thermal_parser::diagnostic::{DiagnosticCode, Severity};
+use thermal_parser::dialect::{detect_dialect, Dialect};
+use thermal_parser::graphics::GraphicsCommand;
+use thermal_parser::parser::Parser;
+use thermal_parser::text::{TextSpan, UserCharacters};
 use thermal_parser::thermal_file::parse_str;
-use thermal_parser::{context::*, parse_esc_pos};
+use thermal_parser::{context::*, parse_auto, parse_esc_pos, parse_esc_pos_with_diagnostics};
 
 #[test]
 fn code_pages() {
@@ -62,21 +73,1053 @@ fn discount() {
     test_sample("discount", "thermal")
 }
 
+#[test]
+fn diagnostics() {
+    //ESC E without its parameter
+    let (_, diagnostics) = parse_esc_pos_with_diagnostics(&b"AB\x1bE".to_vec());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].byte_offset, 2);
+    assert_eq!(diagnostics[0].command_name, "Enable Emphasis");
+    assert_eq!(diagnostics[0].code, DiagnosticCode::TruncatedCommand);
+
+    //GS ( k that ends 3 bytes short
+    let (_, diagnostics) = parse_esc_pos_with_diagnostics(&b"A\x1d(k\x08\x0031P0ABC".to_vec());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].byte_offset, 1);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(diagnostics[0].code, DiagnosticCode::TruncatedCommand);
+
+    //GS ( L with an unknown function
+    let (_, diagnostics) = parse_esc_pos_with_diagnostics(&b"\x1d(L\x02\x000\x7f".to_vec());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert_eq!(diagnostics[0].code, DiagnosticCode::MissingSubcommand);
+
+    //GS ( L define NV graphics without the image parameters
+    let (_, diagnostics) = parse_esc_pos_with_diagnostics(&b"\x1d(L\x04\x000C0 \n".to_vec());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].byte_offset, 0);
+    assert_eq!(diagnostics[0].code, DiagnosticCode::MissingParameters);
+
+    //GS ( L size that can't hold m and fn
+    let (_, diagnostics) = parse_esc_pos_with_diagnostics(&b"\x1d(L\x01\x000\x02".to_vec());
+    assert_eq!(diagnostics[0].code, DiagnosticCode::InvalidSize);
+
+    //ESC * with an unknown mode
+    let (_, diagnostics) = parse_esc_pos_with_diagnostics(&b"\x1b*\x05\x01\x00\xff".to_vec());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, DiagnosticCode::InvalidParameter);
+}
+
+#[test]
+fn subcommand_at_end_of_input() {
+    //GS ( L print buffer graphics is the last command
+    let commands = parse_esc_pos(&b"A\x1d(L\x02\x0002".to_vec());
+    assert!(commands
+        .iter()
+        .any(|command| command.name.as_str() == "Print Buffer Graphics"));
+}
+
+#[test]
+fn command_offsets() {
+    //Text, ESC E 1, GS ( k QR store (expanded to the subcommand) and more text
+    let bytes = b"AB\x1bE1\x1d(k\x06\x001P0abcCD".to_vec();
+    let commands = parse_esc_pos(&bytes);
+    let ranges: Vec<(&str, usize, usize)> = commands
+        .iter()
+        .map(|command| (command.name.as_str(), command.start, command.end))
+        .collect();
+
+    assert_eq!(
+        ranges,
+        vec![
+            ("Begin Print", 0, 0),
+            //Emitted before the first text, it doesn't cover any bytes
+            ("Unknown Command", 0, 0),
+            ("Text", 0, 2),
+            ("Enable Emphasis", 2, 5),
+            ("QR Store the Code2D data", 5, 16),
+            ("Text", 16, 18),
+            ("End Print", 18, 18),
+        ]
+    );
+
+    //Unknown commands collect the bytes up to the next match
+    let commands = parse_esc_pos(&b"A\x1b\x00B\x1bE1".to_vec());
+    assert_eq!(commands[3].name.as_str(), "Unknown Command");
+    assert_eq!((commands[3].start, commands[3].end), (1, 4));
+    assert_eq!((commands[4].start, commands[4].end), (4, 7));
+}
+
+#[test]
+fn streaming() {
+    let mut parser = Parser::new(esc_pos::new());
+
+    //Begin parsing and the text, ESC E is complete with its last byte
+    let names = |commands: Vec<Command>| -> Vec<String> {
+        commands.iter().map(|c| c.name.to_string()).collect()
+    };
+    let commands: Vec<Command> = parser.feed(b"AB\x1bE").collect();
+    assert_eq!(
+        names(commands),
+        vec!["Begin Print", "Unknown Command", "Text"]
+    );
+    let commands: Vec<Command> = parser.feed(b"1").collect();
+    assert_eq!(names(commands), vec!["Enable Emphasis"]);
+
+    //A subcommand split across chunks keeps its size
+    assert_eq!(parser.feed(b"\x1d(k\x06\x00").count(), 0);
+    assert_eq!(parser.feed(b"1P0a").count(), 0);
+    let commands: Vec<Command> = parser.feed(b"bc").collect();
+    assert_eq!(commands[0].name.as_str(), "QR Store the Code2D data");
+    assert_eq!(commands[0].data, b"0abc");
+    assert_eq!((commands[0].start, commands[0].end), (5, 16));
+
+    //Text is only done when the job is
+    assert_eq!(parser.feed(b"CD").count(), 0);
+    let commands: Vec<Command> = parser.finish().collect();
+    assert_eq!(names(commands), vec!["Text", "End Print"]);
+
+    //The parser starts over after finish
+    let commands: Vec<Command> = parser.feed(b"\x1b@").collect();
+    assert_eq!((commands[1].start, commands[1].end), (0, 2));
+}
+
+#[test]
+fn command_trie() {
+    let command_set = esc_pos::new();
+    let trie = CommandTrie::new(&command_set.commands);
+
+    //ESC E needs both bytes
+    let node = match trie.step(trie.root(), 0x1b) {
+        TrieMatch::Partial(node) => node,
+        _ => panic!("ESC should be a partial match"),
+    };
+    match trie.step(node, b'E') {
+        TrieMatch::Command(index) => {
+            assert_eq!(command_set.commands[index].name.as_str(), "Enable Emphasis")
+        }
+        _ => panic!("ESC E should match"),
+    }
+
+    assert!(matches!(trie.step(node, 0x00), TrieMatch::None));
+    assert!(matches!(trie.step(trie.root(), b'A'), TrieMatch::None));
+}
+
+#[test]
+fn builder_round_trip() {
+    let bytes = EscPosBuilder::new()
+        .initialize()
+        .justify(TextJustify::Center)
+        .bold(true)
+        .size(2, 2)
+        .line("TOTAL \x1b12.00")
+        .bold(false)
+        .font(Font::B)
+        .text("Crème brûlée\nÇa")
+        .line_feed()
+        .barcode_hri(HumanReadableInterface::Below)
+        .barcode(BarcodeType::Code128, "{B12345")
+        .qr_model(QrModel::Model2)
+        .qr_size(4)
+        .qr_error_correction(QrErrorCorrection::M)
+        .qr("https://example.com")
+        .raster_image(12, 2, &[0xFF, 0xF0, 0x0F])
+        .feed_lines(3)
+        .cut()
+        .build();
+
+    let (commands, diagnostics) = parse_esc_pos_with_diagnostics(&bytes);
+    assert!(diagnostics.is_empty());
+
+    let names: Vec<&str> = commands
+        .iter()
+        .map(|command| command.name.as_str())
+        .collect();
+
+    assert_eq!(
+        names,
+        vec![
+            "Begin Print",
+            "Initialize",
+            "Set Text Justification",
+            "Enable Emphasis",
+            "Set Character Size",
+            "Text",
+            "Line Feed",
+            "Enable Emphasis",
+            "Set Font",
+            "Text",
+            "Line Feed",
+            "Text",
+            "Line Feed",
+            "Set Hri Print POS",
+            "Barcode",
+            "QR Sets the Model",
+            "QR Sets the dot count",
+            "QR Sets Error Correction Level",
+            "QR Store the Code2D data",
+            "Print the Code2D data",
+            "Raster Bit Image",
+            "Print and Feed Lines",
+            "Feed and Cut",
+            "End Print",
+        ]
+    );
+
+    //Control characters are left out and text uses the codepage
+    let context = Context::new();
+    let text = |index: usize| commands[index].handler.get_text(&commands[index], &context);
+    assert_eq!(text(5).unwrap().text, "TOTAL 12.00");
+    assert_eq!(text(9).unwrap().text, "Crème brûlée");
+    assert_eq!(text(11).unwrap().text, "Ça");
+    assert_eq!(commands[14].data, b"{B12345");
+}
+
+#[test]
+fn margin_commands() {
+    let bytes = b"\x1dL\x60\x00\x1dW\x2c\x01\x1b$\x0a\x00\
+                  \x1dL\x5e\x01\x1dW\xff\xff\x1dL\x00\x00\x1dW\x01\x00"
+        .to_vec();
+
+    let mut parser = Parser::new(esc_pos::new());
+    let commands = parser.parse_bytes(&bytes);
+    let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+
+    assert_eq!(
+        names,
+        vec![
+            "Begin Print",
+            "Set Left Margin",
+            "Set Print Area Width",
+            "Set Absolute Horizontal Position",
+            "Set Left Margin",
+            "Set Print Area Width",
+            "Set Left Margin",
+            "Set Print Area Width",
+            "End Print",
+        ]
+    );
+    assert!(parser.take_diagnostics().is_empty());
+
+    let mut context = Context::new();
+    let printable_width = context.get_printable_width();
+    let apply = |i: usize, context: &mut Context| {
+        commands[i].handler.apply_context(&commands[i], context);
+    };
+
+    //Lines start at the margin and the width is the print area
+    apply(1, &mut context);
+    apply(2, &mut context);
+    assert_eq!(context.get_base_x(), 96);
+    assert_eq!(context.get_x(), 96);
+    assert_eq!(context.get_width(), 300);
+    assert_eq!(context.get_available_width(), 300);
+
+    //Positions are from the margin
+    apply(3, &mut context);
+    assert_eq!(context.get_x(), 106);
+    assert_eq!(context.get_available_width(), 290);
+
+    context.text.justify = TextJustify::Right;
+    context.reset_x();
+    context.set_x(context.calculate_justification(100));
+    assert_eq!(context.get_x(), 296);
+
+    //The margin and width only change at the beginning of a line
+    apply(4, &mut context);
+    apply(5, &mut context);
+    assert_eq!(context.get_base_x(), 96);
+    assert_eq!(context.get_width(), 300);
+    context.reset_x();
+
+    //The print area can't go past the printable area
+    apply(4, &mut context);
+    assert_eq!(context.get_base_x(), 350);
+    assert_eq!(context.get_width(), 300.min(printable_width - 350));
+    apply(5, &mut context);
+    assert_eq!(context.get_width(), printable_width - 350);
+
+    //And is at least one character wide
+    apply(6, &mut context);
+    apply(7, &mut context);
+    assert_eq!(context.get_base_x(), 0);
+    assert_eq!(context.get_width(), context.text.character_width as u32);
+}
+
+#[test]
+fn user_defined_characters() {
+    //Two characters, A is 2 dots wide with the top and bottom dots
+    //of the first column set, B has no columns
+    let bytes = b"\x1b&\x02AB\x02\x80\x01\x00\x00\x00\
+                  \x1b%\x01ABC\x1b?A\x1b%\x00"
+        .to_vec();
+
+    let mut parser = Parser::new(esc_pos::new());
+    let commands = parser.parse_bytes(&bytes);
+    let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+
+    assert_eq!(
+        names,
+        vec![
+            "Begin Print",
+            "Define User-Defined Characters",
+            "Select User-Defined Characters",
+            "Text",
+            "Cancel User-Defined Character",
+            "Select User-Defined Characters",
+            "End Print",
+        ]
+    );
+    assert!(parser.take_diagnostics().is_empty());
+
+    let mut context = Context::new();
+    commands[1]
+        .handler
+        .apply_context(&commands[1], &mut context);
+
+    let a = UserCharacters::to_char(b'A');
+    let character = context.text.user_characters.get(&Font::A, a).unwrap();
+    assert_eq!((character.width, character.height), (2, 16));
+    assert!(character.pixels[0] && character.pixels[15 * 2]);
+    assert_eq!(character.pixels.iter().filter(|p| **p).count(), 2);
+
+    //Characters belong to the font they were defined for
+    assert!(context.text.user_characters.get(&Font::B, a).is_none());
+
+    //Downloaded characters only print with the user-defined set selected
+    let text = |context: &Context| {
+        commands[3]
+            .handler
+            .get_text(&commands[3], context)
+            .unwrap()
+            .text
+    };
+    assert_eq!(text(&context), "ABC");
+
+    commands[2]
+        .handler
+        .apply_context(&commands[2], &mut context);
+    let b = UserCharacters::to_char(b'B');
+    assert_eq!(text(&context), format!("{}{}C", a, b));
+
+    //In Kanji mode they replace the codes that are not multi byte characters
+    let mut kanji = context.clone();
+    kanji.text.character_set = 8;
+    kanji.text.kanji.code_system = 1;
+    kanji.text.kanji.enabled = true;
+    kanji.update_decoder();
+    assert_eq!(kanji.decode_text(b"A\x93\xfaBC"), format!("{}日{}C", a, b));
+
+    commands[4]
+        .handler
+        .apply_context(&commands[4], &mut context);
+    assert_eq!(text(&context), format!("A{}C", b));
+
+    commands[5]
+        .handler
+        .apply_context(&commands[5], &mut context);
+    assert_eq!(text(&context), "ABC");
+}
+
+#[test]
+fn legacy_bit_image_commands() {
+    //An 8x8 image with the first column set, two NV images
+    //of 8x8 and 16x8 dots, then the images are printed
+    let mut bytes = b"\x1d*\x01\x01\xff".to_vec();
+    bytes.extend([0u8; 7]);
+    bytes.extend(b"\x1cq\x02\x01\x00\x01\x00");
+    bytes.extend([0xffu8; 8]);
+    bytes.extend(b"\x02\x00\x01\x00");
+    bytes.extend([0u8; 16]);
+    bytes.extend(b"\x1d/\x03\x1cp\x02\x31\x1b@\x1d/\x00\x1cp\x01\x00");
+    bytes.extend(b"\x1dQ0\x00\x02\x00\x01\x00\x80\x01");
+
+    let mut parser = Parser::new(esc_pos::new());
+    let commands = parser.parse_bytes(&bytes);
+    let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+
+    assert_eq!(
+        names,
+        vec![
+            "Begin Print",
+            "Define Downloaded Bit Image",
+            "Define NV Bit Image",
+            "Print Downloaded Bit Image",
+            "Print NV Bit Image",
+            "Initialize",
+            "Print Downloaded Bit Image",
+            "Print NV Bit Image",
+            "Variable Vertical Size Bit Image",
+            "End Print",
+        ]
+    );
+    assert!(parser.take_diagnostics().is_empty());
+
+    let mut context = Context::new();
+    context.default = Some(Box::new(context.clone()));
+    let image =
+        |i: usize, context: &Context| match commands[i].handler.get_graphics(&commands[i], context)
+        {
+            Some(GraphicsCommand::Image(image)) => Some(image),
+            _ => None,
+        };
+
+    commands[1]
+        .handler
+        .apply_context(&commands[1], &mut context);
+    commands[2]
+        .handler
+        .apply_context(&commands[2], &mut context);
+
+    //Quadruple size, the first column is set
+    let downloaded = image(3, &context).unwrap();
+    assert_eq!((downloaded.w, downloaded.h), (16, 16));
+    assert!(downloaded.pixels[0].a > 0 && downloaded.pixels[1].a > 0);
+    assert_eq!(downloaded.pixels[2].a, 0);
+
+    //Double width
+    let nv = image(4, &context).unwrap();
+    assert_eq!((nv.w, nv.h), (32, 8));
+
+    //Only the NV images are kept after initializing
+    commands[5]
+        .handler
+        .apply_context(&commands[5], &mut context);
+    assert!(image(6, &context).is_none());
+    let nv = image(7, &context).unwrap();
+    assert_eq!((nv.w, nv.h), (8, 8));
+    assert!(nv.pixels.iter().all(|p| p.a > 0));
+
+    let variable = image(8, &context).unwrap();
+    assert_eq!((variable.w, variable.h), (2, 8));
+    assert!(variable.pixels[0].a > 0 && variable.pixels[15].a > 0);
+    assert_eq!(variable.pixels.iter().filter(|p| p.a > 0).count(), 2);
+}
+
+#[test]
+fn oversized_bit_images() {
+    for bytes in [
+        b"\x1cq\x01\xff\xff\xff\xff".to_vec(),
+        b"\x1dQ0\x00\xff\xff\xff\xff".to_vec(),
+    ] {
+        let (commands, diagnostics) = parse_esc_pos_with_diagnostics(&bytes);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, DiagnosticCode::TruncatedCommand);
+
+        let mut context = Context::new();
+        commands[1]
+            .handler
+            .apply_context(&commands[1], &mut context);
+        assert!(context.graphics.stored_graphics.is_empty());
+        assert!(matches!(
+            commands[1].handler.get_graphics(&commands[1], &context),
+            None | Some(GraphicsCommand::Error(_))
+        ));
+    }
+}
+
+#[test]
+fn truncated_and_empty_bit_images() {
+    for (bytes, code) in [
+        (
+            b"\x1b@\x1d*\x02\x02\xff\xff".to_vec(),
+            DiagnosticCode::TruncatedCommand,
+        ),
+        (b"\x1d*\x00\x01".to_vec(), DiagnosticCode::InvalidSize),
+        (
+            b"\x1cq\x01\x00\x00\x01\x00".to_vec(),
+            DiagnosticCode::InvalidSize,
+        ),
+        (
+            b"\x1dQ0\x00\x00\x00\x01\x00".to_vec(),
+            DiagnosticCode::InvalidSize,
+        ),
+    ] {
+        let (commands, diagnostics) = parse_esc_pos_with_diagnostics(&bytes);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, code);
+
+        //Nothing is defined and nothing but an error is printed
+        let mut context = Context::new();
+        for command in &commands {
+            command.handler.apply_context(command, &mut context);
+            assert!(matches!(
+                command.handler.get_graphics(command, &context),
+                None | Some(GraphicsCommand::Error(_))
+            ));
+        }
+        assert!(context.graphics.stored_graphics.is_empty());
+    }
+}
+
+#[test]
+fn macro_commands() {
+    let bytes = b"\x1d:\x1bE\x01Macro\n\x1d:\x1d^\x03\x02\x01".to_vec();
+
+    let mut parser = Parser::new(esc_pos::new());
+    let commands = parser.parse_bytes(&bytes);
+    let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+
+    assert_eq!(
+        names,
+        vec![
+            "Begin Print",
+            "Start or End Macro Definition",
+            "Enable Emphasis",
+            "Text",
+            "Line Feed",
+            "Start or End Macro Definition",
+            "Execute Macro",
+            "End Print",
+        ]
+    );
+    assert!(parser.take_diagnostics().is_empty());
+
+    let mut context = Context::new();
+    commands[1]
+        .handler
+        .apply_context(&commands[1], &mut context);
+    assert!(context.macros.recording);
+    commands[5]
+        .handler
+        .apply_context(&commands[5], &mut context);
+    assert!(!context.macros.recording);
+
+    let execute = &commands[6];
+    let device_commands = execute.handler.get_device_command(execute, &context);
+    assert!(device_commands == Some(vec![DeviceCommand::ExecuteMacro(3, 2, 1)]));
+
+    //Macros are limited to 2048 bytes
+    context.macros.record(&[0u8; 3000]);
+    assert_eq!(context.macros.data.len(), MacroContext::MAX_LEN);
+}
+
+#[test]
+fn kanji_commands() {
+    let bytes = b"\x1bR\x08\x1cC\x31\x1c&\x1c!\x04\x1cS\x02\x02\x1c-\x01\x1cW\x01\x1c.".to_vec();
+
+    let mut parser = Parser::new(esc_pos::new());
+    let commands = parser.parse_bytes(&bytes);
+    let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+
+    assert_eq!(
+        names,
+        vec![
+            "Begin Print",
+            "Set International Character Set",
+            "Select Kanji Code System",
+            "Select Kanji Mode",
+            "Set Kanji Print Mode",
+            "Set Kanji Character Spacing",
+            "Set Kanji Underline",
+            "Set Kanji Quadruple Size",
+            "Cancel Kanji Mode",
+            "End Print",
+        ]
+    );
+    assert!(parser.take_diagnostics().is_empty());
+
+    let mut context = Context::new();
+    for command in &commands[1..4] {
+        command.handler.apply_context(command, &mut context);
+    }
+    assert!(context.text.kanji.enabled);
+    assert!(context.text.kanji.encoding == Some(MultiByteEncoding::ShiftJis));
+
+    //Single bytes use the codepage, 0x5C is the yen sign in Japan
+    assert_eq!(
+        context.decode_text(b"\x97\xcc\x8e\xfb\x8f\x91 \x5c10"),
+        "領収書 ¥10"
+    );
+
+    //A double width Kanji with 2 dots of spacing on each side
+    commands[4]
+        .handler
+        .apply_context(&commands[4], &mut context);
+    commands[5]
+        .handler
+        .apply_context(&commands[5], &mut context);
+    let span = TextSpan::new("合計 10".to_string(), &context);
+    assert_eq!(span.char_width('合'), 56);
+    assert_eq!(span.char_width('1'), 12);
+    assert_eq!(span.get_width(), 56 * 2 + 12 * 3);
+
+    //Lines break between wide characters and are broken by width
+    let words: Vec<String> = span
+        .break_into_words()
+        .iter()
+        .map(|w| w.text.clone())
+        .collect();
+    assert_eq!(words, vec!["合", "計", " ", "10"]);
+    let broken: Vec<String> = span
+        .break_apart(60, 80)
+        .iter()
+        .map(|w| w.text.clone())
+        .collect();
+    assert_eq!(broken, vec!["合", "計 1", "0"]);
+
+    commands[6]
+        .handler
+        .apply_context(&commands[6], &mut context);
+    assert!(context.text.kanji.underline == TextUnderline::On);
+    commands[7]
+        .handler
+        .apply_context(&commands[7], &mut context);
+    assert!(context.text.kanji.double_width && context.text.kanji.double_height);
+
+    //Without Kanji mode, bytes are decoded one at a time
+    commands[8]
+        .handler
+        .apply_context(&commands[8], &mut context);
+    assert_eq!(context.decode_text(b"\x41\x42").chars().count(), 2);
+    assert!(!TextSpan::new("合".to_string(), &context).is_wide('合'));
+}
+
+#[test]
+fn kanji_text_spans() {
+    let mut context = Context::new();
+    context.text.character_set = 8;
+    context.text.kanji.enabled = true;
+    context.update_decoder();
+
+    let text = |text: &str, context: &Context| TextSpan::new(text.to_string(), context);
+    let texts =
+        |spans: Vec<TextSpan>| -> Vec<String> { spans.iter().map(|s| s.text.clone()).collect() };
+
+    //Wide characters are two ASCII characters wide, the spacing
+    //is scaled by the character size and the Kanji double width
+    assert_eq!(text("合", &context).char_width('合'), 24);
+    context.text.width_mult = 2;
+    context.text.kanji.left_spacing = 1;
+    context.text.kanji.right_spacing = 3;
+    let span = text("A合", &context);
+    assert_eq!(
+        (span.char_width('A'), span.char_width('合')),
+        (24, 2 + 48 + 6)
+    );
+    context.text.kanji.double_width = true;
+    let span = text("A合", &context);
+    assert_eq!(
+        (span.char_width('A'), span.char_width('合')),
+        (24, 4 + 96 + 12)
+    );
+    context.text.width_mult = 1;
+    context.text.kanji.double_width = false;
+    context.text.kanji.left_spacing = 0;
+    context.text.kanji.right_spacing = 0;
+
+    //Kanji double height only makes the wide characters taller
+    context.text.kanji.double_height = true;
+    let span = text("A合", &context);
+    assert_eq!((span.char_height('A'), span.char_height('合')), (24, 48));
+    assert_eq!((span.character_height, span.get_height()), (24, 48));
+    assert_eq!(span.get_stretch_height(), 2.0);
+    assert_eq!(text("AB", &context).get_height(), 24);
+    context.text.kanji.double_height = false;
+
+    //Every wide character can start a new line
+    let span = text("ab 合計cd", &context);
+    assert_eq!(
+        texts(span.break_into_words()),
+        vec!["ab ", "合", "計", "cd"]
+    );
+    assert_eq!(texts(span.break_apart(40, 48)), vec!["ab ", "合計", "cd"]);
+
+    //Lines narrower than a wide character still get one character each,
+    //the first line is left empty when nothing fits
+    let span = text("合計A", &context);
+    assert_eq!(texts(span.break_apart(10, 10)), vec!["", "合", "計", "A"]);
+    assert_eq!(texts(span.break_apart(24, 20)), vec!["合", "計", "A"]);
+}
+
+#[test]
+fn multi_byte_encodings() {
+    let decode = |character_set: u8, code_system: u8, bytes: &[u8]| {
+        let mut context = Context::new();
+        context.text.character_set = character_set;
+        context.text.kanji.code_system = code_system;
+        context.text.kanji.enabled = true;
+        context.update_decoder();
+        context.decode_text(bytes)
+    };
+
+    assert_eq!(decode(8, 0, b"\x46\x7c\x4b\x5c"), "日本");
+    assert_eq!(decode(15, 1, b"\xd7\xdc\xbc\xc6\x81\x30\x84\x36"), "总计¥");
+    assert_eq!(decode(15, 2, b"\xd7\xdc"), "总");
+    assert_eq!(
+        get_multi_byte_encoding(8, 2),
+        Some(MultiByteEncoding::ShiftJis)
+    );
+    assert_eq!(decode(13, 1, b"\xc7\xd5\xb0\xe8"), "합계");
+
+    //Invalid trail bytes are replaced, ASCII trail bytes are kept
+    assert_eq!(decode(8, 1, b"\x81\x7f\x82"), "\u{FFFD}\u{7F}\u{FFFD}");
+    assert_eq!(decode(0, 1, b"AB"), "AB");
+    assert!(get_multi_byte_encoding(0, 1).is_none());
+
+    //Big5 is selected like a memory switch and is kept by a reset
+    let mut context = Context::new();
+    context.set_big5(true);
+    context.reset();
+    context.text.character_set = 15;
+    context.text.kanji.enabled = true;
+    context.update_decoder();
+    assert_eq!(context.text.kanji.encoding, Some(MultiByteEncoding::Big5));
+    assert_eq!(context.decode_text(b"\xc1\x60\xad\x70"), "總計");
+}
+
+#[test]
+fn star_line_commands() {
+    let bytes = b"\x1b@\x1b\x1da1\x1bEBold\x1bF\x0eWide\x14\n\
+                  \x1bb\x06\x02\x02\x32A-1\x1e\
+                  \x1b\x1dyS0\x02\x1b\x1dyD1\x00\x03\x00abc\x1b\x1dyP\
+                  \x1bd\x03\x07"
+        .to_vec();
+
+    let mut parser = Parser::new(star_line::new());
+    let commands = parser.parse_bytes(&bytes);
+    let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+
+    assert_eq!(
+        names,
+        vec![
+            "Begin Print",
+            "Initialize",
+            "Set Text Justification",
+            "Enable Emphasis",
+            "Text",
+            "Cancel Emphasis",
+            "Enable Double Width",
+            "Text",
+            "Cancel Double Width",
+            "Line Feed",
+            "Barcode",
+            "QR Set Model",
+            "QR Store the Code2D data",
+            "Print the Code2D data",
+            "Cut",
+            "Open Drawer",
+            "End Print",
+        ]
+    );
+    assert!(parser.take_diagnostics().is_empty());
+
+    //The barcode parameters and RS are kept for rebuilding the command
+    let barcode = &commands[10];
+    assert_eq!(barcode.data, b"A-1");
+    let (command, data) = barcode.handler.get_command_bytes(barcode);
+    assert_eq!([command, data].concat(), b"\x1bb\x06\x02\x02\x32A-1\x1e");
+    assert_eq!(commands[12].data, b"abc");
+}
+
+#[test]
+fn star_graphic_commands() {
+    let bytes = b"\x1b*rR\x1b*rA\x1b*rQ1\x00\
+                  b\x02\x00\xf0\x0f\
+                  \x1b*b\x04\x00\xff\xff\x00\x81\
+                  \x1b*rY2\x00\x1b\x0c\x00\x1b*rB"
+        .to_vec();
+
+    let mut parser = Parser::new(star_graphic::new());
+    let mut commands = parser.parse_bytes(&bytes);
+    let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+
+    assert_eq!(
+        names,
+        vec![
+            "Begin Print",
+            "Initialize Raster Mode",
+            "Enter Raster Mode",
+            "Set Raster Print Quality",
+            "Transfer Raster Data",
+            "Transfer Compressed Raster Data",
+            "Move Raster Vertical Position",
+            "Execute FF Mode",
+            "Quit Raster Mode",
+            "End Print",
+        ]
+    );
+    assert!(parser.take_diagnostics().is_empty());
+
+    //Settings keep the NUL for rebuilding the command
+    let (command, data) = commands[3].handler.get_command_bytes(&commands[3]);
+    assert_eq!([command, data].concat(), b"\x1b*rQ1\x00");
+
+    let mut context = Context::new();
+    for command in commands.iter_mut().take(7) {
+        command.handler.apply_context(command, &mut context);
+    }
+
+    //A line of 16 dots that is padded to 24, then 24 dots and two blank lines
+    let image = match commands[7].handler.get_graphics(&commands[7], &context) {
+        Some(GraphicsCommand::Image(image)) => image,
+        _ => panic!("Expected the raster lines as an image"),
+    };
+    let dots: Vec<u8> = image.pixels.iter().map(|p| (p.a > 0) as u8).collect();
+
+    assert_eq!((image.w, image.h), (24, 4));
+    assert_eq!(
+        dots[0..24],
+        [1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0]
+    );
+    assert_eq!(
+        dots[24..48],
+        [1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 1]
+    );
+    assert!(dots[48..].iter().all(|d| *d == 0));
+}
+
+#[test]
+fn star_graphic_vertical_move_is_limited() {
+    let bytes = b"\x1b*rAb\x01\x00\xff\x1b*rY99999999999\x00\x1b\x0c\x00".to_vec();
+
+    let mut parser = Parser::new(star_graphic::new());
+    let mut commands = parser.parse_bytes(&bytes);
+    let diagnostics = parser.take_diagnostics();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, DiagnosticCode::InvalidParameter);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+
+    let mut context = Context::new();
+    for command in commands.iter_mut().take(4) {
+        command.handler.apply_context(command, &mut context);
+    }
+
+    //The move is a single blank image
+    assert_eq!(context.graphics.buffer_graphics.len(), 2);
+
+    let image = match commands[4].handler.get_graphics(&commands[4], &context) {
+        Some(GraphicsCommand::Image(image)) => image,
+        _ => panic!("Expected the raster lines as an image"),
+    };
+    assert_eq!((image.w, image.h), (8, 8001));
+}
+
+#[test]
+fn zpl_label_commands() {
+    let bytes = b"^XA\n^FO20,30^A0N,48,48^FDHello^FS\n\
+                  ^FO20,100^BCN,80,Y,N,N^FD>:AB12^FS^ZZ1^XZ"
+        .to_vec();
+
+    let mut parser = Parser::new(zpl::new());
+    let commands = parser.parse_bytes(&bytes);
+    let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+
+    assert_eq!(
+        names,
+        vec![
+            "Begin Label Print",
+            "Start Format",
+            "Ignored Data",
+            "Field Origin",
+            "Font",
+            "Field Data",
+            "Field Separator",
+            "Ignored Data",
+            "Field Origin",
+            "Code 128 Bar Code",
+            "Unknown Command",
+            "End Format",
+            "End Print",
+        ]
+    );
+
+    //The field origin sets the print area, the font is 24 dots wide and 48 tall
+    let mut context = Context::new();
+    for command in commands.iter().take(5) {
+        command.handler.apply_context(command, &mut context);
+    }
+
+    let area = &context.page_mode.logical_area;
+    assert_eq!((area.x, area.y, area.w, area.h), (20, 30, 792, 1188));
+    assert!(
+        commands[4]
+            .handler
+            .get_device_command(&commands[4], &context)
+            == Some(vec![
+                DeviceCommand::SetTextWidth(2),
+                DeviceCommand::SetTextHeight(2)
+            ])
+    );
+
+    let text = commands[5]
+        .handler
+        .get_text(&commands[5], &context)
+        .unwrap();
+    assert_eq!(text.text, "Hello");
+
+    //The barcode reads its parameters and data up to the ^FS
+    assert_eq!(commands[9].data, b"N,80,Y,N,N^FD>:AB12^FS");
+    assert!(matches!(
+        commands[9].handler.get_graphics(&commands[9], &context),
+        Some(GraphicsCommand::Barcode(_))
+    ));
+    assert_eq!(commands[10].data, b"^ZZ1");
+}
+
+#[test]
+fn zpl_code128_invocation_codes() {
+    let barcode = |field: &[u8]| {
+        let mut bytes = b"^BCN,80,Y,N,N^FD".to_vec();
+        bytes.extend_from_slice(field);
+        bytes.extend_from_slice(b"^FS");
+
+        let mut parser = Parser::new(zpl::new());
+        let commands = parser.parse_bytes(&bytes);
+        let command = &commands[1];
+
+        match command.handler.get_graphics(command, &Context::new()) {
+            Some(GraphicsCommand::Barcode(barcode)) => barcode,
+            _ => panic!("Expected a barcode for {:?}", field),
+        }
+    };
+
+    assert_eq!(barcode(b">< ").text.text, "> ");
+    assert_eq!(barcode(b">0A>=").text.text, ">A~");
+    assert_eq!(barcode(b"{>:AB>512").text.text, "{AB12");
+
+    //FNC1 is a codeword of its own, > and subset codes aren't
+    assert!(barcode(b">;>812").points.len() > barcode(b">;12").points.len());
+    assert_eq!(barcode(b">:AB12").points, barcode(b"AB12").points);
+}
+
+#[test]
+fn zpl_graphic_box_is_limited_to_the_label() {
+    let bytes = b"^XA^FO100,200^GB99999,99999,3^FS^FO99999,99999^GB10,10^FS^XZ".to_vec();
+
+    let mut parser = Parser::new(zpl::new());
+    let commands = parser.parse_bytes(&bytes);
+    let mut context = Context::new();
+    let mut sizes = vec![];
+
+    for command in &commands {
+        command.handler.apply_context(command, &mut context);
+
+        //The renderer moves the page to the field origin
+        let device_commands = command.handler.get_device_command(command, &context);
+        if device_commands.is_some_and(|c| c.contains(&DeviceCommand::ChangePageArea)) {
+            context.page_mode.apply_logical_area();
+        }
+
+        if command.name.as_str() == "Graphic Box" {
+            match command.handler.get_graphics(command, &context) {
+                Some(GraphicsCommand::Image(image)) => {
+                    assert_eq!(image.pixels.len(), (image.w * image.h) as usize);
+                    sizes.push((image.w, image.h));
+                }
+                _ => panic!("Expected the box as an image"),
+            }
+        }
+    }
+
+    //The label is 812 by 1218 dots, the origin is kept on it
+    assert_eq!(sizes, vec![(712, 1018), (0, 0)]);
+}
+
+#[test]
+fn epl_label_commands() {
+    let bytes = b"N\r\nq400\r\nA10,20,0,4,1,1,N,\"Say \\\"hi\\\", bye\"\r\n\
+                  LO10,60,300,4\r\nB10,80,0,E30,2,4,50,B,\"4006381333931\"\r\nP1\r\n"
+        .to_vec();
+
+    let mut parser = Parser::new(epl::new());
+    let commands = parser.parse_bytes(&bytes);
+    let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+
+    assert_eq!(
+        names,
+        vec![
+            "Begin Label Print",
+            "Clear Image Buffer",
+            "Set Label Width",
+            "ASCII Text",
+            "Text Data",
+            "Line Draw Black",
+            "Bar Code",
+            "Print",
+            "End Print",
+        ]
+    );
+    assert!(parser.take_diagnostics().is_empty());
+
+    let mut context = Context::new();
+    for command in commands.iter().take(4) {
+        command.handler.apply_context(command, &mut context);
+    }
+
+    let text = commands[4]
+        .handler
+        .get_text(&commands[4], &context)
+        .unwrap();
+    assert_eq!(text.text, "Say \"hi\", bye");
+
+    //Graphics are drawn at their own position
+    commands[5]
+        .handler
+        .apply_context(&commands[5], &mut context);
+    let area = &context.page_mode.render_area;
+    assert_eq!((area.x, area.y), (10, 60));
+
+    match commands[5].handler.get_graphics(&commands[5], &context) {
+        Some(GraphicsCommand::Image(image)) => assert_eq!((image.w, image.h), (300, 4)),
+        _ => panic!("Expected the line as an image"),
+    }
+
+    assert!(matches!(
+        commands[6].handler.get_graphics(&commands[6], &context),
+        Some(GraphicsCommand::Barcode(_))
+    ));
+}
+
+#[test]
+fn it_detects_dialects() {
+    let samples = [
+        ("discount", "bin", Dialect::EscPos),
+        ("retail", "bin", Dialect::EscPos),
+        ("receipt_with_barcode", "thermal", Dialect::EscPos),
+        ("star_line", "thermal", Dialect::StarLine),
+        ("star_graphic", "thermal", Dialect::StarGraphic),
+        ("shipping_label", "zpl", Dialect::Zpl),
+        ("shipping_label", "epl", Dialect::Epl),
+    ];
+
+    for (name, ext, dialect) in samples {
+        let guess = detect_dialect(&sample_bytes(name, ext));
+        assert_eq!(guess.set, dialect, "{}.{}", name, ext);
+        assert!(guess.confidence > 0.9, "{}.{}", name, ext);
+    }
+
+    let guess = detect_dialect(b"Plain text\r\nwith no commands\n");
+    assert_eq!((guess.set, guess.confidence), (Dialect::PlainText, 1.0));
+
+    //Initialize and line feeds are the same in every dialect
+    let guess = detect_dialect(b"\x1b@Hello\n");
+    assert_eq!((guess.set, guess.confidence), (Dialect::EscPos, 0.0));
+
+    let (guess, commands) = parse_auto(&sample_bytes("star_line", "thermal"));
+    assert_eq!(guess.set, Dialect::StarLine);
+    assert!(commands.iter().any(|c| c.name.as_str() == "Barcode"));
+}
+
 fn test_sample(name: &str, ext: &str) {
+    parse(&sample_bytes(name, ext), true, name);
+}
+
+fn sample_bytes(name: &str, ext: &str) -> Vec<u8> {
     let sample_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
         .join("..")
         .join("sample_files")
         .join("in")
         .join(format!("{}.{}", name, ext));
 
-    let bytes = if ext == "thermal" {
+    if ext == "thermal" {
         let text = std::fs::read_to_string(sample_file.to_str().unwrap()).unwrap();
         parse_str(&text)
     } else {
         std::fs::read(sample_file.to_str().unwrap()).unwrap()
-    };
-
-    parse(&bytes, true, name);
+    }
 }
 
 fn parse(bytes: &Vec<u8>, debug: bool, name: &str) {
@@ -87,10 +1130,57 @@ fn parse(bytes: &Vec<u8>, debug: bool, name: &str) {
     println!("┌─ PARSE {}", name);
 
     for cmd in commands.iter() {
+        assert!(cmd.start <= cmd.end && cmd.end <= bytes.len());
+
         if debug {
             println!("├─ {}", cmd.handler.debug(&cmd, &context));
         }
     }
 
-    println!("└─ End")
+    println!("└─ End");
+
+    //Feeding the same bytes in chunks gives the same commands
+    for chunk_size in [1, 7, 4096] {
+        let mut parser = Parser::new(esc_pos::new());
+        let mut streamed: Vec<Command> = vec![];
+
+        for chunk in bytes.chunks(chunk_size) {
+            streamed.extend(parser.feed(chunk));
+        }
+        streamed.extend(parser.finish());
+
+        assert_eq!(streamed.len(), commands.len());
+
+        for (a, b) in streamed.iter().zip(commands.iter()) {
+            assert_eq!(a.name, b.name);
+            assert_eq!((a.start, a.end), (b.start, b.end));
+            assert_eq!(a.data, b.data);
+        }
+    }
+}
+
+#[test]
+fn composite_hri_check_digits() {
+    let hri = |symbol_type: u8, data: &str| {
+        let mut bytes = b"\x1b@\x1d(k\x03\x00\x34\x48\x31".to_vec();
+        let len = data.len() + 5;
+        bytes.extend([0x1d, b'(', b'k', len as u8, 0, 52, 80, 48, 48, symbol_type]);
+        bytes.extend(data.as_bytes());
+
+        let commands = Parser::new(esc_pos::new()).parse_bytes(&bytes);
+        let mut context = Context::new();
+        for command in commands.iter() {
+            command.handler.apply_context(command, &mut context);
+        }
+
+        match context.code2d.symbol_storage {
+            Some(GraphicsCommand::Composite(composite)) => composite.text.unwrap().text,
+            _ => panic!("No composite for type {}", symbol_type),
+        }
+    };
+
+    assert_eq!(hri(65, "9638507"), "96385074");
+    assert_eq!(hri(66, "490123456789"), "4901234567894");
+    assert_eq!(hri(67, "03600029145"), "036000291452");
+    assert_eq!(hri(68, "0123456"), "01234565");
 }
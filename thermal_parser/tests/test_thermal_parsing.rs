@@ -1,8 +1,11 @@
 use std::fs::File;
 use std::io::Write;
 use std::path::PathBuf;
-use thermal_parser::parse_esc_pos;
-use thermal_parser::thermal_file::{cmds_to_thermal, parse_str, parse_tokens, try_const};
+use thermal_parser::diagnostic::Severity;
+use thermal_parser::thermal_file::{
+    parse_binary, parse_file, parse_str, parse_str_strict, parse_str_with_options,
+    parse_str_with_warnings, parse_tokens, try_const, ThermalOptions,
+};
 
 #[test]
 fn it_parses_tokens() {
@@ -40,10 +43,246 @@ fn it_parses_lines() {
     assert_eq!(bytes[10], 92); // \
 }
 
-// #[test]
-// fn it_decompiles_binary() {
-//     convert_binary_to_thermal("barcodes_upce")
-// }
+#[test]
+fn it_parses_macros_and_repeats() {
+    let text = "@define BOLD ESC \"E\" 1\n\
+                @define TWICE BOLD BOLD\n\
+                TWICE\n\
+                @repeat 2\n\
+                \"${item}\"\n\
+                '// Comments are skipped in repeats too\n\
+                @repeat ${count}\n\
+                LF\n\
+                @end\n\
+                @end\n";
+
+    let mut options = ThermalOptions::default();
+    options
+        .variables
+        .insert("item".to_string(), "A".to_string());
+    options
+        .variables
+        .insert("count".to_string(), "2".to_string());
+
+    let bytes = parse_str_with_options(text, &options).unwrap();
+    assert_eq!(bytes, b"\x1bE\x01\x1bE\x01A\n\nA\n\n");
+}
+
+#[test]
+fn it_reports_errors() {
+    let text = "ESC \"E\" BOLD\n\
+                \"${missing}\"\n\
+                @end\n\
+                @unknown\n\
+                @define ESC 1\n\
+                @repeat 2\n\
+                LF";
+
+    let errors = parse_str_with_options(text, &ThermalOptions::default()).unwrap_err();
+    let found: Vec<(usize, usize)> = errors.iter().map(|e| (e.line, e.column)).collect();
+
+    assert_eq!(found, vec![(1, 9), (2, 2), (3, 1), (4, 1), (5, 9), (6, 1)]);
+    assert_eq!(errors[0].to_string(), "1:9: Unknown name BOLD");
+
+    //parse_str keeps going and writes unknown names as they are
+    assert_eq!(&parse_str(text)[..6], b"\x1bEBOLD");
+}
+
+#[test]
+fn it_parses_includes() {
+    let dir = std::env::temp_dir().join(format!("thermal_includes_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("parts")).unwrap();
+
+    std::fs::write(
+        dir.join("parts").join("header.thermal"),
+        "@define INIT ESC \"@\"\n@include \"title.thermal\"\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("parts").join("title.thermal"), "\"${title}\" LF\n").unwrap();
+    std::fs::write(
+        dir.join("receipt.thermal"),
+        "@include \"parts/header.thermal\"\nINIT\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("loop.thermal"), "@include \"./loop.thermal\"\n").unwrap();
+
+    let mut options = ThermalOptions::default();
+    options
+        .variables
+        .insert("title".to_string(), "Hi".to_string());
+
+    let bytes = parse_file(&dir.join("receipt.thermal"), &options).unwrap();
+    assert_eq!(bytes, b"Hi\n\x1b@");
+
+    //Text that isn't from a file includes from the include dir
+    options.include_dir = Some(dir.clone());
+    let bytes = parse_str_with_options("@include \"parts/title.thermal\"", &options).unwrap();
+    assert_eq!(bytes, b"Hi\n");
+
+    let errors = parse_file(&dir.join("loop.thermal"), &options).unwrap_err();
+    assert!(errors[0].message.ends_with("includes itself"));
+
+    let errors = parse_str_with_options("@include \"missing.thermal\"", &options).unwrap_err();
+    assert_eq!((errors[0].line, errors[0].column), (1, 1));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn it_parses_strictly() {
+    let text = "ESC \"E\" 0xZZ 300\n\
+                \"ok\" 1,2 LF\n\
+                GS \"k\" \"unterminated \\\" string";
+
+    let errors = parse_str_strict(text).unwrap_err();
+    let spans: Vec<(usize, usize, usize)> = errors
+        .iter()
+        .map(|e| (e.line, e.column, e.end_column))
+        .collect();
+
+    assert_eq!(spans, vec![(1, 9, 13), (1, 14, 17), (2, 6, 9), (3, 8, 31)]);
+    assert!(errors.iter().all(|e| e.severity == Severity::Error));
+    assert_eq!(errors[0].to_string(), "1:9: Invalid hex value 0xZZ");
+    assert_eq!(errors[3].message, "Unterminated string");
+
+    //The lenient parser writes the same bytes as before, with warnings
+    let (bytes, warnings) = parse_str_with_warnings(text);
+    assert_eq!(bytes, parse_str(text));
+    assert_eq!(bytes, b"\x1bE300ok1,2\n\x1dkunterminated \" string");
+    assert_eq!(warnings.len(), 4);
+    assert!(warnings.iter().all(|w| w.severity == Severity::Warning));
+    assert_eq!(
+        warnings[1].to_string(),
+        "1:14: warning: Decimal value 300 doesn't fit in a byte"
+    );
+
+    assert_eq!(
+        parse_str_strict("ESC \"@\" 255 0xff"),
+        Ok(vec![0x1b, b'@', 255, 255])
+    );
+}
+
+#[test]
+fn it_decompiles_samples() {
+    let samples = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("sample_files")
+        .join("in");
+
+    for entry in std::fs::read_dir(samples).unwrap() {
+        let path = entry.unwrap().path();
+
+        let bytes = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("bin") => std::fs::read(&path).unwrap(),
+            Some("thermal") => parse_str_strict(&std::fs::read_to_string(&path).unwrap()).unwrap(),
+            _ => continue,
+        };
+
+        let thermal_file = parse_binary(bytes.clone()).join("\n");
+
+        assert!(
+            parse_str_strict(&thermal_file) == Ok(bytes),
+            "{:?} does not decompile to the same bytes",
+            path
+        );
+    }
+}
+
+#[test]
+fn it_decompiles_with_comments() {
+    //Codepage 2 (PC850) and a justification
+    let bytes = vec![0x1b, b't', 2, b'C', 0x82, b'"', 0x1b, b'a', 1, b'\\', b'\n'];
+    let lines = parse_binary(bytes.clone());
+
+    assert_eq!(
+        lines,
+        [
+            "'// Set Code Table (PC850: Multilingual, Us English)",
+            "ESC \"t\" 2",
+            "",
+            "'// \"Cé\\\"\"",
+            "\"C\" 0x82 \"\\\"\"",
+            "'// Set Text Justification (Justify Center)",
+            "ESC \"a\" 1",
+            "",
+            "\"\\\\\"",
+            "LF",
+        ]
+    );
+    assert_eq!(parse_str(&lines.join("\n")), bytes);
+}
+
+#[test]
+fn it_decompiles_variable_syntax() {
+    let bytes = b"Total ${price} \\${x} $\n".to_vec();
+    let thermal_file = parse_binary(bytes.clone()).join("\n");
+
+    assert_eq!(parse_str_strict(&thermal_file), Ok(bytes));
+    assert_eq!(parse_str("\"\\${a} \\\\${b}\""), b"${a} \\${b}");
+
+    //A $ before a variable is just a dollar sign
+    let mut options = ThermalOptions::default();
+    options
+        .variables
+        .insert("price".to_string(), "12.00".to_string());
+    let bytes = parse_str_with_options("\"$${price} \\${price}\"", &options).unwrap();
+    assert_eq!(bytes, b"$12.00 ${price}");
+}
+
+#[test]
+fn it_reports_errors_at_source_columns() {
+    let mut options = ThermalOptions::default();
+    options
+        .variables
+        .insert("long".to_string(), "\"a long value\"".to_string());
+
+    //The columns are in the line before ${long} was replaced
+    let errors = parse_str_with_options("${long} ${missing} 0xZZ", &options).unwrap_err();
+    let spans: Vec<(usize, usize, usize)> = errors
+        .iter()
+        .map(|e| (e.line, e.column, e.end_column))
+        .collect();
+    assert_eq!(spans, vec![(1, 9, 19), (1, 20, 24)]);
+    assert_eq!(errors[0].message, "Unknown variable missing");
+
+    //A token that comes from a variable spans the whole ${name}
+    options
+        .variables
+        .insert("hex".to_string(), "0xZZ".to_string());
+    let errors = parse_str_with_options("LF ${hex}", &options).unwrap_err();
+    assert_eq!((errors[0].column, errors[0].end_column), (4, 10));
+
+    //Unknown variables are kept as they are by the lenient parser
+    let (bytes, warnings) = parse_str_with_warnings("\"${missing}\" LF");
+    assert_eq!(bytes, b"${missing}\n");
+    assert_eq!(
+        warnings[0].to_string(),
+        "1:2: warning: Unknown variable missing"
+    );
+}
+
+#[test]
+fn it_limits_repeats() {
+    let errors = parse_str_strict("@repeat 100000\nLF\n@end").unwrap_err();
+    assert_eq!((errors[0].line, errors[0].column), (1, 9));
+    assert_eq!(
+        errors[0].message,
+        "@repeat 100000 is over the limit of 10000 repeats"
+    );
+
+    //Nested blocks count towards the limit together
+    let errors = parse_str_strict("@repeat 200\n@repeat 200\nLF\n@end\n@end").unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!((errors[0].line, errors[0].column), (2, 9));
+
+    assert_eq!(parse_str("@repeat 100000\nLF\n@end\nCR"), b"\r");
+    assert_eq!(
+        parse_str_strict("@repeat 100\n@repeat 100\nLF\n@end\n@end")
+            .unwrap()
+            .len(),
+        10_000
+    );
+}
 
 // Utility function for converting bin files
 // into the more readable format. Ideally all
@@ -62,8 +301,7 @@ pub fn convert_binary_to_thermal(name: &str) {
         .join(format!("{}.converted.thermal", name));
 
     let original_bytes = std::fs::read(binary_filepath.to_str().unwrap()).unwrap();
-    let parsed_commands = parse_esc_pos(&original_bytes);
-    let thermal_file = cmds_to_thermal(&parsed_commands);
+    let thermal_file = parse_binary(original_bytes.clone()).join("\n");
     let new_bytes = parse_str(&thermal_file);
 
     for i in 0..original_bytes.len() {
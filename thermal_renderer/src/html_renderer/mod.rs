@@ -19,6 +19,7 @@ mod thermal_html;
 use crate::html_renderer::thermal_html::{encode_html_image, graphics_to_svg, spans_to_html};
 use crate::image_renderer::thermal_image::ThermalImage;
 use crate::renderer::{DebugProfile, OutputRenderer, RenderOutput, Renderer};
+use thermal_parser::command_sets::CommandSet;
 use thermal_parser::context::{Context, PrintDirection, Rotation, TextJustify};
 use thermal_parser::graphics::{Image, ImageFlow, VectorGraphic};
 use thermal_parser::text::TextSpan;
@@ -80,6 +81,20 @@ impl HtmlRenderer {
         renderer.render(bytes)
     }
 
+    /// Renders bytes in another printer language to an html
+    pub fn render_with_command_set(
+        bytes: &Vec<u8>,
+        command_set: CommandSet,
+        debug_profile: Option<DebugProfile>,
+    ) -> RenderOutput<ReceiptHtml> {
+        let mut child_renderer: Box<dyn OutputRenderer<_>> = Box::new(HtmlRenderer::new());
+        let mut renderer = Renderer::new(
+            &mut child_renderer,
+            debug_profile.unwrap_or(DebugProfile::default()),
+        );
+        renderer.render_with_command_set(bytes, command_set)
+    }
+
     fn push_row(&mut self, row: HtmlRow) {
         self.content.push(format!(
             "<p style='height: {}px; margin-top: {}px'>{}</p>",
@@ -224,7 +239,12 @@ impl OutputRenderer<ReceiptHtml> for HtmlRenderer {
         if context.page_mode.enabled {
             for span in spans {
                 if let Some(_) = &span.dimensions {
-                    self.page_image.render_span(x_offset, max_height, span);
+                    self.page_image.render_span(
+                        x_offset,
+                        max_height,
+                        span,
+                        &context.text.user_characters,
+                    );
                 }
             }
         } else {
@@ -234,6 +254,7 @@ impl OutputRenderer<ReceiptHtml> for HtmlRenderer {
                 max_height,
                 0.78,
                 &context.graphics.render_colors,
+                &context.text.user_characters,
             ));
         }
     }
@@ -4,33 +4,61 @@ use base64::Engine;
 use png::{ColorType, Encoder};
 use thermal_parser::context::{Font, RenderColors};
 use thermal_parser::graphics::{Image, VectorGraphic, RGBA};
-use thermal_parser::text::TextSpan;
+use thermal_parser::text::{TextSpan, UserCharacter, UserCharacters};
 
 pub fn encode_html_image(image: &Image) -> HtmlRow {
+    let base64_encoded_image = encode_png(image.w, image.h, &image.as_rgba_u8());
+
+    HtmlRow {
+        y: image.y,
+        height: image.h,
+        content: format!(
+            "<img style='left: {}px;' class='img' width='{}' src='data:image/png;base64, {}' />",
+            image.x, image.w, base64_encoded_image
+        ),
+    }
+}
+
+//Base64 encoded png from rgba bytes
+fn encode_png(width: u32, height: u32, image_data: &[u8]) -> String {
     // Create a buffer to hold the PNG image data
     let mut png_data: Vec<u8> = Vec::new();
-    let image_data: Vec<u8> = image.as_rgba_u8();
 
-    let mut encoder = Encoder::new(&mut png_data, image.w, image.h);
+    let mut encoder = Encoder::new(&mut png_data, width, height);
     encoder.set_color(ColorType::Rgba);
     encoder.set_depth(png::BitDepth::Eight);
     let mut writer = encoder.write_header().expect("Failed to write PNG header");
     writer
-        .write_image_data(&image_data)
+        .write_image_data(image_data)
         .expect("Failed to write PNG image data");
 
     writer.finish().expect("Error encoding png");
 
-    let base64_encoded_image = general_purpose::STANDARD_NO_PAD.encode(&png_data);
+    general_purpose::STANDARD_NO_PAD.encode(&png_data)
+}
 
-    HtmlRow {
-        y: image.y,
-        height: image.h,
-        content: format!(
-            "<img style='left: {}px;' class='img' width='{}' src='data:image/png;base64, {}' />",
-            image.x, image.w, base64_encoded_image
-        ),
+//Downloaded characters are drawn in the text color and
+//sized to the character cell so they flow with the text
+fn user_char_to_html(character: &UserCharacter, text: &TextSpan) -> String {
+    let width = text.base_character_width;
+    let height = text.base_character_height;
+    let mut image_data = vec![0u8; (width * height * 4) as usize];
+
+    for y in 0..character.height.min(height) {
+        for x in 0..character.width.min(width) {
+            if character.pixels[(y * character.width + x) as usize] {
+                let index = ((y * width + x) * 4) as usize;
+                let color = text.text_color;
+                image_data[index..index + 4].copy_from_slice(&[color.r, color.g, color.b, 255]);
+            }
+        }
     }
+
+    format!(
+        "<img class='uc' style='height: {}px' src='data:image/png;base64, {}' />",
+        height,
+        encode_png(width, height, &image_data)
+    )
 }
 
 pub fn graphics_to_svg(graphics: &Vec<VectorGraphic>) -> HtmlRow {
@@ -89,6 +117,7 @@ pub fn spans_to_html(
     max_height: u32,
     baseline_ratio: f32,
     render_colors: &RenderColors,
+    user_characters: &UserCharacters,
 ) -> HtmlRow {
     if spans.is_empty() {
         return HtmlRow::empty();
@@ -98,8 +127,15 @@ pub fn spans_to_html(
     let mut min_y = u32::MAX;
 
     for span in spans {
-        height = height.max(span.character_height);
-        let (y, content) = span_to_html(span, x_offset, max_height, baseline_ratio, render_colors);
+        height = height.max(span.get_height());
+        let (y, content) = span_to_html(
+            span,
+            x_offset,
+            max_height,
+            baseline_ratio,
+            render_colors,
+            user_characters,
+        );
         min_y = min_y.min(y);
         spans_html.push(content);
     }
@@ -120,12 +156,13 @@ fn span_to_html(
     max_height: u32,
     baseline_ratio: f32,
     render_colors: &RenderColors,
+    user_characters: &UserCharacters,
 ) -> (u32, String) {
     //All of this is to calculate the offset for smaller characters
     //When a larger character is in the same line.
     //We need to adjust the y to try and match the character baselines
     let max_height_baseline = max_height as f32 * baseline_ratio;
-    let span_baseline = text.character_height as f32 * baseline_ratio;
+    let span_baseline = text.get_height() as f32 * baseline_ratio;
     let baseline_offset = (max_height_baseline - span_baseline) as u32;
 
     let mut class_list = vec![];
@@ -166,7 +203,7 @@ fn span_to_html(
         class_list.push(str_w)
     }
 
-    let str_h = match text.stretch_height as u32 {
+    let str_h = match text.get_stretch_height() as u32 {
         2 => STRETCH_H_CLASSES[0],
         3 => STRETCH_H_CLASSES[1],
         4 => STRETCH_H_CLASSES[2],
@@ -213,6 +250,15 @@ fn span_to_html(
         class_list.push(bg_class);
     }
 
+    let content: String = text
+        .text
+        .chars()
+        .map(|char| match user_characters.get(&text.font, char) {
+            Some(character) => user_char_to_html(character, text),
+            None => char.to_string(),
+        })
+        .collect();
+
     (
         y,
         format!(
@@ -220,7 +266,7 @@ fn span_to_html(
             x_offset + x,
             baseline_offset,
             class_list.join(" "),
-            text.text
+            content
         ),
     )
 }
@@ -18,6 +18,7 @@
 
 use crate::image_renderer::thermal_image::ThermalImage;
 use crate::renderer::{DebugProfile, OutputRenderer, RenderOutput, Renderer};
+use thermal_parser::command_sets::CommandSet;
 use thermal_parser::context::{Context, PrintDirection, Rotation, TextJustify};
 use thermal_parser::graphics::{Image, VectorGraphic, RGBA};
 use thermal_parser::text::TextSpan;
@@ -51,6 +52,20 @@ impl ImageRenderer {
         );
         renderer.render(bytes)
     }
+
+    /// Renders bytes in another printer language to an image
+    pub fn render_with_command_set(
+        bytes: &Vec<u8>,
+        command_set: CommandSet,
+        debug_profile: Option<DebugProfile>,
+    ) -> RenderOutput<ReceiptImage> {
+        let mut child_renderer: Box<dyn OutputRenderer<_>> = Box::new(ImageRenderer::new());
+        let mut renderer = Renderer::new(
+            &mut child_renderer,
+            debug_profile.unwrap_or(DebugProfile::default()),
+        );
+        renderer.render_with_command_set(bytes, command_set)
+    }
 }
 
 /// ReceiptImage is the main output for the image renderer
@@ -206,7 +221,7 @@ impl OutputRenderer<ReceiptImage> for ImageRenderer {
 
         for span in spans {
             if let Some(_) = &span.dimensions {
-                canvas.render_span(x_offset, max_height, span);
+                canvas.render_span(x_offset, max_height, span, &context.text.user_characters);
             }
         }
     }
@@ -6,7 +6,7 @@ use fontdue::layout::CharacterData;
 use std::rc::Rc;
 use thermal_parser::context::Font;
 use thermal_parser::graphics::{Image, Rectangle, RGBA};
-use thermal_parser::text::TextSpan;
+use thermal_parser::text::{TextSpan, UserCharacter, UserCharacters};
 
 const SIZE_TO_FONT_RATIO: f32 = 1.68;
 const SIZE_TO_BASELINE_RATIO: f32 = 0.0315;
@@ -277,7 +277,44 @@ impl ThermalImage {
         Some((bytes, final_width, final_height))
     }
 
-    pub fn render_span(&mut self, x_offset: u32, max_height: u32, span: &TextSpan) {
+    //Downloaded characters fill the character cell from the top left
+    fn render_user_char(
+        character: &UserCharacter,
+        width: u32,
+        height: u32,
+        final_width: u32,
+        final_height: u32,
+        background_color: &RGBA,
+        text_color: &RGBA,
+    ) -> (Vec<RGBA>, u32, u32) {
+        let mut bytes = vec![*background_color; width as usize * height as usize];
+
+        for y in 0..character.height.min(height) {
+            for x in 0..character.width.min(width) {
+                if character.pixels[(y * character.width + x) as usize] {
+                    bytes[(y * width + x) as usize] = *text_color;
+                }
+            }
+        }
+
+        if final_width != width || final_height != height {
+            return (
+                ThermalImage::scale_bitmap(&bytes, width, height, final_width, final_height),
+                final_width,
+                final_height,
+            );
+        }
+
+        (bytes, final_width, final_height)
+    }
+
+    pub fn render_span(
+        &mut self,
+        x_offset: u32,
+        max_height: u32,
+        span: &TextSpan,
+        user_characters: &UserCharacters,
+    ) {
         if span.dimensions.is_none() {
             return;
         }
@@ -295,17 +332,48 @@ impl ThermalImage {
         }
 
         for char in span.text.chars() {
-            let char_bitmap = ThermalImage::render_char(
-                char,
-                span.base_character_width,
-                span.base_character_height,
-                span.character_width,
-                span.character_height,
-                font.clone(),
-                font_size,
-                &span.background_color,
-                &span.text_color,
-            );
+            //Taller wide characters share the baseline with the rest of the text
+            let char_height = span.char_height(char);
+            let char_y_offset = if char_height == span.character_height {
+                y_offset
+            } else {
+                ((max_height - char_height) as f32 * baseline_ratio) as u32
+            };
+
+            //Wide characters are rendered from a double width cell between their spacing
+            let (char_x, base_width, char_width) = if span.is_wide(char) {
+                let (left, _right) = span.wide_character_spacing;
+                (
+                    cur_x + left,
+                    span.base_character_width * 2,
+                    span.wide_character_width,
+                )
+            } else {
+                (cur_x, span.base_character_width, span.character_width)
+            };
+
+            let char_bitmap = match user_characters.get(&span.font, char) {
+                Some(character) => Some(ThermalImage::render_user_char(
+                    character,
+                    span.base_character_width,
+                    span.base_character_height,
+                    span.character_width,
+                    span.character_height,
+                    &span.background_color,
+                    &span.text_color,
+                )),
+                None => ThermalImage::render_char(
+                    char,
+                    base_width,
+                    span.base_character_height,
+                    char_width,
+                    char_height,
+                    font.clone(),
+                    font_size,
+                    &span.background_color,
+                    &span.text_color,
+                ),
+            };
 
             if let Some(mut bitmap) = char_bitmap {
                 if bitmap.1 == 0 || bitmap.2 == 0 {
@@ -323,8 +391,8 @@ impl ThermalImage {
                 }
 
                 self.put_pixels(
-                    cur_x,
-                    dimensions.y + y_offset,
+                    char_x,
+                    dimensions.y + char_y_offset,
                     bitmap.1,
                     bitmap.2,
                     bitmap.0,
@@ -333,7 +401,7 @@ impl ThermalImage {
                 );
             }
 
-            cur_x += span.character_width;
+            cur_x += span.char_width(char);
         }
 
         //Draw baseline
@@ -17,10 +17,13 @@
 use crate::renderer::RenderErrorKind::ChildRenderError;
 use std::{fmt, mem};
 use thermal_parser::command::{Command, CommandType, DeviceCommand};
+use thermal_parser::command_sets::{esc_pos, CommandSet};
 use thermal_parser::context::{Context, HumanReadableInterface, Rotation, TextJustify};
+use thermal_parser::diagnostic::{Diagnostic, DiagnosticCode, Severity};
 use thermal_parser::graphics::{
-    Barcode, Code2D, GraphicsCommand, Image, ImageFlow, Rectangle, VectorGraphic,
+    Barcode, Code2D, Composite, GraphicsCommand, Image, ImageFlow, Rectangle, VectorGraphic,
 };
+use thermal_parser::parser::Parser;
 use thermal_parser::text::TextSpan;
 
 #[derive(Debug, Clone, Copy)]
@@ -45,6 +48,8 @@ impl Default for DebugProfile {
 pub struct RenderOutput<Output> {
     pub output: Vec<Output>,
     pub errors: Vec<RenderError>,
+    //Problems found while parsing, followed by the ones found while rendering
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 #[derive(Debug)]
@@ -69,9 +74,15 @@ pub struct Renderer<'a, Output> {
     renderer: &'a mut Box<dyn OutputRenderer<Output>>,
     output_buffer: Vec<Output>,
     error_buffer: Vec<RenderError>,
+    diagnostic_buffer: Vec<Diagnostic>,
+    //Start offset and name of the command being processed
+    command_start: usize,
+    command_name: String,
     span_buffer: Vec<TextSpan>,
     context: Context,
     debug_profile: DebugProfile,
+    //Parses macros while a job is being rendered
+    parser: Option<Parser>,
 }
 
 impl<'a, Output> Renderer<'a, Output> {
@@ -84,8 +95,12 @@ impl<'a, Output> Renderer<'a, Output> {
             context: Context::new(),
             span_buffer: vec![],
             error_buffer: vec![],
+            diagnostic_buffer: vec![],
+            command_start: 0,
+            command_name: String::new(),
             output_buffer: vec![],
             debug_profile,
+            parser: None,
         }
     }
 
@@ -114,32 +129,104 @@ impl<'a, Output> Renderer<'a, Output> {
     }
 
     fn log_error(&mut self, kind: RenderErrorKind, description: String) {
+        let (severity, code) = match kind {
+            RenderErrorKind::UnknownCommand => (Severity::Warning, DiagnosticCode::UnknownCommand),
+            RenderErrorKind::GraphicsError => (Severity::Error, DiagnosticCode::GraphicsError),
+            RenderErrorKind::ChildRenderError => (Severity::Error, DiagnosticCode::RenderError),
+        };
+
+        self.diagnostic_buffer.push(Diagnostic {
+            byte_offset: self.command_start,
+            command_name: self.command_name.clone(),
+            severity,
+            code,
+            message: description.clone(),
+        });
+
         self.error_buffer.push(RenderError { kind, description });
     }
 
+    /// Settings that printers make with memory switches, like
+    /// Context::set_big5, are changed here before rendering
+    pub fn context_mut(&mut self) -> &mut Context {
+        &mut self.context
+    }
+
     pub fn render(&mut self, bytes: &Vec<u8>) -> RenderOutput<Output> {
+        self.render_with_command_set(bytes, esc_pos::new())
+    }
+
+    /// Renders bytes in another printer language, like command_sets::star_line
+    pub fn render_with_command_set(
+        &mut self,
+        bytes: &Vec<u8>,
+        command_set: CommandSet,
+    ) -> RenderOutput<Output> {
         self.renderer.set_debug_profile(self.debug_profile);
         self.log_debug_start("Begin Render");
 
-        let commands = thermal_parser::parse_esc_pos(bytes);
+        let mut parser = Parser::new(command_set);
+        let commands = parser.parse_bytes(bytes);
+        self.diagnostic_buffer.extend(parser.take_diagnostics());
+        self.parser = Some(parser);
 
-        for command in commands {
-            self.log_debug(&format!(
-                "{}",
-                command.handler.debug(&command, &self.context)
-            ));
-            self.process_command(&command);
-        }
+        self.process_commands(&commands, bytes);
+        self.parser = None;
 
         let mut output = vec![];
         let mut errors = vec![];
+        let mut diagnostics = vec![];
 
         mem::swap(&mut output, &mut self.output_buffer);
         mem::swap(&mut errors, &mut self.error_buffer);
+        mem::swap(&mut diagnostics, &mut self.diagnostic_buffer);
 
         self.log_debug_end("End Render");
 
-        RenderOutput { output, errors }
+        RenderOutput {
+            output,
+            errors,
+            diagnostics,
+        }
+    }
+
+    //Commands that are processed while a macro is being
+    //defined are recorded from the bytes they were parsed from
+    fn process_commands(&mut self, commands: &[Command], bytes: &[u8]) {
+        for command in commands {
+            self.command_start = command.start;
+            self.command_name = command.name.to_string();
+            self.log_debug(&command.handler.debug(command, &self.context).to_string());
+
+            let recording = self.context.macros.recording;
+            self.process_command(command);
+
+            if recording && self.context.macros.recording {
+                if let Some(command_bytes) = bytes.get(command.start..command.end) {
+                    self.context.macros.record(command_bytes);
+                }
+            }
+        }
+    }
+
+    //Macros are parsed as a job of their own, without the begin and end of the job
+    fn execute_macro(&mut self, times: u8) {
+        let mut parser = match self.parser.take() {
+            Some(parser) => parser,
+            None => return,
+        };
+
+        let bytes = self.context.macros.data.clone();
+        let commands = parser.parse_bytes(&bytes);
+        self.diagnostic_buffer.extend(parser.take_diagnostics());
+
+        let job = &commands[1..commands.len() - 1];
+
+        for _ in 0..times {
+            self.process_commands(job, &bytes);
+        }
+
+        self.parser = Some(parser);
     }
 
     //default implementation
@@ -161,6 +248,9 @@ impl<'a, Output> Renderer<'a, Output> {
             CommandType::Graphics => {
                 self.process_text();
 
+                //Graphics that set their own position do it in the context
+                command.handler.apply_context(command, &mut self.context);
+
                 let maybe_gfx = command.handler.get_graphics(command, &mut self.context);
 
                 if let Some(gfx) = maybe_gfx {
@@ -171,6 +261,9 @@ impl<'a, Output> Renderer<'a, Output> {
                         GraphicsCommand::Code2D(code_2d) => {
                             self.process_code_2d(&code_2d);
                         }
+                        GraphicsCommand::Composite(composite) => {
+                            self.process_composite(&composite);
+                        }
                         GraphicsCommand::Barcode(barcode) => {
                             self.process_barcode(&barcode);
                         }
@@ -276,7 +369,7 @@ impl<'a, Output> Renderer<'a, Output> {
 
                         //Advance the y since a page is being rendered
                         self.context.graphics.render_area.y += self.context.page_mode.page_area.h;
-                        self.context.graphics.render_area.x = 0;
+                        self.context.reset_x();
                     }
                     DeviceCommand::ChangePageArea => {
                         //This is important to make sure that we know the direction has already been altered
@@ -297,6 +390,9 @@ impl<'a, Output> Renderer<'a, Output> {
                     DeviceCommand::ClearBufferGraphics => {
                         self.context.graphics.buffer_graphics.clear();
                     }
+                    DeviceCommand::ExecuteMacro(times, _wait, _mode) => {
+                        self.execute_macro(*times);
+                    }
                     _ => {}
                 }
             }
@@ -339,10 +435,29 @@ impl<'a, Output> Renderer<'a, Output> {
         self.renderer.render_graphics(context, &graphics);
     }
 
+    fn process_composite(&mut self, composite: &Composite) {
+        self.process_code_2d(&Code2D {
+            points: composite.points.clone(),
+            width: composite.width,
+            point_width: composite.point_width,
+            point_height: composite.point_width,
+        });
+
+        //The symbol ends on its last row, move past it
+        self.context.offset_y(composite.point_width);
+
+        if let Some(text) = &composite.text {
+            self.context.offset_y(8);
+            self.collect_text(text.clone());
+            self.process_text();
+            self.context.newline(1);
+        }
+    }
+
     fn process_barcode(&mut self, barcode: &Barcode) {
         let mut graphics = vec![];
 
-        match self.context.barcode.human_readable {
+        match barcode.hri {
             HumanReadableInterface::Above | HumanReadableInterface::Both => {
                 self.collect_text(barcode.text.clone());
                 self.process_text();
@@ -379,7 +494,7 @@ impl<'a, Output> Renderer<'a, Output> {
         self.context.reset_x();
         self.context.offset_y(barcode.point_height as u32);
 
-        match self.context.barcode.human_readable {
+        match barcode.hri {
             HumanReadableInterface::Below | HumanReadableInterface::Both => {
                 self.context.offset_y(8);
                 self.collect_text(barcode.text.clone());
@@ -472,7 +587,7 @@ impl<'a, Output> Renderer<'a, Output> {
 
             //Tabs have a special behavior
             if word.text.eq("\t") {
-                let current_x = self.context.get_x();
+                let current_x = self.context.get_x().saturating_sub(self.context.get_base_x());
                 let mut current_tab_pos = 0;
                 for tab_len in &self.context.text.tabs {
                     if current_tab_pos >= current_x {
@@ -492,10 +607,7 @@ impl<'a, Output> Renderer<'a, Output> {
                 continue;
             } else if word_width > max_width {
                 //Break the word into parts for super long words
-                let mut broken = word.break_apart(
-                    (avail_width / word.character_width) as usize,
-                    (max_width / word.character_width).max(word.character_width) as usize,
-                );
+                let mut broken = word.break_apart(avail_width, max_width);
 
                 let broken_len = broken.len() - 1;
                 for (i, broke) in broken.iter_mut().enumerate() {
@@ -553,7 +665,7 @@ impl<'a, Output> Renderer<'a, Output> {
 
             for span in line {
                 line_width += span.get_width();
-                max_height = max_height.max(span.character_height);
+                max_height = max_height.max(span.get_height());
             }
 
             match justification {
@@ -2,6 +2,7 @@ use png::BitDepth;
 use std::fs::File;
 use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
+use thermal_parser::command_sets::{epl, esc_pos, star_graphic, star_line, zpl, CommandSet};
 use thermal_parser::thermal_file::parse_str;
 use thermal_renderer::html_renderer::HtmlRenderer;
 // use thermal_renderer::html_renderer::HtmlRenderer;
@@ -78,7 +79,79 @@ fn discount() {
     test_sample("discount", "thermal")
 }
 
+#[test]
+fn margins() {
+    test_sample("margins", "thermal")
+}
+
+#[test]
+fn user_characters() {
+    test_sample("user_characters", "thermal")
+}
+
+#[test]
+fn legacy_bit_images() {
+    test_sample("legacy_bit_images", "thermal")
+}
+
+#[test]
+fn macros() {
+    test_sample("macros", "thermal")
+}
+
+#[test]
+fn kanji() {
+    test_sample("kanji", "thermal")
+}
+
+#[test]
+fn kanji_mixed() {
+    test_sample("kanji_mixed", "thermal")
+}
+
+#[test]
+fn star_line() {
+    test_sample_with_command_set("star_line", "thermal", star_line::new)
+}
+
+#[test]
+fn star_graphic() {
+    test_sample_with_command_set("star_graphic", "thermal", star_graphic::new)
+}
+
+#[test]
+fn shipping_label() {
+    test_sample_with_command_set("shipping_label", "zpl", zpl::new)
+}
+
+#[test]
+fn shipping_label_epl() {
+    test_sample_with_command_set("shipping_label", "epl", epl::new)
+}
+
+#[test]
+fn malformed_bit_images() {
+    let jobs = [
+        b"\x1b@\x1d*\x02\x02\xff\xff\x1d/\x00".to_vec(),
+        b"\x1b@\x1d*\x00\x01\x1d/\x00".to_vec(),
+        b"\x1b@\x1cq\x01\x00\x00\x01\x00\x1cp\x01\x00".to_vec(),
+        b"\x1b@\x1dQ0\x00\x00\x00\x01\x00".to_vec(),
+    ];
+
+    //The images are reported instead of breaking the render
+    for bytes in &jobs {
+        let html = HtmlRenderer::render_with_command_set(bytes, esc_pos::new(), None);
+        assert_eq!(html.output.len(), 1);
+        let image = ImageRenderer::render_with_command_set(bytes, esc_pos::new(), None);
+        assert_eq!(image.output.len(), 1);
+    }
+}
+
 fn test_sample(name: &str, ext: &str) {
+    test_sample_with_command_set(name, ext, esc_pos::new)
+}
+
+fn test_sample_with_command_set(name: &str, ext: &str, command_set: fn() -> CommandSet) {
     let sample_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
         .join("..")
         .join("sample_files")
@@ -115,20 +188,28 @@ fn test_sample(name: &str, ext: &str) {
 
     render_image(
         &bytes,
+        command_set(),
         format!("{}.png", img_out.to_str().unwrap().to_string()),
         name.to_string(),
         debug_profile,
     );
     render_html(
         &bytes,
+        command_set(),
         format!("{}.html", html_out.to_str().unwrap().to_string()),
         name.to_string(),
         debug_profile,
     );
 }
 
-fn render_html(bytes: &Vec<u8>, out_path: String, name: String, debug_profile: DebugProfile) {
-    let renders = HtmlRenderer::render(bytes, Some(debug_profile));
+fn render_html(
+    bytes: &Vec<u8>,
+    command_set: CommandSet,
+    out_path: String,
+    name: String,
+    debug_profile: DebugProfile,
+) {
+    let renders = HtmlRenderer::render_with_command_set(bytes, command_set, Some(debug_profile));
 
     if let Some(render) = renders.output.first() {
         let path = Path::new(&out_path);
@@ -150,8 +231,14 @@ fn render_html(bytes: &Vec<u8>, out_path: String, name: String, debug_profile: D
     }
 }
 
-fn render_image(bytes: &Vec<u8>, out_path: String, name: String, debug_profile: DebugProfile) {
-    let renders = ImageRenderer::render(bytes, Some(debug_profile));
+fn render_image(
+    bytes: &Vec<u8>,
+    command_set: CommandSet,
+    out_path: String,
+    name: String,
+    debug_profile: DebugProfile,
+) {
+    let renders = ImageRenderer::render_with_command_set(bytes, command_set, Some(debug_profile));
 
     if let Some(render) = renders.output.first() {
         save_image(&render.bytes, render.width, render.height, out_path);